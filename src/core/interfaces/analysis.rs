@@ -0,0 +1,588 @@
+//! Analysis tools for polifunctions.
+//!
+//! This module provides utilities for sampling polifunctions over a grid of
+//! inputs and analyzing the structure of the resulting outputs.
+
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, Interval, TrustLevel};
+use super::interval_valued::IntervalValuedPolifunction;
+use super::domains::RealInterval;
+
+/// Output intervals of an interval-valued polifunction sampled over a grid of inputs
+#[derive(Debug)]
+pub struct IntervalSweep<X, T> {
+    /// The sampled inputs, in sweep order
+    pub inputs: Vec<X>,
+    /// The result for each input (same length and order as `inputs`)
+    pub results: Vec<Result<Interval<T>, PolifunctionError>>,
+}
+
+/// Evaluate an interval-valued polifunction at every point of a grid
+///
+/// With `TrustLevel::Trusted` the points go through `evaluate_trusted`, so a
+/// grid built from the domain is not checked against it again.
+pub fn sweep_intervals<P>(p: &P, grid: &[<P::Domain as Domain>::Element], trust: TrustLevel)
+    -> IntervalSweep<<P::Domain as Domain>::Element, <P::Codomain as Codomain>::Element>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone,
+    <P::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    IntervalSweep {
+        inputs: grid.to_vec(),
+        results: grid.iter().map(|x| interval_at(p, x, trust)).collect(),
+    }
+}
+
+/// Output interval of `p` at `x`, skipping the domain check when trusted
+fn interval_at<P>(p: &P, x: &<P::Domain as Domain>::Element, trust: TrustLevel)
+    -> Result<Interval<<P::Codomain as Codomain>::Element>, PolifunctionError>
+where
+    P: IntervalValuedPolifunction,
+    <P::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    if trust == TrustLevel::Checked {
+        return p.value_interval(x);
+    }
+    
+    match p.evaluate_trusted(x)? {
+        PolifunctionValue::Interval(interval) => Ok(interval),
+        // A degenerate interval may have been normalized to Single
+        PolifunctionValue::Single(value) => Interval::new(value.clone(), value, true, true),
+        _ => p.value_interval(x),
+    }
+}
+
+/// Number of points reported in [`SweepTimings::slowest`]
+const SLOWEST_POINTS: usize = 10;
+
+/// Options for a timed sweep
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SweepOptions {
+    /// Record a point as timed out if evaluating it takes longer than this
+    pub abort_point_after: Option<Duration>,
+    /// Whether the grid points are checked against the domain
+    pub trust: TrustLevel,
+}
+
+/// Per-point evaluation times of a sweep
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepTimings {
+    /// Median evaluation time
+    pub median: Duration,
+    /// 99th percentile evaluation time
+    pub p99: Duration,
+    /// The slowest points as (input index, evaluation time), slowest first
+    pub slowest: Vec<(usize, Duration)>,
+}
+
+impl SweepTimings {
+    /// Summarize the evaluation time of every point, in sweep order
+    fn from_durations(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        
+        // Nearest-rank percentile
+        let percentile = |q: f64| {
+            if sorted.is_empty() {
+                Duration::ZERO
+            } else {
+                let rank = (q * sorted.len() as f64).ceil() as usize;
+                sorted[rank.clamp(1, sorted.len()) - 1]
+            }
+        };
+        
+        let mut slowest: Vec<(usize, Duration)> = durations.iter().copied().enumerate().collect();
+        slowest.sort_by_key(|&(idx, duration)| (std::cmp::Reverse(duration), idx));
+        slowest.truncate(SLOWEST_POINTS);
+        
+        Self {
+            median: percentile(0.5),
+            p99: percentile(0.99),
+            slowest,
+        }
+    }
+}
+
+impl Display for SweepTimings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "median {:?}, p99 {:?}", self.median, self.p99)?;
+        for (idx, duration) in &self.slowest {
+            writeln!(f, "  point {}: {:?}", idx, duration)?;
+        }
+        Ok(())
+    }
+}
+
+/// A sweep together with the evaluation times of its points
+#[derive(Debug)]
+pub struct TimedSweep<X, T> {
+    /// The sweep results
+    pub sweep: IntervalSweep<X, T>,
+    /// Timing statistics over all points
+    pub timings: SweepTimings,
+}
+
+/// Evaluate an interval-valued polifunction at every point of a grid, timing each point
+///
+/// Polifunction evaluation cannot be interrupted, so a point that exceeds
+/// `abort_point_after` still runs to completion; its result is then replaced by
+/// `PolifunctionError::Timeout` and the sweep continues with the next point.
+pub fn sweep_intervals_timed<P>(p: &P, grid: &[<P::Domain as Domain>::Element], options: SweepOptions)
+    -> TimedSweep<<P::Domain as Domain>::Element, <P::Codomain as Codomain>::Element>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone,
+    <P::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    let mut results = Vec::with_capacity(grid.len());
+    let mut durations = Vec::with_capacity(grid.len());
+    
+    for x in grid {
+        let start = Instant::now();
+        let result = interval_at(p, x, options.trust);
+        let elapsed = start.elapsed();
+        
+        let timed_out = options.abort_point_after.is_some_and(|limit| elapsed > limit);
+        results.push(if timed_out { Err(PolifunctionError::Timeout) } else { result });
+        durations.push(elapsed);
+    }
+    
+    TimedSweep {
+        sweep: IntervalSweep {
+            inputs: grid.to_vec(),
+            results,
+        },
+        timings: SweepTimings::from_durations(&durations),
+    }
+}
+
+/// How intervals are required to overlap to belong to the same segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Each interval overlaps its immediate predecessor
+    Chain,
+    /// Every interval overlaps the running intersection of the segment
+    /// (i.e. all intervals in the segment overlap pairwise)
+    Mutual,
+}
+
+/// A run of consecutive sweep entries whose output intervals overlap
+#[derive(Debug, Clone)]
+pub struct Segment<T> {
+    /// Index of the first sweep entry in the segment
+    pub start_idx: usize,
+    /// Index of the last sweep entry in the segment (inclusive)
+    pub end_idx: usize,
+    /// Smallest interval containing every interval in the segment
+    pub hull: Interval<T>,
+}
+
+/// Group consecutive sweep entries into segments of overlapping output intervals
+///
+/// A new segment starts whenever the next interval fails the overlap test of the
+/// given policy. Error entries in the sweep always end the current segment and
+/// are not part of any segment.
+pub fn segment_by_overlap<X, T>(sweep: &IntervalSweep<X, T>, policy: OverlapPolicy) -> Vec<Segment<T>>
+where
+    T: PartialOrd + Clone,
+{
+    let mut segments = Vec::new();
+    // Current segment and the interval its next member must overlap
+    let mut current: Option<(Segment<T>, Interval<T>)> = None;
+    
+    for (idx, result) in sweep.results.iter().enumerate() {
+        let interval = match result {
+            Ok(interval) => interval,
+            Err(_) => {
+                if let Some((segment, _)) = current.take() {
+                    segments.push(segment);
+                }
+                continue;
+            }
+        };
+        
+        current = match current.take() {
+            Some((mut segment, reference)) => match reference.intersect(interval) {
+                Some(overlap) => {
+                    segment.end_idx = idx;
+                    // Overlapping intervals always have comparable endpoints
+                    if let Some(hull) = segment.hull.hull(interval) {
+                        segment.hull = hull;
+                    }
+                    let reference = match policy {
+                        OverlapPolicy::Chain => interval.clone(),
+                        OverlapPolicy::Mutual => overlap,
+                    };
+                    Some((segment, reference))
+                },
+                None => {
+                    segments.push(segment);
+                    Some((Segment { start_idx: idx, end_idx: idx, hull: interval.clone() }, interval.clone()))
+                }
+            },
+            None => Some((Segment { start_idx: idx, end_idx: idx, hull: interval.clone() }, interval.clone())),
+        };
+    }
+    
+    if let Some((segment, _)) = current {
+        segments.push(segment);
+    }
+    
+    segments
+}
+
+/// Sweep an interval-valued polifunction over a grid and segment the result
+pub fn segment_polifunction<P>(p: &P, grid: &[<P::Domain as Domain>::Element], policy: OverlapPolicy)
+    -> Vec<Segment<<P::Codomain as Codomain>::Element>>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone,
+    <P::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    segment_by_overlap(&sweep_intervals(p, grid, TrustLevel::Checked), policy)
+}
+
+/// An input at which a membership decision is fragile
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FragileInput {
+    /// The input
+    pub input: f64,
+    /// How fragile the decision is, from 0.0 (robust) to 1.0 (most fragile)
+    pub severity: f64,
+}
+
+/// Fragile inputs found by a scan, most severe first
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FragilityReport {
+    /// The fragile inputs, sorted by decreasing severity
+    pub entries: Vec<FragileInput>,
+}
+
+impl FragilityReport {
+    /// Build a report from unsorted entries
+    fn from_entries(mut entries: Vec<FragileInput>) -> Self {
+        entries.sort_by(|a, b| {
+            b.severity.partial_cmp(&a.severity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.input.partial_cmp(&b.input).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        Self { entries }
+    }
+    
+    /// Check whether no fragile inputs were found
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Display for FragilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} fragile input(s)", self.entries.len())?;
+        for entry in &self.entries {
+            writeln!(f, "  x = {}: severity {:.3}", entry.input, entry.severity)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find inputs where perturbing the input by `perturbation` flips `contains_value`
+///
+/// For each input, `contains_value(input - perturbation, value)` and
+/// `contains_value(input + perturbation, value)` are compared with the
+/// unperturbed answer. Severity is the fraction of the two perturbed answers
+/// that disagree; a perturbed input that cannot be evaluated counts as a
+/// disagreement. Inputs that cannot be evaluated themselves are skipped.
+pub fn fragility_scan<P>(p: &P, inputs: &[f64], value: &<P::Codomain as Codomain>::Element, perturbation: f64)
+    -> FragilityReport
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+{
+    let mut entries = Vec::new();
+    
+    for &input in inputs {
+        let baseline = match p.contains_value(&input, value) {
+            Ok(contained) => contained,
+            Err(_) => continue,
+        };
+        
+        let flips = [input - perturbation, input + perturbation]
+            .iter()
+            .filter(|perturbed| !matches!(p.contains_value(perturbed, value), Ok(c) if c == baseline))
+            .count();
+        
+        if flips > 0 {
+            entries.push(FragileInput {
+                input,
+                severity: flips as f64 / 2.0,
+            });
+        }
+    }
+    
+    FragilityReport::from_entries(entries)
+}
+
+/// Report how close `value` lies to the nearest endpoint of each output interval
+///
+/// The distance to the nearest endpoint is measured as a fraction of the interval
+/// width, so severity is `1 - 2 * distance / width` clamped to [0, 1]: a value on
+/// an endpoint has severity 1.0 and a value at the midpoint (or far outside) 0.0.
+/// Inputs with zero severity, degenerate intervals and evaluation errors are omitted.
+pub fn interval_boundary_proximity<P>(p: &P, inputs: &[f64], value: f64) -> FragilityReport
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    let mut entries = Vec::new();
+    
+    for &input in inputs {
+        let interval = match p.value_interval(&input) {
+            Ok(interval) => interval,
+            Err(_) => continue,
+        };
+        
+        let width = interval.upper - interval.lower;
+        if width.is_nan() || width <= 0.0 {
+            continue;
+        }
+        
+        let distance = (value - interval.lower).abs().min((interval.upper - value).abs());
+        let severity = (1.0 - 2.0 * distance / width).clamp(0.0, 1.0);
+        if severity > 0.0 {
+            entries.push(FragileInput { input, severity });
+        }
+    }
+    
+    FragilityReport::from_entries(entries)
+}
+
+/// Find the roots of a real-valued polifunction on a sampled interval
+///
+/// The interval is sampled at `samples` evenly spaced points (including both
+/// ends), and every sign change between consecutive samples is refined by
+/// bisection until the bracket is narrower than `tol`. Samples that are
+/// exactly zero are reported as they are. Samples outside the domain of `p`
+/// are skipped, so no root is searched for across such a gap, and a bracket
+/// whose bisection runs into the edge of the domain is dropped. Values other
+/// than `Single` are an `InvalidOperation`.
+pub fn find_roots<P>(p: &P, domain: &RealInterval, samples: usize, tol: f64) -> Result<Vec<f64>, PolifunctionError>
+where
+    P: PolifunctionBase,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    if samples < 2 || tol.is_nan() || tol <= 0.0 {
+        return Err(PolifunctionError::InvalidOperation);
+    }
+    
+    // None marks a point outside the domain of `p`
+    let sample = |x: f64| -> Result<Option<f64>, PolifunctionError> {
+        match p.evaluate(&x) {
+            Ok(PolifunctionValue::Single(y)) => Ok(Some(y)),
+            Ok(_) => Err(PolifunctionError::InvalidOperation),
+            Err(e) if e.is_domain_error() => Ok(None),
+            Err(e) => Err(e),
+        }
+    };
+    
+    let step = (domain.upper - domain.lower) / (samples - 1) as f64;
+    let mut roots = Vec::new();
+    let mut previous: Option<(f64, f64)> = None;
+    
+    for i in 0..samples {
+        let x = if i == samples - 1 { domain.upper } else { domain.lower + step * i as f64 };
+        let current = sample(x)?.map(|y| (x, y));
+        
+        if let Some((x, y)) = current {
+            if y == 0.0 {
+                roots.push(x);
+            } else if let Some((x0, y0)) = previous {
+                if y0 != 0.0 && (y0 < 0.0) != (y < 0.0) {
+                    if let Some(root) = bisect_root(&sample, x0, x, y0 < 0.0, tol)? {
+                        roots.push(root);
+                    }
+                }
+            }
+        }
+        
+        previous = current;
+    }
+    
+    Ok(roots)
+}
+
+/// Narrow a sign-change bracket down to `tol`, returning its midpoint
+///
+/// Returns None if a point of the bracket turns out to be outside the domain.
+fn bisect_root(sample: &impl Fn(f64) -> Result<Option<f64>, PolifunctionError>,
+               mut low: f64, mut high: f64, negative_at_low: bool, tol: f64)
+    -> Result<Option<f64>, PolifunctionError> {
+    while high - low > tol {
+        let mid = low + (high - low) / 2.0;
+        // The bracket cannot shrink any further in floating point
+        if mid <= low || mid >= high {
+            break;
+        }
+        
+        let y = match sample(mid)? {
+            Some(y) => y,
+            None => return Ok(None),
+        };
+        
+        if y == 0.0 {
+            return Ok(Some(mid));
+        }
+        if (y < 0.0) == negative_at_low {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    
+    Ok(Some(low + (high - low) / 2.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::RealLine;
+    use crate::core::interfaces::interval_valued::BasicIntervalValuedPolifunction;
+    use crate::core::interfaces::operations::LiftedPolifunction;
+    
+    fn closed(lower: f64, upper: f64) -> Interval<f64> {
+        Interval::new(lower, upper, true, true).unwrap()
+    }
+    
+    /// The band [x - 1, x + 1]
+    fn band() -> BasicIntervalValuedPolifunction<RealLine, RealLine> {
+        BasicIntervalValuedPolifunction::new(|x: &f64| Interval::new(x - 1.0, x + 1.0, true, true), RealLine, RealLine)
+    }
+    
+    fn grid() -> Vec<f64> {
+        (0..=10).map(f64::from).collect()
+    }
+    
+    #[test]
+    fn fragility_scan_flags_grid_points_next_to_the_crossings() {
+        // 5.5 is inside the band for x in [4.5, 6.5]
+        let report = fragility_scan(&band(), &grid(), &5.5, 0.6);
+        
+        let flagged: Vec<(f64, f64)> = report.entries.iter().map(|e| (e.input, e.severity)).collect();
+        assert_eq!(flagged, vec![(4.0, 0.5), (5.0, 0.5), (6.0, 0.5), (7.0, 0.5)]);
+        
+        // A perturbation too small to reach a crossing flags nothing
+        assert!(fragility_scan(&band(), &grid(), &5.5, 0.25).is_empty());
+    }
+    
+    #[test]
+    fn boundary_proximity_is_sorted_by_severity() {
+        let report = interval_boundary_proximity(&band(), &grid(), 5.25);
+        
+        let flagged: Vec<(f64, f64)> = report.entries.iter().map(|e| (e.input, e.severity)).collect();
+        assert_eq!(flagged, vec![(4.0, 0.75), (6.0, 0.75), (5.0, 0.25), (7.0, 0.25)]);
+        assert_eq!(
+            report.to_string(),
+            "4 fragile input(s)\n  x = 4: severity 0.750\n  x = 6: severity 0.750\n  x = 5: severity 0.250\n  x = 7: severity 0.250\n",
+        );
+    }
+    
+    fn hand_made_sweep() -> IntervalSweep<usize, f64> {
+        IntervalSweep {
+            inputs: (0..6).collect(),
+            results: vec![
+                Ok(closed(0.0, 2.0)),
+                Ok(closed(1.0, 3.0)),
+                Ok(closed(2.5, 4.0)),
+                Ok(closed(10.0, 11.0)),
+                Err(PolifunctionError::ComputationError),
+                Ok(closed(10.5, 12.0)),
+            ],
+        }
+    }
+    
+    fn spans(segments: &[Segment<f64>]) -> Vec<(usize, usize, f64, f64)> {
+        segments.iter().map(|s| (s.start_idx, s.end_idx, s.hull.lower, s.hull.upper)).collect()
+    }
+    
+    #[test]
+    fn chain_segments_follow_neighbouring_overlaps() {
+        let segments = segment_by_overlap(&hand_made_sweep(), OverlapPolicy::Chain);
+        assert_eq!(spans(&segments), vec![(0, 2, 0.0, 4.0), (3, 3, 10.0, 11.0), (5, 5, 10.5, 12.0)]);
+    }
+    
+    #[test]
+    fn mutual_segments_require_a_common_overlap() {
+        // [2.5, 4] overlaps [1, 3] but not [0, 2], so it starts a new segment
+        let segments = segment_by_overlap(&hand_made_sweep(), OverlapPolicy::Mutual);
+        assert_eq!(
+            spans(&segments),
+            vec![(0, 1, 0.0, 3.0), (2, 2, 2.5, 4.0), (3, 3, 10.0, 11.0), (5, 5, 10.5, 12.0)],
+        );
+    }
+    
+    #[test]
+    fn timed_sweep_reports_slow_points_as_timeouts() {
+        let slow_at_two = BasicIntervalValuedPolifunction::new(
+            |x: &f64| {
+                if *x == 2.0 {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Interval::new(*x, *x, true, true)
+            },
+            RealLine,
+            RealLine,
+        );
+        let options = SweepOptions { abort_point_after: Some(Duration::from_millis(20)), ..SweepOptions::default() };
+        let timed = sweep_intervals_timed(&slow_at_two, &[0.0, 1.0, 2.0, 3.0], options);
+        
+        assert_eq!(timed.sweep.results[2], Err(PolifunctionError::Timeout));
+        assert!(timed.sweep.results.iter().enumerate().all(|(i, r)| i == 2 || r.is_ok()));
+        assert_eq!(timed.timings.slowest[0].0, 2);
+        assert!(timed.timings.slowest[0].1 >= Duration::from_millis(50));
+        assert_eq!(timed.timings.slowest.len(), 4);
+        
+        // Without a limit every point keeps its result
+        let untimed = sweep_intervals_timed(&slow_at_two, &[2.0], SweepOptions::default());
+        assert_eq!(untimed.sweep.results[0], Ok(closed(2.0, 2.0)));
+    }
+    
+    #[test]
+    fn trusted_sweeps_skip_the_domain_check() {
+        let band = BasicIntervalValuedPolifunction::new(
+            |x: &f64| Interval::new(*x, x + 1.0, true, true),
+            RealInterval::closed(0.0, 10.0),
+            RealLine,
+        );
+        let grid = [1.0, 20.0];
+        
+        let checked = sweep_intervals(&band, &grid, TrustLevel::Checked);
+        assert_eq!(checked.results[0], Ok(closed(1.0, 2.0)));
+        assert!(checked.results[1].as_ref().unwrap_err().is_domain_error());
+        
+        // 20 is outside [0, 10], but the caller vouched for the grid
+        let trusted = sweep_intervals(&band, &grid, TrustLevel::Trusted);
+        assert_eq!(trusted.results, vec![Ok(closed(1.0, 2.0)), Ok(closed(20.0, 21.0))]);
+        
+        let options = SweepOptions { trust: TrustLevel::Trusted, ..SweepOptions::default() };
+        let timed = sweep_intervals_timed(&band, &grid, options);
+        assert_eq!(timed.sweep.results, trusted.results);
+    }
+    
+    #[test]
+    fn find_roots_refines_sign_changes() {
+        let parabola = LiftedPolifunction::new(|x: &f64| Ok(x * x - 2.0), RealLine, RealLine);
+        let roots = find_roots(&parabola, &RealInterval::closed(-3.0, 3.0), 7, 1e-9).unwrap();
+        
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] + 2f64.sqrt()).abs() < 1e-9);
+        assert!((roots[1] - 2f64.sqrt()).abs() < 1e-9);
+        assert_eq!(find_roots(&parabola, &RealInterval::closed(-3.0, 3.0), 1, 1e-9), Err(PolifunctionError::InvalidOperation));
+    }
+}
@@ -0,0 +1,375 @@
+//! Builtin polifunctions and a registry to look them up by name.
+//!
+//! This module provides constructors for common multivalued inverses (square
+//! root branches, the inverse of floor and arcsin branches) and a
+//! [`PolifunctionRegistry`] that instantiates them from a name and numeric
+//! parameters. Every registry builds its own entries; there is no global state.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Interval, IntervalSet};
+use super::domains::Unbounded;
+use super::dynamic::BoxedPolifunction;
+
+/// Real polifunction given by a domain predicate and a value function
+struct Builtin<F> {
+    /// Whether an input is in the domain
+    domain: fn(&f64) -> bool,
+    /// Values at an input of the domain
+    values: F,
+}
+
+impl<F> PolifunctionBase for Builtin<F>
+where
+    F: Fn(f64) -> PolifunctionValue<f64>,
+{
+    type Domain = Unbounded<f64>;
+    type Codomain = Unbounded<f64>;
+
+    fn evaluate(&self, input: &f64) -> Result<PolifunctionValue<f64>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+
+        Ok((self.values)(*input))
+    }
+
+    fn in_domain(&self, input: &f64) -> bool {
+        (self.domain)(input)
+    }
+}
+
+/// Collect real values into a [`PolifunctionValue::MultiInterval`] of points
+///
+/// f64 values cannot be collected into a `Set`, so the branches of a real
+/// polifunction are returned as degenerate intervals; equal values are merged.
+fn points(values: impl IntoIterator<Item = f64>) -> PolifunctionValue<f64> {
+    let intervals = values.into_iter().map(|v| Interval {
+        lower: v,
+        upper: v,
+        lower_inclusive: true,
+        upper_inclusive: true,
+    });
+    PolifunctionValue::MultiInterval(IntervalSet::new(intervals))
+}
+
+/// Both branches of the square root: x -> {-√x, √x} on [0, ∞)
+pub fn sqrt_branches() -> BoxedPolifunction<f64, f64> {
+    BoxedPolifunction::new(Builtin {
+        domain: |x| x.is_finite() && *x >= 0.0,
+        values: |x: f64| points([-x.sqrt(), x.sqrt()]),
+    })
+}
+
+/// Inverse of floor: an integer n maps to [n, n + 1)
+pub fn floor_inverse() -> BoxedPolifunction<f64, f64> {
+    BoxedPolifunction::new(Builtin {
+        domain: |x| x.is_finite() && x.fract() == 0.0,
+        values: |n: f64| PolifunctionValue::Interval(Interval {
+            lower: n,
+            upper: n + 1.0,
+            lower_inclusive: true,
+            upper_inclusive: false,
+        }),
+    })
+}
+
+/// Branches of arcsin over `periods` periods: x -> {asin(x) + 2πk, π - asin(x) + 2πk} for k in 0..periods
+///
+/// The domain is [-1, 1]. Returns an error if `periods` is zero.
+pub fn arcsin_branches(periods: u32) -> Result<BoxedPolifunction<f64, f64>, PolifunctionError> {
+    if periods == 0 {
+        return Err(PolifunctionError::Other("arcsin_branches needs at least one period".to_string()));
+    }
+
+    Ok(BoxedPolifunction::new(Builtin {
+        domain: |x| (-1.0..=1.0).contains(x),
+        values: move |x: f64| {
+            let principal = x.asin();
+            points((0..periods).flat_map(|k| {
+                let shift = 2.0 * PI * k as f64;
+                [principal + shift, PI - principal + shift]
+            }))
+        },
+    }))
+}
+
+/// Type of a builtin parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterKind {
+    /// Any finite real number
+    Real,
+    /// A whole number, passed as f64
+    Integer,
+}
+
+/// Name, type, default and bounds of a builtin parameter
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterSchema {
+    pub name: String,
+    pub kind: ParameterKind,
+    pub default: f64,
+    /// Smallest accepted value, if any
+    pub minimum: Option<f64>,
+    /// Largest accepted value, if any
+    pub maximum: Option<f64>,
+}
+
+impl ParameterSchema {
+    /// Check a value against the schema, naming the parameter in the error
+    fn validate(&self, builtin: &str, value: f64) -> Result<f64, PolifunctionError> {
+        let invalid = |reason: String| {
+            PolifunctionError::Other(format!("Invalid parameter '{}' for '{}': {}", self.name, builtin, reason))
+        };
+
+        if !value.is_finite() {
+            return Err(invalid(format!("expected a finite number, got {}", value)));
+        }
+        if self.kind == ParameterKind::Integer && value.fract() != 0.0 {
+            return Err(invalid(format!("expected an integer, got {}", value)));
+        }
+        if let Some(minimum) = self.minimum.filter(|minimum| value < *minimum) {
+            return Err(invalid(format!("must be at least {}, got {}", minimum, value)));
+        }
+        if let Some(maximum) = self.maximum.filter(|maximum| value > *maximum) {
+            return Err(invalid(format!("must be at most {}, got {}", maximum, value)));
+        }
+        Ok(value)
+    }
+}
+
+/// Constructor of a builtin from its validated parameters, defaults filled in
+pub type BuiltinFactory = fn(&HashMap<String, f64>) -> Result<BoxedPolifunction<f64, f64>, PolifunctionError>;
+
+/// A named builtin with its parameter schema and constructor
+#[derive(Debug, Clone)]
+pub struct BuiltinEntry {
+    /// Canonical name the builtin is registered under
+    pub name: String,
+    pub description: String,
+    pub parameters: Vec<ParameterSchema>,
+    pub factory: BuiltinFactory,
+}
+
+impl BuiltinEntry {
+    /// Validate `params` against the schema and build the polifunction
+    ///
+    /// Missing parameters take their defaults. Unknown parameters and values
+    /// that do not fit the schema are rejected with an error naming them.
+    pub fn instantiate(&self, params: &HashMap<String, f64>) -> Result<BoxedPolifunction<f64, f64>, PolifunctionError> {
+        if let Some(unknown) = params.keys().find(|name| !self.parameters.iter().any(|p| &p.name == *name)) {
+            return Err(PolifunctionError::Other(format!("Unknown parameter '{}' for '{}'", unknown, self.name)));
+        }
+
+        let mut resolved = HashMap::with_capacity(self.parameters.len());
+        for parameter in &self.parameters {
+            let value = params.get(&parameter.name).copied().unwrap_or(parameter.default);
+            resolved.insert(parameter.name.clone(), parameter.validate(&self.name, value)?);
+        }
+        (self.factory)(&resolved)
+    }
+}
+
+/// Catalog of the builtin polifunctions
+///
+/// | Name              | Parameters                                 |
+/// |-------------------|--------------------------------------------|
+/// | `sqrt_branches`   | none                                       |
+/// | `floor_inverse`   | none                                       |
+/// | `arcsin_branches` | `periods`: integer in 1..=1000, default 1  |
+pub struct BuiltinCatalog;
+
+impl BuiltinCatalog {
+    /// List every builtin, freshly built on each call
+    pub fn list() -> Vec<BuiltinEntry> {
+        vec![
+            BuiltinEntry {
+                name: "sqrt_branches".to_string(),
+                description: "Both branches of the square root on [0, ∞)".to_string(),
+                parameters: Vec::new(),
+                factory: |_| Ok(sqrt_branches()),
+            },
+            BuiltinEntry {
+                name: "floor_inverse".to_string(),
+                description: "Inverse of floor, mapping an integer n to [n, n + 1)".to_string(),
+                parameters: Vec::new(),
+                factory: |_| Ok(floor_inverse()),
+            },
+            BuiltinEntry {
+                name: "arcsin_branches".to_string(),
+                description: "Branches of arcsin on [-1, 1] over a number of periods".to_string(),
+                parameters: vec![ParameterSchema {
+                    name: "periods".to_string(),
+                    kind: ParameterKind::Integer,
+                    default: 1.0,
+                    minimum: Some(1.0),
+                    // Keeps the conversion to u32 exact and the output sets small
+                    maximum: Some(1000.0),
+                }],
+                factory: |params| arcsin_branches(params["periods"] as u32),
+            },
+        ]
+    }
+}
+
+/// Registry of named real polifunction constructors
+#[derive(Debug, Clone, Default)]
+pub struct PolifunctionRegistry {
+    entries: HashMap<String, BuiltinEntry>,
+}
+
+impl PolifunctionRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding every entry of [`BuiltinCatalog::list`]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        for entry in BuiltinCatalog::list() {
+            registry.register(entry);
+        }
+        registry
+    }
+
+    /// Register an entry under its name, returning the entry it replaces
+    pub fn register(&mut self, entry: BuiltinEntry) -> Option<BuiltinEntry> {
+        self.entries.insert(entry.name.clone(), entry)
+    }
+
+    /// Get the entry registered under a name
+    pub fn get(&self, name: &str) -> Option<&BuiltinEntry> {
+        self.entries.get(name)
+    }
+
+    /// Iterate over the registered names
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Build the polifunction registered under `name` from its parameters
+    ///
+    /// See [`BuiltinEntry::instantiate`]. Returns an error if no entry has the name.
+    pub fn instantiate(&self, name: &str, params: &HashMap<String, f64>) -> Result<BoxedPolifunction<f64, f64>, PolifunctionError> {
+        let entry = self.get(name).ok_or_else(|| PolifunctionError::Other(format!("Unknown polifunction '{}'", name)))?;
+        entry.instantiate(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|(name, value)| (name.to_string(), *value)).collect()
+    }
+
+    /// The points of a MultiInterval value, which must all be degenerate
+    fn point_values(value: PolifunctionValue<f64>) -> Vec<f64> {
+        let PolifunctionValue::MultiInterval(set) = value else {
+            panic!("expected points, got {:?}", value);
+        };
+        set.intervals().iter().map(|i| {
+            assert_eq!(i.lower, i.upper);
+            i.lower
+        }).collect()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len(), "{:?} vs {:?}", actual, expected);
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{:?} vs {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn with_builtins_registers_the_catalog() {
+        let registry = PolifunctionRegistry::with_builtins();
+        let mut names: Vec<_> = registry.names().collect();
+        names.sort();
+        assert_eq!(names, vec!["arcsin_branches", "floor_inverse", "sqrt_branches"]);
+        assert!(PolifunctionRegistry::new().names().next().is_none());
+
+        let periods = &registry.get("arcsin_branches").unwrap().parameters[0];
+        assert_eq!((periods.name.as_str(), periods.kind, periods.default), ("periods", ParameterKind::Integer, 1.0));
+    }
+
+    #[test]
+    fn instantiate_sqrt_branches_by_name() {
+        let registry = PolifunctionRegistry::with_builtins();
+        let sqrt = registry.instantiate("sqrt_branches", &HashMap::new()).unwrap();
+        assert_eq!(point_values(sqrt.evaluate(&4.0).unwrap()), vec![-2.0, 2.0]);
+        assert_eq!(point_values(sqrt.evaluate(&0.0).unwrap()), vec![0.0]);
+        assert_eq!(sqrt.evaluate(&-1.0), Err(PolifunctionError::DomainError));
+    }
+
+    #[test]
+    fn instantiate_arcsin_branches_with_and_without_overrides() {
+        let registry = PolifunctionRegistry::with_builtins();
+
+        let one_period = registry.instantiate("arcsin_branches", &HashMap::new()).unwrap();
+        assert_close(&point_values(one_period.evaluate(&0.5).unwrap()), &[PI / 6.0, 5.0 * PI / 6.0]);
+        assert_close(&point_values(one_period.evaluate(&1.0).unwrap()), &[PI / 2.0]);
+        assert!(!one_period.in_domain(&1.5));
+
+        let two_periods = registry.instantiate("arcsin_branches", &params(&[("periods", 2.0)])).unwrap();
+        assert_close(
+            &point_values(two_periods.evaluate(&0.5).unwrap()),
+            &[PI / 6.0, 5.0 * PI / 6.0, 13.0 * PI / 6.0, 17.0 * PI / 6.0],
+        );
+    }
+
+    #[test]
+    fn floor_inverse_maps_integers_to_half_open_intervals() {
+        let inverse = PolifunctionRegistry::with_builtins().instantiate("floor_inverse", &HashMap::new()).unwrap();
+        assert_eq!(inverse.evaluate(&-2.0), Ok(PolifunctionValue::Interval(Interval::new(-2.0, -1.0, true, false).unwrap())));
+        assert!(inverse.evaluate(&0.5).unwrap_err().is_domain_error());
+    }
+
+    #[test]
+    fn instantiate_names_the_bad_parameter() {
+        let registry = PolifunctionRegistry::with_builtins();
+        let message = |name: &str, pairs: &[(&str, f64)]| match registry.instantiate(name, &params(pairs)) {
+            Err(PolifunctionError::Other(message)) => message,
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(_) => panic!("{} with {:?} was accepted", name, pairs),
+        };
+
+        assert_eq!(message("arcsin_branches", &[("periods", 1.5)]), "Invalid parameter 'periods' for 'arcsin_branches': expected an integer, got 1.5");
+        assert_eq!(message("arcsin_branches", &[("periods", 0.0)]), "Invalid parameter 'periods' for 'arcsin_branches': must be at least 1, got 0");
+        assert_eq!(message("arcsin_branches", &[("periods", 1001.0)]), "Invalid parameter 'periods' for 'arcsin_branches': must be at most 1000, got 1001");
+        assert_eq!(message("arcsin_branches", &[("periods", 1e10)]), "Invalid parameter 'periods' for 'arcsin_branches': must be at most 1000, got 10000000000");
+        assert!(registry.instantiate("arcsin_branches", &params(&[("periods", 1000.0)])).is_ok());
+        assert_eq!(
+            message("arcsin_branches", &[("periods", f64::INFINITY)]),
+            "Invalid parameter 'periods' for 'arcsin_branches': expected a finite number, got inf",
+        );
+        assert_eq!(message("sqrt_branches", &[("periods", 2.0)]), "Unknown parameter 'periods' for 'sqrt_branches'");
+        assert_eq!(message("tan_branches", &[]), "Unknown polifunction 'tan_branches'");
+    }
+
+    #[test]
+    fn registries_are_independent() {
+        let mut custom = PolifunctionRegistry::with_builtins();
+        let mut entry = custom.get("sqrt_branches").unwrap().clone();
+        entry.name = "square_root".to_string();
+        assert!(custom.register(entry).is_none());
+
+        assert!(custom.instantiate("square_root", &HashMap::new()).is_ok());
+        assert!(PolifunctionRegistry::with_builtins().get("square_root").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parameter_schemas_serialize() {
+        let entry = BuiltinCatalog::list().into_iter().find(|entry| entry.name == "arcsin_branches").unwrap();
+        assert_eq!(
+            serde_json::to_string(&entry.parameters).unwrap(),
+            r#"[{"name":"periods","kind":"Integer","default":1.0,"minimum":1.0,"maximum":1000.0}]"#,
+        );
+    }
+}
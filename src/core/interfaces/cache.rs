@@ -0,0 +1,698 @@
+//! In-memory caching of polifunction evaluations.
+//!
+//! This module provides wrappers that memoize evaluation results, so
+//! repeated queries with the same input only evaluate the wrapped
+//! polifunction once.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Sub;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, Interval};
+use super::set_valued::SetValuedPolifunction;
+use super::interval_valued::IntervalValuedPolifunction;
+
+/// Map from inputs to the cached evaluation results of a polifunction
+type CacheMap<P> = HashMap<
+    <<P as PolifunctionBase>::Domain as Domain>::Element,
+    PolifunctionValue<<<P as PolifunctionBase>::Codomain as Codomain>::Element>,
+>;
+
+/// Polifunction wrapper that memoizes evaluation results
+///
+/// Results are keyed by the input. Errors are never cached, so a failed
+/// evaluation is retried on the next query.
+pub struct CachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// The wrapped polifunction
+    inner: P,
+    /// Cached evaluation results by input
+    cache: RefCell<CacheMap<P>>,
+}
+
+impl<P> CachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// Create a new cached polifunction with an empty cache
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+    
+    /// Remove every cached result
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+    
+    /// Number of cached results
+    pub fn cache_len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+}
+
+impl<P> PolifunctionBase for CachedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        if let Some(value) = self.cache.borrow().get(input) {
+            return Ok(value.clone());
+        }
+        
+        // The borrow is released before delegating, so a re-entrant inner
+        // polifunction cannot trigger a double borrow
+        let value = self.inner.evaluate(input)?;
+        self.cache.borrow_mut().insert(input.clone(), value.clone());
+        Ok(value)
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+impl<P> SetValuedPolifunction for CachedPolifunction<P>
+where
+    P: SetValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Set(set) => Ok(set),
+            // A normalized one-element set is cached as Single
+            PolifunctionValue::Single(value) => Ok(std::iter::once(value).collect()),
+            _ => self.inner.value_set(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.contains(value))
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.len())
+    }
+}
+
+impl<P> IntervalValuedPolifunction for CachedPolifunction<P>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Interval(interval) => Ok(interval),
+            // A normalized degenerate interval is cached as Single
+            PolifunctionValue::Single(value) => Interval::new(value.clone(), value, true, true),
+            _ => self.inner.value_interval(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        let interval = self.value_interval(input)?;
+        Ok(interval.width())
+    }
+}
+
+/// Thread-safe variant of [`CachedPolifunction`]
+///
+/// The cache is guarded by a mutex, so the wrapper is `Send + Sync` whenever
+/// the wrapped polifunction and its values are. The lock is not held while
+/// the wrapped polifunction is evaluated, so concurrent misses on the same
+/// input may evaluate it more than once.
+pub struct SyncCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// The wrapped polifunction
+    inner: P,
+    /// Cached evaluation results by input
+    cache: Mutex<CacheMap<P>>,
+}
+
+impl<P> SyncCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// Create a new cached polifunction with an empty cache
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// Remove every cached result
+    pub fn clear_cache(&self) {
+        self.lock_cache().clear();
+    }
+    
+    /// Number of cached results
+    pub fn cache_len(&self) -> usize {
+        self.lock_cache().len()
+    }
+    
+    /// Lock the cache, recovering it if another thread panicked while holding it
+    fn lock_cache(&self) -> MutexGuard<'_, CacheMap<P>> {
+        // Entries are inserted whole, so a poisoned cache is still consistent
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<P> PolifunctionBase for SyncCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        if let Some(value) = self.lock_cache().get(input) {
+            return Ok(value.clone());
+        }
+        
+        // The lock is released before delegating, so a re-entrant inner
+        // polifunction cannot deadlock
+        let value = self.inner.evaluate(input)?;
+        self.lock_cache().insert(input.clone(), value.clone());
+        Ok(value)
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+impl<P> SetValuedPolifunction for SyncCachedPolifunction<P>
+where
+    P: SetValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Set(set) => Ok(set),
+            // A normalized one-element set is cached as Single
+            PolifunctionValue::Single(value) => Ok(std::iter::once(value).collect()),
+            _ => self.inner.value_set(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.contains(value))
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.len())
+    }
+}
+
+impl<P> IntervalValuedPolifunction for SyncCachedPolifunction<P>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Interval(interval) => Ok(interval),
+            // A normalized degenerate interval is cached as Single
+            PolifunctionValue::Single(value) => Interval::new(value.clone(), value, true, true),
+            _ => self.inner.value_interval(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        let interval = self.value_interval(input)?;
+        Ok(interval.width())
+    }
+}
+
+/// Entries of a least-recently-used cache, ordered by last use
+struct LruEntries<K, V> {
+    /// Cached values with the tick of their last use
+    entries: HashMap<K, (V, u64)>,
+    /// Keys by the tick of their last use, oldest first
+    recency: BTreeMap<u64, K>,
+    /// Tick assigned to the next use
+    next_tick: u64,
+}
+
+impl<K: Clone + Hash + Eq, V: Clone> LruEntries<K, V> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+        }
+    }
+    
+    /// Look up a value and mark it as most recently used
+    fn get(&mut self, key: &K) -> Option<V> {
+        let tick = self.next_tick;
+        let (value, last_used) = self.entries.get_mut(key)?;
+        let key = self.recency.remove(last_used)?;
+        *last_used = tick;
+        let value = value.clone();
+        self.recency.insert(tick, key);
+        self.next_tick += 1;
+        Some(value)
+    }
+    
+    /// Store a value as most recently used, evicting the oldest entry if full
+    fn insert(&mut self, key: K, value: V, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        
+        if let Some((_, last_used)) = self.entries.remove(&key) {
+            self.recency.remove(&last_used);
+        } else if self.entries.len() >= capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.recency.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+    }
+    
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+    
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Least-recently-used entries holding the evaluation results of a polifunction
+type LruMap<P> = LruEntries<
+    <<P as PolifunctionBase>::Domain as Domain>::Element,
+    PolifunctionValue<<<P as PolifunctionBase>::Codomain as Codomain>::Element>,
+>;
+
+/// Polifunction wrapper that memoizes a bounded number of evaluation results
+///
+/// Once `capacity` results are cached, storing a new one evicts the least
+/// recently used. Errors are never cached.
+pub struct LruCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// The wrapped polifunction
+    inner: P,
+    /// Maximum number of cached results
+    capacity: usize,
+    /// Cached evaluation results by input
+    cache: RefCell<LruMap<P>>,
+    /// Number of evaluations answered from the cache
+    hits: Cell<usize>,
+    /// Number of evaluations delegated to the wrapped polifunction
+    misses: Cell<usize>,
+}
+
+impl<P> LruCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone,
+{
+    /// Create a new cached polifunction holding at most `capacity` results
+    pub fn new(inner: P, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            cache: RefCell::new(LruEntries::new()),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+    
+    /// Maximum number of cached results
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Number of evaluations answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+    
+    /// Number of evaluations that had to call the wrapped polifunction
+    pub fn misses(&self) -> usize {
+        self.misses.get()
+    }
+    
+    /// Remove every cached result
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+    
+    /// Number of cached results
+    pub fn cache_len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+}
+
+impl<P> PolifunctionBase for LruCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        // Bind the lookup so the borrow ends before delegating
+        let cached = self.cache.borrow_mut().get(input);
+        if let Some(value) = cached {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(value);
+        }
+        
+        self.misses.set(self.misses.get() + 1);
+        let value = self.inner.evaluate(input)?;
+        self.cache.borrow_mut().insert(input.clone(), value.clone(), self.capacity);
+        Ok(value)
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+impl<P> SetValuedPolifunction for LruCachedPolifunction<P>
+where
+    P: SetValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Set(set) => Ok(set),
+            // A normalized one-element set is cached as Single
+            PolifunctionValue::Single(value) => Ok(std::iter::once(value).collect()),
+            _ => self.inner.value_set(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.contains(value))
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.len())
+    }
+}
+
+impl<P> IntervalValuedPolifunction for LruCachedPolifunction<P>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        match self.evaluate(input)? {
+            PolifunctionValue::Interval(interval) => Ok(interval),
+            // A normalized degenerate interval is cached as Single
+            PolifunctionValue::Single(value) => Interval::new(value.clone(), value, true, true),
+            _ => self.inner.value_interval(input),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        let interval = self.value_interval(input)?;
+        Ok(interval.width())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    use crate::core::interfaces::operations::{lift_to_set, to_interval, LiftedPolifunction};
+    
+    /// Squares on [-10, 10) that fail for negative inputs, counting every call
+    fn counting_square(calls: &Arc<AtomicUsize>)
+        -> LiftedPolifunction<impl Fn(&i64) -> Result<i64, PolifunctionError>, IntegerRange, Unbounded<i64>> {
+        let calls = Arc::clone(calls);
+        LiftedPolifunction::new(
+            move |x: &i64| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                if *x < 0 { Err(PolifunctionError::ComputationError) } else { Ok(x * x) }
+            },
+            IntegerRange::new(-10, 10).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    fn call_count(calls: &Arc<AtomicUsize>) -> usize {
+        calls.load(Ordering::Relaxed)
+    }
+    
+    #[test]
+    fn cached_polifunction_evaluates_each_input_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = CachedPolifunction::new(counting_square(&calls));
+        
+        for _ in 0..3 {
+            assert_eq!(cached.evaluate(&3), Ok(PolifunctionValue::Single(9)));
+            assert_eq!(cached.evaluate(&4), Ok(PolifunctionValue::Single(16)));
+        }
+        assert_eq!(call_count(&calls), 2);
+        assert_eq!(cached.cache_len(), 2);
+        
+        cached.clear_cache();
+        assert_eq!(cached.evaluate(&3), Ok(PolifunctionValue::Single(9)));
+        assert_eq!(call_count(&calls), 3);
+    }
+    
+    #[test]
+    fn cached_polifunction_retries_errors() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = CachedPolifunction::new(counting_square(&calls));
+        
+        assert_eq!(cached.evaluate(&-1), Err(PolifunctionError::ComputationError));
+        assert_eq!(cached.evaluate(&-1), Err(PolifunctionError::ComputationError));
+        assert_eq!(call_count(&calls), 2);
+        
+        // Inputs outside the domain never reach the wrapped polifunction
+        assert_eq!(cached.evaluate(&10), Err(PolifunctionError::DomainError));
+        assert_eq!(call_count(&calls), 2);
+        assert_eq!(cached.cache_len(), 0);
+    }
+    
+    /// Set-valued squares whose `evaluate` reports the one-element set as Single
+    fn counting_square_set(calls: &Arc<AtomicUsize>)
+        -> impl SetValuedPolifunction<Domain = IntegerRange, Codomain = Unbounded<i64>> {
+        let calls = Arc::clone(calls);
+        lift_to_set(
+            move |x: &i64| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Ok(x * x)
+            },
+            IntegerRange::new(-10, 10).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    #[test]
+    fn cached_value_set_and_value_interval_reuse_normalized_values() {
+        let nine: HashSet<i64> = [9].into_iter().collect();
+        
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = CachedPolifunction::new(counting_square_set(&calls));
+        let sync_cached = SyncCachedPolifunction::new(counting_square_set(&calls));
+        let lru_cached = LruCachedPolifunction::new(counting_square_set(&calls), 2);
+        for _ in 0..3 {
+            assert_eq!(cached.value_set(&3), Ok(nine.clone()));
+            assert_eq!(sync_cached.value_set(&3), Ok(nine.clone()));
+            assert_eq!(lru_cached.value_set(&-3), Ok(nine.clone()));
+        }
+        assert_eq!(call_count(&calls), 3);
+        assert_eq!((lru_cached.hits(), lru_cached.misses()), (2, 1));
+        
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = CachedPolifunction::new(to_interval(counting_square_set(&calls)));
+        for _ in 0..3 {
+            assert_eq!(cached.value_interval(&2), Interval::new(4, 4, true, true));
+        }
+        assert_eq!(call_count(&calls), 1);
+    }
+    
+    #[test]
+    fn sync_cached_polifunction_evaluates_each_input_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = Arc::new(SyncCachedPolifunction::new(counting_square(&calls)));
+        
+        assert_eq!(cached.evaluate(&5), Ok(PolifunctionValue::Single(25)));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let cached = Arc::clone(&cached);
+                std::thread::spawn(move || cached.evaluate(&5))
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Ok(PolifunctionValue::Single(25)));
+        }
+        assert_eq!(call_count(&calls), 1);
+        
+        assert_eq!(cached.evaluate(&-2), Err(PolifunctionError::ComputationError));
+        assert_eq!(cached.evaluate(&-2), Err(PolifunctionError::ComputationError));
+        assert_eq!(call_count(&calls), 3);
+        assert_eq!(cached.cache_len(), 1);
+    }
+    
+    #[test]
+    fn lru_with_zero_capacity_never_caches() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = LruCachedPolifunction::new(counting_square(&calls), 0);
+        
+        for _ in 0..3 {
+            assert_eq!(cached.evaluate(&2), Ok(PolifunctionValue::Single(4)));
+        }
+        assert_eq!(call_count(&calls), 3);
+        assert_eq!((cached.hits(), cached.misses()), (0, 3));
+        assert_eq!(cached.cache_len(), 0);
+    }
+    
+    #[test]
+    fn lru_with_unit_capacity_keeps_the_last_input() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = LruCachedPolifunction::new(counting_square(&calls), 1);
+        
+        for x in [1, 1, 2, 1, 1] {
+            cached.evaluate(&x).unwrap();
+        }
+        assert_eq!(call_count(&calls), 3);
+        assert_eq!((cached.hits(), cached.misses()), (2, 3));
+        assert_eq!(cached.cache_len(), 1);
+    }
+    
+    #[test]
+    fn lru_evicts_the_least_recently_used_input() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = LruCachedPolifunction::new(counting_square(&calls), 2);
+        
+        // Reading 1 again makes 2 the least recently used, so 3 evicts it
+        for x in [1, 2, 1, 3] {
+            cached.evaluate(&x).unwrap();
+        }
+        assert_eq!((cached.hits(), cached.misses()), (1, 3));
+        
+        cached.evaluate(&1).unwrap();
+        assert_eq!((cached.hits(), cached.misses()), (2, 3));
+        cached.evaluate(&2).unwrap();
+        assert_eq!((cached.hits(), cached.misses()), (2, 4));
+        
+        // Storing 2 evicted 3, the least recently used after reading 1
+        cached.evaluate(&3).unwrap();
+        assert_eq!((cached.hits(), cached.misses()), (2, 5));
+        assert_eq!(call_count(&calls), 5);
+        assert_eq!(cached.cache_len(), 2);
+    }
+    
+    #[test]
+    fn lru_counts_failed_evaluations_as_misses() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = LruCachedPolifunction::new(counting_square(&calls), 4);
+        
+        assert!(cached.evaluate(&-3).is_err());
+        assert!(cached.evaluate(&-3).is_err());
+        assert_eq!((cached.hits(), cached.misses()), (0, 2));
+        assert_eq!(cached.cache_len(), 0);
+        assert_eq!(cached.capacity(), 4);
+    }
+}
@@ -0,0 +1,349 @@
+//! Persistent on-disk caching of polifunction evaluations.
+//!
+//! This module provides a wrapper that stores evaluation results as individual
+//! files in a directory, so identical evaluations can be reused across runs.
+
+#![cfg(feature = "serde")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain};
+
+/// Counter used to give concurrent writers distinct temporary file names
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A single cached evaluation as stored on disk
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<V> {
+    /// Serialized input, used to detect hash collisions
+    input: String,
+    /// Model version the value was computed with
+    version: String,
+    /// The cached evaluation result
+    value: V,
+}
+
+/// Limit applied when pruning a cache directory
+#[derive(Debug, Clone, Copy)]
+pub enum PruneLimit {
+    /// Keep at most this many entries, removing the oldest first
+    MaxEntries(usize),
+    /// Remove entries last written longer ago than this
+    MaxAge(Duration),
+}
+
+/// Polifunction wrapper that persists evaluation results to a directory
+///
+/// Results are keyed by a hash of the serialized input and a model version
+/// string, so bumping the version invalidates every previously cached value.
+/// Cache files that cannot be read or parsed are ignored and rewritten.
+/// Errors are never cached.
+pub struct DiskCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// The wrapped polifunction
+    inner: P,
+    /// Directory holding one file per cached evaluation
+    directory: PathBuf,
+    /// Model version mixed into every cache key
+    version: String,
+    /// Number of evaluations answered from disk
+    hits: AtomicUsize,
+    /// Number of evaluations delegated to the inner polifunction
+    misses: AtomicUsize,
+}
+
+impl<P> DiskCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// Create a new disk-cached polifunction, creating the directory if needed
+    pub fn new(inner: P, directory: impl AsRef<Path>, version: impl Into<String>) -> Result<Self, PolifunctionError> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)
+            .map_err(|e| PolifunctionError::Other(format!("Cannot create cache directory: {}", e)))?;
+        
+        Ok(Self {
+            inner,
+            directory,
+            version: version.into(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        })
+    }
+    
+    /// Number of evaluations answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+    
+    /// Number of evaluations that had to call the inner polifunction
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+    
+    /// Remove cache files according to the given limit
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune(&self, limit: PruneLimit) -> Result<usize, PolifunctionError> {
+        let io_error = |e: std::io::Error| PolifunctionError::Other(format!("Cannot prune cache directory: {}", e));
+        
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let modified = fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                entries.push((path, modified));
+            }
+        }
+        
+        let to_remove: Vec<PathBuf> = match limit {
+            PruneLimit::MaxEntries(max_entries) => {
+                // Newest first, then drop everything past the limit
+                entries.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+                entries.into_iter().skip(max_entries).map(|(path, _)| path).collect()
+            },
+            PruneLimit::MaxAge(max_age) => {
+                let now = SystemTime::now();
+                entries.into_iter()
+                    .filter(|(_, modified)| now.duration_since(*modified).is_ok_and(|age| age > max_age))
+                    .map(|(path, _)| path)
+                    .collect()
+            },
+        };
+        
+        let mut removed = 0;
+        for path in to_remove {
+            // Another process may have pruned the same file already
+            if fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+        
+        Ok(removed)
+    }
+    
+    /// Path of the cache file for a serialized input
+    fn entry_path(&self, input: &str) -> PathBuf {
+        let key = stable_hash(&[input.as_bytes(), &[0], self.version.as_bytes()]);
+        self.directory.join(format!("{:016x}.json", key))
+    }
+}
+
+impl<P> PolifunctionBase for DiskCachedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Domain as Domain>::Element: Serialize,
+    PolifunctionValue<<P::Codomain as Codomain>::Element>: Serialize + DeserializeOwned,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        let serialized_input = serde_json::to_string(input)
+            .map_err(|e| PolifunctionError::Other(format!("Cannot serialize input: {}", e)))?;
+        let path = self.entry_path(&serialized_input);
+        
+        // Unreadable, corrupt or colliding entries are treated as misses
+        let existing = fs::read_to_string(&path).ok();
+        if let Some(contents) = &existing {
+            if let Ok(entry) = serde_json::from_str::<CacheEntry<PolifunctionValue<_>>>(contents) {
+                if entry.input == serialized_input && entry.version == self.version {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(entry.value);
+                }
+            }
+        }
+        
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = self.inner.evaluate(input)?;
+        
+        // Replace a broken entry; otherwise only write if no other wrapper
+        // has stored this result in the meantime
+        if existing.is_some() || !path.exists() {
+            let entry = CacheEntry {
+                input: serialized_input,
+                version: self.version.clone(),
+                value: &value,
+            };
+            if let Ok(contents) = serde_json::to_string(&entry) {
+                // Failing to write only costs a future recomputation
+                let _ = write_atomically(&path, &contents);
+            }
+        }
+        
+        Ok(value)
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+/// Write a file by writing a temporary sibling and renaming it into place
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_path = path.with_extension(format!("tmp-{}-{}", std::process::id(), counter));
+    
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// 64-bit FNV-1a hash, stable across runs and toolchain versions
+fn stable_hash(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    use crate::core::interfaces::operations::LiftedPolifunction;
+    
+    /// Empty directory under the system temp dir, unique to this process and test
+    struct TempDir(PathBuf);
+    
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("polifunctions-disk-cache-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            Self(path)
+        }
+        
+        /// Paths of the cache files currently in the directory
+        fn entries(&self) -> Vec<PathBuf> {
+            fs::read_dir(&self.0)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        }
+    }
+    
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+    
+    /// Squares on [0, 100), counting every call
+    fn counting_square(calls: &Arc<AtomicUsize>)
+        -> LiftedPolifunction<impl Fn(&i64) -> Result<i64, PolifunctionError>, IntegerRange, Unbounded<i64>> {
+        let calls = Arc::clone(calls);
+        LiftedPolifunction::new(
+            move |x: &i64| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Ok(x * x)
+            },
+            IntegerRange::new(0, 100).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    #[test]
+    fn results_survive_a_new_wrapper() {
+        let dir = TempDir::new("round-trip");
+        let calls = Arc::new(AtomicUsize::new(0));
+        
+        let first = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        assert_eq!(first.evaluate(&7), Ok(PolifunctionValue::Single(49)));
+        assert_eq!((first.hits(), first.misses()), (0, 1));
+        assert_eq!(dir.entries().len(), 1);
+        
+        let second = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        assert_eq!(second.evaluate(&7), Ok(PolifunctionValue::Single(49)));
+        assert_eq!((second.hits(), second.misses()), (1, 0));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+    
+    #[test]
+    fn changing_the_version_invalidates_results() {
+        let dir = TempDir::new("version");
+        let calls = Arc::new(AtomicUsize::new(0));
+        
+        let old = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        old.evaluate(&3).unwrap();
+        
+        let new = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v2").unwrap();
+        assert_eq!(new.evaluate(&3), Ok(PolifunctionValue::Single(9)));
+        assert_eq!((new.hits(), new.misses()), (0, 1));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        
+        // Both versions keep their own entry
+        assert_eq!(dir.entries().len(), 2);
+        assert_eq!(old.evaluate(&3), Ok(PolifunctionValue::Single(9)));
+        assert_eq!((old.hits(), old.misses()), (1, 1));
+    }
+    
+    #[test]
+    fn corrupt_entries_are_recomputed_and_rewritten() {
+        let dir = TempDir::new("corrupt");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        cached.evaluate(&5).unwrap();
+        
+        let entries = dir.entries();
+        assert_eq!(entries.len(), 1);
+        fs::write(&entries[0], "{ not json").unwrap();
+        
+        assert_eq!(cached.evaluate(&5), Ok(PolifunctionValue::Single(25)));
+        assert_eq!((cached.hits(), cached.misses()), (0, 2));
+        
+        // The broken entry was replaced
+        assert_eq!(cached.evaluate(&5), Ok(PolifunctionValue::Single(25)));
+        assert_eq!((cached.hits(), cached.misses()), (1, 2));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+    
+    #[test]
+    fn missing_entries_are_recomputed() {
+        let dir = TempDir::new("missing");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        cached.evaluate(&8).unwrap();
+        
+        for path in dir.entries() {
+            fs::remove_file(path).unwrap();
+        }
+        
+        assert_eq!(cached.evaluate(&8), Ok(PolifunctionValue::Single(64)));
+        assert_eq!((cached.hits(), cached.misses()), (0, 2));
+        assert_eq!(dir.entries().len(), 1);
+    }
+    
+    #[test]
+    fn prune_keeps_at_most_the_given_number_of_entries() {
+        let dir = TempDir::new("prune");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cached = DiskCachedPolifunction::new(counting_square(&calls), &dir.0, "v1").unwrap();
+        for x in 0..4 {
+            cached.evaluate(&x).unwrap();
+        }
+        
+        assert_eq!(cached.prune(PruneLimit::MaxEntries(1)), Ok(3));
+        assert_eq!(dir.entries().len(), 1);
+        assert_eq!(cached.prune(PruneLimit::MaxAge(Duration::from_secs(3600))), Ok(0));
+    }
+}
@@ -0,0 +1,295 @@
+//! Distribution-valued polifunctions implementation.
+//!
+//! This module provides traits and implementations for polifunctions
+//! that map inputs to probability distributions over output values.
+
+use std::hash::Hash;
+use std::ops::Add;
+use std::sync::Arc;
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, ProbabilityDistribution};
+
+/// Trait for distribution-valued polifunctions
+pub trait DistributionValuedPolifunction: PolifunctionBase {
+    /// Get the probability distribution of values at the given input
+    fn value_distribution(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<ProbabilityDistribution<<Self::Codomain as Codomain>::Element>, PolifunctionError>;
+    
+    /// Get the probability of a specific value in the output for a given input
+    ///
+    /// Values outside the support of the distribution have probability 0.0.
+    fn probability_of(&self, input: &<Self::Domain as Domain>::Element,
+                      value: &<Self::Codomain as Codomain>::Element)
+        -> Result<f64, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: PartialEq,
+    {
+        let distribution = self.value_distribution(input)?;
+        Ok(distribution.probability(value))
+    }
+}
+
+/// Mapping function of a [`BasicDistributionValuedPolifunction`], stored behind an `Arc`
+pub type DistributionMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<ProbabilityDistribution<<C as Codomain>::Element>, PolifunctionError>;
+
+/// Mapping function of a [`BasicDistributionValuedPolifunction`] that can be shared between threads
+pub type SyncDistributionMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<ProbabilityDistribution<<C as Codomain>::Element>, PolifunctionError> + Send + Sync;
+
+/// Basic implementation of a distribution-valued polifunction
+///
+/// The mapping function is kept behind an `Arc`, so clones share it.
+pub struct BasicDistributionValuedPolifunction<D, C, F: ?Sized = DistributionMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Function that maps inputs to probability distributions over outputs
+    mapping_function: Arc<F>,
+    /// Domain of the function
+    domain: D,
+    /// Codomain of the function
+    codomain: C,
+}
+
+impl<D, C, F: ?Sized> Clone for BasicDistributionValuedPolifunction<D, C, F>
+where
+    D: Domain + Clone,
+    C: Codomain + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            mapping_function: Arc::clone(&self.mapping_function),
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+        }
+    }
+}
+
+/// The mapping function is shown as a placeholder
+impl<D, C, F: ?Sized> std::fmt::Debug for BasicDistributionValuedPolifunction<D, C, F>
+where
+    D: Domain + std::fmt::Debug,
+    C: Codomain + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicDistributionValuedPolifunction")
+            .field("mapping_function", &format_args!("<function>"))
+            .field("domain", &self.domain)
+            .field("codomain", &self.codomain)
+            .finish()
+    }
+}
+
+impl<D, C> BasicDistributionValuedPolifunction<D, C>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Create a new distribution-valued polifunction with the given mapping function
+    pub fn new(
+        mapping_function: impl Fn(&D::Element) -> Result<ProbabilityDistribution<C::Element>, PolifunctionError> + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> BasicDistributionValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    F: Fn(&D::Element) -> Result<ProbabilityDistribution<C::Element>, PolifunctionError>,
+{
+    /// Create a new distribution-valued polifunction that keeps the concrete type of the mapping function
+    ///
+    /// The result is `Send + Sync` whenever the function, domain and codomain are.
+    pub fn from_fn(mapping_function: F, domain: D, codomain: C) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C> BasicDistributionValuedPolifunction<D, C, SyncDistributionMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Create a new distribution-valued polifunction whose mapping function can be shared between threads
+    ///
+    /// The result is `Send + Sync` whenever the domain and codomain are.
+    pub fn new_sync(
+        mapping_function: impl Fn(&D::Element) -> Result<ProbabilityDistribution<C::Element>, PolifunctionError> + Send + Sync + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> PolifunctionBase for BasicDistributionValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: std::fmt::Debug,
+    F: Fn(&D::Element) -> Result<ProbabilityDistribution<C::Element>, PolifunctionError> + ?Sized,
+{
+    type Domain = D;
+    type Codomain = C;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
+        }
+        
+        let distribution = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::Distribution(distribution))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.domain.contains(input)
+    }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let distribution = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::Distribution(distribution))
+    }
+}
+
+impl<D, C, F> DistributionValuedPolifunction for BasicDistributionValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: std::fmt::Debug,
+    F: Fn(&D::Element) -> Result<ProbabilityDistribution<C::Element>, PolifunctionError> + ?Sized,
+{
+    fn value_distribution(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<ProbabilityDistribution<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
+        }
+        
+        (self.mapping_function)(input)
+    }
+}
+
+/// Sum of two independent distribution-valued polifunctions
+///
+/// At each input the result is the convolution of the two distributions, i.e.
+/// the distribution of the sum of independent draws from both operands.
+pub struct SumDistributionPolifunction<P1, P2>
+where
+    P1: DistributionValuedPolifunction,
+    P2: DistributionValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    p1: P1,
+    p2: P2,
+}
+
+impl<P1, P2> SumDistributionPolifunction<P1, P2>
+where
+    P1: DistributionValuedPolifunction,
+    P2: DistributionValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    /// Create a new sum of two distribution-valued polifunctions
+    pub fn new(p1: P1, p2: P2) -> Self {
+        Self { p1, p2 }
+    }
+}
+
+impl<P1, P2> PolifunctionBase for SumDistributionPolifunction<P1, P2>
+where
+    P1: DistributionValuedPolifunction,
+    P2: DistributionValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: Add<Output = <P1::Codomain as Codomain>::Element> + Clone + Eq + Hash,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        let distribution = self.value_distribution(input)?;
+        Ok(PolifunctionValue::Distribution(distribution))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        // The input must be in the domain of both polifunctions
+        self.p1.in_domain(input) && self.p2.in_domain(input)
+    }
+}
+
+impl<P1, P2> DistributionValuedPolifunction for SumDistributionPolifunction<P1, P2>
+where
+    P1: DistributionValuedPolifunction,
+    P2: DistributionValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: Add<Output = <P1::Codomain as Codomain>::Element> + Clone + Eq + Hash,
+{
+    fn value_distribution(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<ProbabilityDistribution<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let distribution1 = self.p1.value_distribution(input)?;
+        let distribution2 = self.p2.value_distribution(input)?;
+        
+        Ok(distribution1.convolve(&distribution2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    
+    /// A fair coin between `x` and `x + 1` on `domain`
+    fn coin(domain: IntegerRange) -> BasicDistributionValuedPolifunction<IntegerRange, Unbounded<i64>> {
+        BasicDistributionValuedPolifunction::new(
+            |x: &i64| ProbabilityDistribution::new(vec![(*x, 0.5), (x + 1, 0.5)]),
+            domain,
+            Unbounded::new(),
+        )
+    }
+    
+    #[test]
+    fn probability_of_values_outside_the_support_is_zero() {
+        let p = coin(IntegerRange::new(0, 5).unwrap());
+        assert_eq!(p.probability_of(&2, &3), Ok(0.5));
+        assert_eq!(p.probability_of(&2, &4), Ok(0.0));
+        assert_eq!(p.evaluate(&7), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn sum_convolves_the_distributions() {
+        let sum = SumDistributionPolifunction::new(coin(IntegerRange::new(0, 5).unwrap()), coin(IntegerRange::new(0, 5).unwrap()));
+        
+        // x + x, x + x + 1 twice, and x + x + 2
+        let distribution = sum.value_distribution(&1).unwrap();
+        assert_eq!(distribution.pairs(), &[(2, 0.25), (3, 0.5), (4, 0.25)]);
+        assert_eq!(sum.probability_of(&1, &3), Ok(0.5));
+        assert_eq!(sum.evaluate(&1), Ok(PolifunctionValue::Distribution(distribution)));
+    }
+    
+    #[test]
+    fn sum_is_defined_where_both_operands_are() {
+        let sum = SumDistributionPolifunction::new(coin(IntegerRange::new(0, 5).unwrap()), coin(IntegerRange::new(3, 10).unwrap()));
+        assert!(sum.in_domain(&3) && sum.in_domain(&4));
+        assert!(!sum.in_domain(&2) && !sum.in_domain(&5));
+        assert_eq!(sum.evaluate(&2), Err(PolifunctionError::DomainError));
+        assert_eq!(sum.evaluate(&7), Err(PolifunctionError::DomainError));
+        assert!(sum.evaluate(&4).is_ok());
+    }
+}
@@ -0,0 +1,547 @@
+//! Ready-made domains and codomains.
+//!
+//! This module provides `Domain` and `Codomain` implementations for common sets
+//! of values, so polifunctions can be constructed without hand-rolling a domain
+//! or codomain type.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use super::polifunction::{Domain, Codomain, FiniteDomain, PolifunctionError};
+
+/// Implement `Codomain` for a type by delegating to its `Domain` implementation
+///
+/// Generic parameters and their bounds go in brackets before the type:
+/// `codomain_from_domain!([T: Hash + Eq] FiniteSet<T>);`
+#[macro_export]
+macro_rules! codomain_from_domain {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> $crate::core::interfaces::polifunction::Codomain for $ty {
+            type Element = <$ty as $crate::core::interfaces::polifunction::Domain>::Element;
+            
+            fn contains(&self, element: &Self::Element) -> bool {
+                <$ty as $crate::core::interfaces::polifunction::Domain>::contains(self, element)
+            }
+        }
+    };
+    ($ty:ty) => {
+        $crate::codomain_from_domain!([] $ty);
+    };
+}
+
+/// All finite real numbers (NaN and infinities are rejected)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealLine;
+
+impl Domain for RealLine {
+    type Element = f64;
+    
+    fn contains(&self, element: &f64) -> bool {
+        element.is_finite()
+    }
+}
+
+codomain_from_domain!(RealLine);
+
+/// An interval of real numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealInterval {
+    pub lower: f64,
+    pub upper: f64,
+    pub lower_inclusive: bool,
+    pub upper_inclusive: bool,
+}
+
+impl RealInterval {
+    /// Create a new closed interval [lower, upper]
+    pub fn closed(lower: f64, upper: f64) -> Self {
+        Self {
+            lower,
+            upper,
+            lower_inclusive: true,
+            upper_inclusive: true,
+        }
+    }
+    
+    /// Create a new open interval (lower, upper)
+    pub fn open(lower: f64, upper: f64) -> Self {
+        Self {
+            lower,
+            upper,
+            lower_inclusive: false,
+            upper_inclusive: false,
+        }
+    }
+}
+
+impl Domain for RealInterval {
+    type Element = f64;
+    
+    fn contains(&self, element: &f64) -> bool {
+        let above_lower = if self.lower_inclusive { *element >= self.lower } else { *element > self.lower };
+        let below_upper = if self.upper_inclusive { *element <= self.upper } else { *element < self.upper };
+        // NaN fails both comparisons and is never contained
+        above_lower && below_upper
+    }
+}
+
+codomain_from_domain!(RealInterval);
+
+/// A closed interval of real numbers [lo, hi]
+///
+/// With the `serde` feature, deserialization checks the bounds like
+/// [`ClosedRealInterval::new`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ClosedRealIntervalRepr"))]
+pub struct ClosedRealInterval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+/// Unvalidated serialized form of a [`ClosedRealInterval`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ClosedRealIntervalRepr {
+    lo: f64,
+    hi: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ClosedRealIntervalRepr> for ClosedRealInterval {
+    type Error = PolifunctionError;
+    
+    fn try_from(repr: ClosedRealIntervalRepr) -> Result<Self, Self::Error> {
+        ClosedRealInterval::new(repr.lo, repr.hi)
+    }
+}
+
+impl ClosedRealInterval {
+    /// Create a new closed interval, checking that `lo <= hi`
+    pub fn new(lo: f64, hi: f64) -> Result<Self, PolifunctionError> {
+        if lo.is_nan() || hi.is_nan() || lo > hi {
+            return Err(PolifunctionError::Other(format!("Invalid interval bounds: {} > {}", lo, hi)));
+        }
+        
+        Ok(Self { lo, hi })
+    }
+}
+
+impl Domain for ClosedRealInterval {
+    type Element = f64;
+    
+    fn contains(&self, element: &f64) -> bool {
+        *element >= self.lo && *element <= self.hi
+    }
+}
+
+codomain_from_domain!(ClosedRealInterval);
+
+/// A half-open range of integers [start, end)
+///
+/// With the `serde` feature, deserialization checks the bounds like
+/// [`IntegerRange::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "IntegerRangeRepr"))]
+pub struct IntegerRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Unvalidated serialized form of an [`IntegerRange`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct IntegerRangeRepr {
+    start: i64,
+    end: i64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<IntegerRangeRepr> for IntegerRange {
+    type Error = PolifunctionError;
+    
+    fn try_from(repr: IntegerRangeRepr) -> Result<Self, Self::Error> {
+        IntegerRange::new(repr.start, repr.end)
+    }
+}
+
+impl IntegerRange {
+    /// Create a new integer range [start, end), checking that `start <= end`
+    pub fn new(start: i64, end: i64) -> Result<Self, PolifunctionError> {
+        if start > end {
+            return Err(PolifunctionError::Other(format!("Invalid range bounds: {} > {}", start, end)));
+        }
+        
+        Ok(Self { start, end })
+    }
+}
+
+impl Domain for IntegerRange {
+    type Element = i64;
+    
+    fn contains(&self, element: &i64) -> bool {
+        (self.start..self.end).contains(element)
+    }
+}
+
+codomain_from_domain!(IntegerRange);
+
+impl FiniteDomain for IntegerRange {
+    fn elements(&self) -> Box<dyn Iterator<Item = i64> + '_> {
+        Box::new(self.start..self.end)
+    }
+    
+    fn size(&self) -> usize {
+        self.end.saturating_sub(self.start).max(0) as usize
+    }
+}
+
+/// An explicit finite set of elements
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiniteSet<T: Hash + Eq>(pub HashSet<T>);
+
+impl<T: Hash + Eq> FiniteSet<T> {
+    /// Create a new finite set from the given elements
+    pub fn new(elements: impl IntoIterator<Item = T>) -> Self {
+        Self(elements.into_iter().collect())
+    }
+}
+
+impl<T: Hash + Eq> Domain for FiniteSet<T> {
+    type Element = T;
+    
+    fn contains(&self, element: &T) -> bool {
+        self.0.contains(element)
+    }
+}
+
+impl<T: Hash + Eq + Clone> FiniteDomain for FiniteSet<T> {
+    fn elements(&self) -> Box<dyn Iterator<Item = T> + '_> {
+        Box::new(self.0.iter().cloned())
+    }
+    
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+codomain_from_domain!([T: Hash + Eq] FiniteSet<T>);
+
+/// Alternative name for [`FiniteSet`] when used as a domain
+pub type FiniteSetDomain<T> = FiniteSet<T>;
+
+/// The set of all values of a type
+#[derive(Debug)]
+pub struct Unbounded<T>(PhantomData<fn() -> T>);
+
+impl<T> Unbounded<T> {
+    /// Create a new unbounded domain
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for Unbounded<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Unbounded<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Unbounded<T> {}
+
+impl<T> Domain for Unbounded<T> {
+    type Element = T;
+    
+    fn contains(&self, _element: &T) -> bool {
+        true
+    }
+}
+
+impl<T> Codomain for Unbounded<T> {
+    type Element = T;
+    
+    fn contains(&self, _element: &T) -> bool {
+        true
+    }
+}
+
+/// Pairs of elements from two domains
+#[derive(Debug, Clone, Copy)]
+pub struct ProductDomain<D1, D2> {
+    pub first: D1,
+    pub second: D2,
+}
+
+impl<D1: Domain, D2: Domain> ProductDomain<D1, D2> {
+    /// Create a new product of two domains
+    pub fn new(first: D1, second: D2) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<D1: Domain, D2: Domain> Domain for ProductDomain<D1, D2> {
+    type Element = (D1::Element, D2::Element);
+    
+    fn contains(&self, element: &Self::Element) -> bool {
+        self.first.contains(&element.0) && self.second.contains(&element.1)
+    }
+}
+
+codomain_from_domain!([D1: Domain, D2: Domain] ProductDomain<D1, D2>);
+
+/// Create the product of two domains, containing the pairs of their elements
+///
+/// Useful for lifting two-argument functions, which take the pair as input.
+pub fn product_domain<D1: Domain, D2: Domain>(first: D1, second: D2) -> ProductDomain<D1, D2> {
+    ProductDomain::new(first, second)
+}
+
+/// Elements contained in either of two domains
+#[derive(Debug, Clone, Copy)]
+pub struct UnionDomain<D1, D2> {
+    pub first: D1,
+    pub second: D2,
+}
+
+impl<D1, D2> UnionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    /// Create a new union of two domains
+    pub fn new(first: D1, second: D2) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<D1, D2> Domain for UnionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    type Element = D1::Element;
+    
+    fn contains(&self, element: &Self::Element) -> bool {
+        self.first.contains(element) || self.second.contains(element)
+    }
+}
+
+/// Create the union of two domains, e.g. to define a polifunction on a disconnected domain
+pub fn union_domain<D1, D2>(first: D1, second: D2) -> UnionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    UnionDomain::new(first, second)
+}
+
+/// Elements contained in both of two domains
+#[derive(Debug, Clone, Copy)]
+pub struct IntersectionDomain<D1, D2> {
+    pub first: D1,
+    pub second: D2,
+}
+
+impl<D1, D2> IntersectionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    /// Create a new intersection of two domains
+    pub fn new(first: D1, second: D2) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<D1, D2> Domain for IntersectionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    type Element = D1::Element;
+    
+    fn contains(&self, element: &Self::Element) -> bool {
+        self.first.contains(element) && self.second.contains(element)
+    }
+}
+
+/// Create the intersection of two domains, e.g. the inputs where two operands are both defined
+pub fn intersection_domain<D1, D2>(first: D1, second: D2) -> IntersectionDomain<D1, D2>
+where
+    D1: Domain,
+    D2: Domain<Element = D1::Element>,
+{
+    IntersectionDomain::new(first, second)
+}
+
+/// Elements of an enclosing domain that are not in an excluded domain
+#[derive(Debug, Clone, Copy)]
+pub struct ComplementDomain<D, E> {
+    pub excluded: D,
+    pub enclosing: E,
+}
+
+impl<D, E> ComplementDomain<D, E>
+where
+    D: Domain,
+    E: Domain<Element = D::Element>,
+{
+    /// Create the complement of `excluded` relative to `enclosing`
+    pub fn new(excluded: D, enclosing: E) -> Self {
+        Self { excluded, enclosing }
+    }
+}
+
+impl<D, E> Domain for ComplementDomain<D, E>
+where
+    D: Domain,
+    E: Domain<Element = D::Element>,
+{
+    type Element = D::Element;
+    
+    fn contains(&self, element: &Self::Element) -> bool {
+        self.enclosing.contains(element) && !self.excluded.contains(element)
+    }
+}
+
+/// Elements satisfying an arbitrary predicate
+#[derive(Clone, Copy)]
+pub struct PredicateDomain<T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    predicate: F,
+    _phantom: PhantomData<fn(&T)>,
+}
+
+impl<T, F> PredicateDomain<T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    /// Create a new domain of the elements for which `predicate` returns true
+    pub fn new(predicate: F) -> Self {
+        Self {
+            predicate,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F> Domain for PredicateDomain<T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    type Element = T;
+    
+    fn contains(&self, element: &T) -> bool {
+        (self.predicate)(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClosedRealInterval, ComplementDomain, FiniteSet, IntegerRange, PredicateDomain, RealInterval, RealLine,
+        Unbounded, intersection_domain, product_domain, union_domain,
+    };
+    use crate::core::interfaces::polifunction::{Domain, FiniteDomain};
+    
+    #[test]
+    fn real_interval_respects_open_and_closed_ends() {
+        let closed = RealInterval::closed(0.0, 1.0);
+        assert!(closed.contains(&0.0) && closed.contains(&1.0) && closed.contains(&0.5));
+        assert!(!closed.contains(&-0.0001) && !closed.contains(&1.0001));
+        
+        let open = RealInterval::open(0.0, 1.0);
+        assert!(!open.contains(&0.0) && !open.contains(&1.0));
+        assert!(open.contains(&0.5));
+        
+        let half_open = RealInterval { lower_inclusive: true, ..open };
+        assert!(half_open.contains(&0.0) && !half_open.contains(&1.0));
+        
+        assert!(!closed.contains(&f64::NAN));
+        assert!(RealLine.contains(&1e300) && !RealLine.contains(&f64::INFINITY) && !RealLine.contains(&f64::NAN));
+    }
+    
+    #[test]
+    fn closed_real_interval_checks_bounds() {
+        let interval = ClosedRealInterval::new(-1.0, 1.0).unwrap();
+        assert!(interval.contains(&-1.0) && interval.contains(&1.0));
+        assert!(!interval.contains(&1.5));
+        
+        assert!(ClosedRealInterval::new(2.0, 2.0).unwrap().contains(&2.0));
+        assert!(ClosedRealInterval::new(2.0, 1.0).is_err());
+        assert!(ClosedRealInterval::new(f64::NAN, 1.0).is_err());
+    }
+    
+    #[test]
+    fn integer_range_is_half_open() {
+        let range = IntegerRange::new(-2, 3).unwrap();
+        assert!(range.contains(&-2) && range.contains(&2));
+        assert!(!range.contains(&3) && !range.contains(&-3));
+        assert_eq!(range.elements().collect::<Vec<_>>(), vec![-2, -1, 0, 1, 2]);
+        assert_eq!(range.size(), 5);
+        
+        let empty = IntegerRange::new(4, 4).unwrap();
+        assert_eq!(empty.elements().count(), 0);
+        assert_eq!(empty.size(), 0);
+        assert!(IntegerRange::new(4, 3).is_err());
+    }
+    
+    #[test]
+    fn finite_set_iterates_its_elements() {
+        let set = FiniteSet::new(["a", "b", "a", "c"]);
+        assert_eq!(set.size(), 3);
+        
+        let mut elements: Vec<_> = set.elements().collect();
+        elements.sort();
+        assert_eq!(elements, vec!["a", "b", "c"]);
+        assert!(set.contains(&"b") && !set.contains(&"d"));
+    }
+    
+    #[test]
+    fn union_intersection_and_complement() {
+        let low = RealInterval::closed(0.0, 2.0);
+        let high = RealInterval::open(1.0, 3.0);
+        
+        let union = union_domain(low, high);
+        assert!(union.contains(&0.0) && union.contains(&2.5));
+        assert!(!union.contains(&3.0) && !union.contains(&-1.0));
+        
+        let intersection = intersection_domain(low, high);
+        assert!(intersection.contains(&1.5) && intersection.contains(&2.0));
+        assert!(!intersection.contains(&1.0) && !intersection.contains(&2.5));
+        
+        // (1, 3) without [0, 2] leaves (2, 3)
+        let complement = ComplementDomain::new(low, high);
+        assert!(!complement.contains(&2.0) && complement.contains(&2.5));
+        assert!(!complement.contains(&3.0) && !complement.contains(&0.5));
+    }
+    
+    #[test]
+    fn product_domain_checks_both_components() {
+        let product = product_domain(IntegerRange::new(0, 3).unwrap(), RealInterval::open(0.0, 1.0));
+        assert!(product.contains(&(0, 0.5)));
+        assert!(!product.contains(&(3, 0.5)));
+        assert!(!product.contains(&(1, 1.0)));
+        assert!(crate::core::interfaces::polifunction::Codomain::contains(&product, &(2, 0.25)));
+    }
+    
+    #[test]
+    fn predicate_and_unbounded_domains() {
+        let even = PredicateDomain::new(|x: &i64| x % 2 == 0);
+        assert!(even.contains(&4) && !even.contains(&3));
+        assert!(Unbounded::<String>::new().contains(&"anything".to_string()));
+    }
+}
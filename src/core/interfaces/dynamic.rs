@@ -0,0 +1,127 @@
+//! Type-erased polifunctions.
+//!
+//! `PolifunctionBase` has associated domain and codomain types, so
+//! polifunctions of different concrete types cannot be stored together. This
+//! module provides an object-safe trait and a boxed wrapper that only expose
+//! the element types, so e.g. any number of f64 -> f64 polifunctions can be
+//! kept in one `Vec`.
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain};
+use super::domains::Unbounded;
+
+/// Object-safe view of a polifunction from `I` to `O`
+pub trait DynPolifunction<I, O> {
+    /// Evaluate the polifunction at the given input
+    fn evaluate_dyn(&self, input: &I) -> Result<PolifunctionValue<O>, PolifunctionError>;
+    
+    /// Check if a given input is in the domain of the polifunction
+    fn in_domain_dyn(&self, input: &I) -> bool;
+}
+
+impl<P, I, O> DynPolifunction<I, O> for P
+where
+    P: PolifunctionBase,
+    P::Domain: Domain<Element = I>,
+    P::Codomain: Codomain<Element = O>,
+{
+    fn evaluate_dyn(&self, input: &I) -> Result<PolifunctionValue<O>, PolifunctionError> {
+        self.evaluate(input)
+    }
+    
+    fn in_domain_dyn(&self, input: &I) -> bool {
+        self.in_domain(input)
+    }
+}
+
+/// Boxed polifunction from `I` to `O` with its concrete type erased
+///
+/// The domain and codomain are [`Unbounded`]; domain membership is still
+/// decided by the wrapped polifunction through `in_domain`.
+pub struct BoxedPolifunction<I, O>(Box<dyn DynPolifunction<I, O>>);
+
+impl<I, O> BoxedPolifunction<I, O> {
+    /// Box a polifunction, erasing its concrete type
+    pub fn new<P>(p: P) -> Self
+    where
+        P: PolifunctionBase + 'static,
+        P::Domain: Domain<Element = I>,
+        P::Codomain: Codomain<Element = O>,
+    {
+        Self(Box::new(p))
+    }
+}
+
+impl<I, O> PolifunctionBase for BoxedPolifunction<I, O> {
+    type Domain = Unbounded<I>;
+    type Codomain = Unbounded<O>;
+    
+    fn evaluate(&self, input: &I) -> Result<PolifunctionValue<O>, PolifunctionError> {
+        self.0.evaluate_dyn(input)
+    }
+    
+    fn in_domain(&self, input: &I) -> bool {
+        self.0.in_domain_dyn(input)
+    }
+}
+
+/// Box a polifunction, erasing its concrete type
+pub fn boxed<P>(p: P) -> BoxedPolifunction<<P::Domain as Domain>::Element, <P::Codomain as Codomain>::Element>
+where
+    P: PolifunctionBase + 'static,
+{
+    BoxedPolifunction::new(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::IntegerRange;
+    use crate::core::interfaces::interval_valued::BasicIntervalValuedPolifunction;
+    use crate::core::interfaces::operations::{negate, LiftedPolifunction};
+    use crate::core::interfaces::polifunction::Interval;
+    use crate::core::interfaces::set_valued::BasicSetValuedPolifunction;
+    
+    fn naturals() -> IntegerRange {
+        IntegerRange::new(0, 10).unwrap()
+    }
+    
+    fn set_of(values: &[i64]) -> PolifunctionValue<i64> {
+        PolifunctionValue::Set(values.iter().copied().collect())
+    }
+    
+    #[test]
+    fn heterogeneous_polifunctions_in_one_vec() {
+        let polifunctions: Vec<BoxedPolifunction<i64, i64>> = vec![
+            boxed(LiftedPolifunction::new(|x: &i64| Ok(x * x), naturals(), Unbounded::new())),
+            boxed(BasicSetValuedPolifunction::new(|x: &i64| Ok([*x, -*x].into_iter().collect()), naturals(), Unbounded::new())),
+            boxed(BasicIntervalValuedPolifunction::new(|x: &i64| Interval::new(*x, x + 1, true, false), Unbounded::new(), Unbounded::new())),
+            boxed(negate(LiftedPolifunction::new(|x: &i64| Ok(x + 1), naturals(), Unbounded::new()))),
+        ];
+        
+        let values: Vec<_> = polifunctions.iter().map(|p| p.evaluate(&3)).collect();
+        assert_eq!(values, vec![
+            Ok(PolifunctionValue::Single(9)),
+            Ok(set_of(&[3, -3])),
+            Ok(PolifunctionValue::Interval(Interval::new(3, 4, true, false).unwrap())),
+            Ok(PolifunctionValue::Single(-4)),
+        ]);
+        
+        // Domain membership still comes from the wrapped polifunctions
+        let in_domain: Vec<_> = polifunctions.iter().map(|p| p.in_domain(&-1)).collect();
+        assert_eq!(in_domain, vec![false, false, true, false]);
+        assert!(polifunctions[0].evaluate(&-1).unwrap_err().is_domain_error());
+    }
+    
+    #[test]
+    fn dyn_polifunctions_in_one_vec() {
+        let polifunctions: Vec<Box<dyn DynPolifunction<i64, i64>>> = vec![
+            Box::new(LiftedPolifunction::new(|x: &i64| Ok(2 * x), naturals(), Unbounded::new())),
+            Box::new(BasicSetValuedPolifunction::new(|_: &i64| Ok([0, 1].into_iter().collect()), Unbounded::new(), Unbounded::new())),
+        ];
+        
+        assert_eq!(polifunctions[0].evaluate_dyn(&4), Ok(PolifunctionValue::Single(8)));
+        assert_eq!(polifunctions[1].evaluate_dyn(&4), Ok(set_of(&[0, 1])));
+        assert!(!polifunctions[0].in_domain_dyn(&10));
+        assert!(polifunctions[1].in_domain_dyn(&10));
+    }
+}
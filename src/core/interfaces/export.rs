@@ -0,0 +1,175 @@
+//! Export of sampled polifunctions.
+//!
+//! This module provides helpers that evaluate an interval-valued polifunction
+//! at a list of inputs and collect or write the resulting intervals, e.g. to
+//! plot their lower and upper envelopes.
+
+use std::fmt::Display;
+use std::io::Write;
+
+use super::polifunction::{PolifunctionBase, PolifunctionError, Domain, Codomain, Interval};
+use super::interval_valued::IntervalValuedPolifunction;
+
+/// What to do with inputs outside the domain when exporting samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfDomainPolicy {
+    /// Leave the input out of the output
+    #[default]
+    Skip,
+    /// Write the input with empty interval fields
+    EmptyFields,
+    /// Stop with a `DomainError`
+    Abort,
+}
+
+/// Inputs of a polifunction paired with their output intervals
+pub type SampledPoints<P> = Vec<(
+    <<P as PolifunctionBase>::Domain as Domain>::Element,
+    Interval<<<P as PolifunctionBase>::Codomain as Codomain>::Element>,
+)>;
+
+/// Evaluate an interval-valued polifunction at each input, in order
+///
+/// Inputs outside the domain are skipped; any other evaluation error is
+/// returned.
+pub fn sample_to_points<P>(p: &P, inputs: impl IntoIterator<Item = <P::Domain as Domain>::Element>)
+    -> Result<SampledPoints<P>, PolifunctionError>
+where
+    P: IntervalValuedPolifunction,
+{
+    let mut points = Vec::new();
+    for input in inputs {
+        if !p.in_domain(&input) {
+            continue;
+        }
+        
+        let interval = p.value_interval(&input)?;
+        points.push((input, interval));
+    }
+    Ok(points)
+}
+
+/// Evaluate an interval-valued polifunction at each input and write the results as CSV
+///
+/// Writes a header followed by one `input,lower,upper,lower_inclusive,upper_inclusive`
+/// row per input. Inputs outside the domain are handled according to `policy`;
+/// any other evaluation error, and any write error, is returned.
+pub fn sample_to_csv<P, W>(
+    p: &P,
+    inputs: impl IntoIterator<Item = <P::Domain as Domain>::Element>,
+    mut writer: W,
+    policy: OutOfDomainPolicy,
+) -> Result<(), PolifunctionError>
+where
+    P: IntervalValuedPolifunction,
+    <P::Domain as Domain>::Element: Display,
+    <P::Codomain as Codomain>::Element: Display,
+    W: Write,
+{
+    let write_error = |e: std::io::Error| PolifunctionError::Other(format!("Failed to write CSV: {}", e));
+    
+    writeln!(writer, "input,lower,upper,lower_inclusive,upper_inclusive").map_err(write_error)?;
+    for input in inputs {
+        let row = if p.in_domain(&input) {
+            let interval = p.value_interval(&input)?;
+            format!(
+                "{},{},{},{},{}",
+                csv_field(&input),
+                csv_field(&interval.lower),
+                csv_field(&interval.upper),
+                interval.lower_inclusive,
+                interval.upper_inclusive,
+            )
+        } else {
+            match policy {
+                OutOfDomainPolicy::Skip => continue,
+                OutOfDomainPolicy::EmptyFields => format!("{},,,,", csv_field(&input)),
+                OutOfDomainPolicy::Abort => return Err(PolifunctionError::DomainError),
+            }
+        };
+        writeln!(writer, "{}", row).map_err(write_error)?;
+    }
+    writer.flush().map_err(write_error)
+}
+
+/// Format a value as a CSV field, quoting it if it contains a separator, quote or line break
+fn csv_field(value: &impl Display) -> String {
+    let text = value.to_string();
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    use crate::core::interfaces::interval_valued::BasicIntervalValuedPolifunction;
+    
+    /// x -> [x, x + 2) on 0..3, failing to compute at 2 if `fail_at_two` is set
+    fn window(fail_at_two: bool) -> BasicIntervalValuedPolifunction<IntegerRange, Unbounded<i64>> {
+        BasicIntervalValuedPolifunction::new(
+            move |x: &i64| if fail_at_two && *x == 2 {
+                Err(PolifunctionError::ComputationError)
+            } else {
+                Interval::new(*x, x + 2, true, false)
+            },
+            IntegerRange::new(0, 3).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    fn csv(p: &BasicIntervalValuedPolifunction<IntegerRange, Unbounded<i64>>, policy: OutOfDomainPolicy) -> Result<String, PolifunctionError> {
+        let mut buffer = Vec::new();
+        sample_to_csv(p, -1..4, &mut buffer, policy)?;
+        Ok(String::from_utf8(buffer).unwrap())
+    }
+    
+    #[test]
+    fn csv_skips_inputs_outside_the_domain_by_default() {
+        assert_eq!(
+            csv(&window(false), OutOfDomainPolicy::default()).unwrap(),
+            "input,lower,upper,lower_inclusive,upper_inclusive\n\
+             0,0,2,true,false\n\
+             1,1,3,true,false\n\
+             2,2,4,true,false\n",
+        );
+    }
+    
+    #[test]
+    fn csv_writes_empty_fields_for_inputs_outside_the_domain() {
+        assert_eq!(
+            csv(&window(false), OutOfDomainPolicy::EmptyFields).unwrap(),
+            "input,lower,upper,lower_inclusive,upper_inclusive\n\
+             -1,,,,\n\
+             0,0,2,true,false\n\
+             1,1,3,true,false\n\
+             2,2,4,true,false\n\
+             3,,,,\n",
+        );
+    }
+    
+    #[test]
+    fn csv_aborts_on_domain_and_computation_errors() {
+        assert_eq!(csv(&window(false), OutOfDomainPolicy::Abort), Err(PolifunctionError::DomainError));
+        assert_eq!(csv(&window(true), OutOfDomainPolicy::Skip), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field(&"plain"), "plain");
+        assert_eq!(csv_field(&"a,b"), "\"a,b\"");
+        assert_eq!(csv_field(&"say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field(&"two\nlines"), "\"two\nlines\"");
+    }
+    
+    #[test]
+    fn points_skip_inputs_outside_the_domain() {
+        let points = sample_to_points(&window(false), -1..4).unwrap();
+        let points: Vec<_> = points.into_iter().map(|(x, i)| (x, i.lower, i.upper)).collect();
+        assert_eq!(points, vec![(0, 0, 2), (1, 1, 3), (2, 2, 4)]);
+        assert_eq!(sample_to_points(&window(true), 0..3), Err(PolifunctionError::ComputationError));
+    }
+}
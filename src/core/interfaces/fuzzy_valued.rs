@@ -0,0 +1,226 @@
+//! Fuzzy-valued polifunctions implementation.
+//!
+//! This module provides traits and implementations for polifunctions
+//! that map inputs to fuzzy sets of output values.
+
+use std::sync::Arc;
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, FuzzySet};
+
+/// Trait for fuzzy-valued polifunctions
+pub trait FuzzyValuedPolifunction: PolifunctionBase {
+    /// Get the fuzzy set of values at the given input
+    fn value_fuzzy_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<FuzzySet<<Self::Codomain as Codomain>::Element>, PolifunctionError>;
+    
+    /// Get the membership degree of a specific value in the output for a given input
+    ///
+    /// Values that do not appear in the fuzzy set have degree 0.0.
+    fn membership_of(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<f64, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: PartialEq,
+    {
+        let fuzzy_set = self.value_fuzzy_set(input)?;
+        Ok(fuzzy_set.membership(value))
+    }
+}
+
+/// Mapping function of a [`BasicFuzzyValuedPolifunction`], stored behind an `Arc`
+pub type FuzzyMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<FuzzySet<<C as Codomain>::Element>, PolifunctionError>;
+
+/// Mapping function of a [`BasicFuzzyValuedPolifunction`] that can be shared between threads
+pub type SyncFuzzyMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<FuzzySet<<C as Codomain>::Element>, PolifunctionError> + Send + Sync;
+
+/// Basic implementation of a fuzzy-valued polifunction
+///
+/// The mapping function is kept behind an `Arc`, so clones share it.
+pub struct BasicFuzzyValuedPolifunction<D, C, F: ?Sized = FuzzyMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Function that maps inputs to fuzzy sets of outputs
+    mapping_function: Arc<F>,
+    /// Domain of the function
+    domain: D,
+    /// Codomain of the function
+    codomain: C,
+}
+
+impl<D, C, F: ?Sized> Clone for BasicFuzzyValuedPolifunction<D, C, F>
+where
+    D: Domain + Clone,
+    C: Codomain + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            mapping_function: Arc::clone(&self.mapping_function),
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+        }
+    }
+}
+
+/// The mapping function is shown as a placeholder
+impl<D, C, F: ?Sized> std::fmt::Debug for BasicFuzzyValuedPolifunction<D, C, F>
+where
+    D: Domain + std::fmt::Debug,
+    C: Codomain + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicFuzzyValuedPolifunction")
+            .field("mapping_function", &format_args!("<function>"))
+            .field("domain", &self.domain)
+            .field("codomain", &self.codomain)
+            .finish()
+    }
+}
+
+impl<D, C> BasicFuzzyValuedPolifunction<D, C>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Create a new fuzzy-valued polifunction with the given mapping function
+    pub fn new(
+        mapping_function: impl Fn(&D::Element) -> Result<FuzzySet<C::Element>, PolifunctionError> + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> BasicFuzzyValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    F: Fn(&D::Element) -> Result<FuzzySet<C::Element>, PolifunctionError>,
+{
+    /// Create a new fuzzy-valued polifunction that keeps the concrete type of the mapping function
+    ///
+    /// The result is `Send + Sync` whenever the function, domain and codomain are.
+    pub fn from_fn(mapping_function: F, domain: D, codomain: C) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C> BasicFuzzyValuedPolifunction<D, C, SyncFuzzyMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+{
+    /// Create a new fuzzy-valued polifunction whose mapping function can be shared between threads
+    ///
+    /// The result is `Send + Sync` whenever the domain and codomain are.
+    pub fn new_sync(
+        mapping_function: impl Fn(&D::Element) -> Result<FuzzySet<C::Element>, PolifunctionError> + Send + Sync + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> PolifunctionBase for BasicFuzzyValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: std::fmt::Debug,
+    F: Fn(&D::Element) -> Result<FuzzySet<C::Element>, PolifunctionError> + ?Sized,
+{
+    type Domain = D;
+    type Codomain = C;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
+        }
+        
+        let fuzzy_set = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::FuzzySet(fuzzy_set))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.domain.contains(input)
+    }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let fuzzy_set = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::FuzzySet(fuzzy_set))
+    }
+}
+
+impl<D, C, F> FuzzyValuedPolifunction for BasicFuzzyValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: std::fmt::Debug,
+    F: Fn(&D::Element) -> Result<FuzzySet<C::Element>, PolifunctionError> + ?Sized,
+{
+    fn value_fuzzy_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<FuzzySet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
+        }
+        
+        (self.mapping_function)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    
+    /// x -> "about x": x with degree 1.0 and its neighbours with degree 0.5
+    fn about() -> BasicFuzzyValuedPolifunction<IntegerRange, Unbounded<i64>> {
+        BasicFuzzyValuedPolifunction::new(
+            |x: &i64| FuzzySet::new(vec![(x - 1, 0.5), (*x, 1.0), (x + 1, 0.5)]),
+            IntegerRange::new(0, 10).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    #[test]
+    fn evaluates_to_the_fuzzy_set_inside_the_domain() {
+        let p = about();
+        let expected = FuzzySet::new(vec![(2, 0.5), (3, 1.0), (4, 0.5)]).unwrap();
+        assert_eq!(p.value_fuzzy_set(&3), Ok(expected.clone()));
+        assert_eq!(p.evaluate(&3), Ok(PolifunctionValue::FuzzySet(expected)));
+        
+        assert!(!p.in_domain(&10));
+        assert_eq!(p.evaluate(&10), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn membership_of_values_outside_the_set_is_zero() {
+        let p = about();
+        assert_eq!(p.membership_of(&3, &3), Ok(1.0));
+        assert_eq!(p.membership_of(&3, &4), Ok(0.5));
+        assert_eq!(p.membership_of(&3, &7), Ok(0.0));
+        assert!(p.membership_of(&-1, &0).is_err());
+    }
+    
+    #[test]
+    fn mapping_errors_are_returned() {
+        let p = BasicFuzzyValuedPolifunction::new(|_: &i64| FuzzySet::new(vec![(0, 1.5)]), Unbounded::new(), Unbounded::<i64>::new());
+        assert!(matches!(p.evaluate(&0), Err(PolifunctionError::Other(_))));
+    }
+}
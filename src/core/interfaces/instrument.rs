@@ -0,0 +1,290 @@
+//! Instrumentation of polifunction evaluations.
+//!
+//! This module provides a wrapper that counts how often a polifunction is
+//! queried, to find out which parts of a composite polifunction are evaluated
+//! more often than expected.
+
+use std::collections::HashSet;
+use std::ops::Sub;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, Interval};
+use super::set_valued::SetValuedPolifunction;
+use super::interval_valued::IntervalValuedPolifunction;
+
+/// Call counts recorded by an [`InstrumentedPolifunction`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EvaluationStats {
+    /// Calls to `evaluate`
+    pub evaluate: usize,
+    /// Calls to `value_set`
+    pub value_set: usize,
+    /// Calls to `value_interval`
+    pub value_interval: usize,
+    /// Calls to `in_domain`
+    pub in_domain: usize,
+    /// Calls to `evaluate`, `value_set` or `value_interval` that failed with a `DomainError`
+    pub domain_errors: usize,
+}
+
+/// Atomic call counters shared by an [`InstrumentedPolifunction`] and its [`StatsHandle`]s
+#[derive(Default)]
+struct Counters {
+    evaluate: AtomicUsize,
+    value_set: AtomicUsize,
+    value_interval: AtomicUsize,
+    in_domain: AtomicUsize,
+    domain_errors: AtomicUsize,
+}
+
+impl Counters {
+    fn snapshot(&self) -> EvaluationStats {
+        EvaluationStats {
+            evaluate: self.evaluate.load(Ordering::Relaxed),
+            value_set: self.value_set.load(Ordering::Relaxed),
+            value_interval: self.value_interval.load(Ordering::Relaxed),
+            in_domain: self.in_domain.load(Ordering::Relaxed),
+            domain_errors: self.domain_errors.load(Ordering::Relaxed),
+        }
+    }
+    
+    fn reset(&self) {
+        for counter in [&self.evaluate, &self.value_set, &self.value_interval, &self.in_domain, &self.domain_errors] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+    
+    /// Count a call and any `DomainError` it returned
+    fn record<T>(&self, counter: &AtomicUsize, result: Result<T, PolifunctionError>) -> Result<T, PolifunctionError> {
+        counter.fetch_add(1, Ordering::Relaxed);
+        if result.as_ref().is_err_and(PolifunctionError::is_domain_error) {
+            self.domain_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+/// Handle for reading the counters of an [`InstrumentedPolifunction`]
+///
+/// The handle stays valid after the instrumented polifunction has been moved
+/// into a composite, so the calls to a leaf can still be inspected.
+#[derive(Clone)]
+pub struct StatsHandle(Arc<Counters>);
+
+impl StatsHandle {
+    /// Get the calls counted so far
+    pub fn stats(&self) -> EvaluationStats {
+        self.0.snapshot()
+    }
+    
+    /// Reset all counters to zero
+    pub fn reset(&self) {
+        self.0.reset()
+    }
+}
+
+/// Polifunction wrapper that counts calls to the wrapped polifunction
+///
+/// Every call is forwarded unchanged. The counters are atomic, so the wrapper
+/// can be shared between threads whenever the wrapped polifunction can.
+pub struct InstrumentedPolifunction<P> {
+    /// The wrapped polifunction
+    inner: P,
+    /// Call counters, shared with the stats handles
+    counters: Arc<Counters>,
+}
+
+impl<P> InstrumentedPolifunction<P> {
+    /// Wrap a polifunction with all counters at zero
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            counters: Arc::new(Counters::default()),
+        }
+    }
+    
+    /// Get the calls counted so far
+    pub fn stats(&self) -> EvaluationStats {
+        self.counters.snapshot()
+    }
+    
+    /// Reset all counters to zero
+    pub fn reset_stats(&self) {
+        self.counters.reset()
+    }
+    
+    /// Get a handle for reading the counters after the wrapper has been moved
+    pub fn stats_handle(&self) -> StatsHandle {
+        StatsHandle(Arc::clone(&self.counters))
+    }
+    
+    /// Get the wrapped polifunction
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P> PolifunctionBase for InstrumentedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        self.counters.record(&self.counters.evaluate, self.inner.evaluate(input))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.counters.in_domain.fetch_add(1, Ordering::Relaxed);
+        self.inner.in_domain(input)
+    }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        self.counters.record(&self.counters.evaluate, self.inner.evaluate_trusted(input))
+    }
+}
+
+impl<P> SetValuedPolifunction for InstrumentedPolifunction<P>
+where
+    P: SetValuedPolifunction,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        self.counters.record(&self.counters.value_set, self.inner.value_set(input))
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        self.inner.contains_value(input, value)
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        self.inner.cardinality(input)
+    }
+}
+
+impl<P> IntervalValuedPolifunction for InstrumentedPolifunction<P>
+where
+    P: IntervalValuedPolifunction,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        self.counters.record(&self.counters.value_interval, self.inner.value_interval(input))
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        self.inner.contains_value(input, value)
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        self.inner.interval_width(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, Unbounded};
+    use crate::core::interfaces::interval_valued::BasicIntervalValuedPolifunction;
+    use crate::core::interfaces::set_valued::BasicSetValuedPolifunction;
+    
+    /// x -> {x, -x} on 0..5, failing to compute at 4
+    fn plus_minus() -> InstrumentedPolifunction<BasicSetValuedPolifunction<IntegerRange, Unbounded<i64>>> {
+        InstrumentedPolifunction::new(BasicSetValuedPolifunction::new(
+            |x: &i64| if *x == 4 { Err(PolifunctionError::ComputationError) } else { Ok([*x, -*x].into_iter().collect()) },
+            IntegerRange::new(0, 5).unwrap(),
+            Unbounded::new(),
+        ))
+    }
+    
+    #[test]
+    fn counts_calls_by_method() {
+        let p = plus_minus();
+        assert_eq!(p.stats(), EvaluationStats::default());
+        
+        p.evaluate(&1).unwrap();
+        p.evaluate(&2).unwrap();
+        p.value_set(&3).unwrap();
+        assert!(p.in_domain(&0));
+        assert!(!p.in_domain(&9));
+        
+        // contains_value and cardinality are forwarded without being counted
+        assert_eq!(SetValuedPolifunction::contains_value(&p, &1, &-1), Ok(true));
+        assert_eq!(p.cardinality(&1), Ok(2));
+        
+        assert_eq!(p.stats(), EvaluationStats { evaluate: 2, value_set: 1, value_interval: 0, in_domain: 2, domain_errors: 0 });
+    }
+    
+    #[test]
+    fn counts_failed_calls_and_domain_errors() {
+        let p = plus_minus();
+        assert!(p.evaluate(&9).unwrap_err().is_domain_error());
+        assert!(p.value_set(&-1).unwrap_err().is_domain_error());
+        assert_eq!(p.evaluate(&4), Err(PolifunctionError::ComputationError));
+        assert_eq!(p.value_set(&4), Err(PolifunctionError::ComputationError));
+        
+        // Failed calls count as calls; only domain errors count as domain errors
+        let stats = p.stats();
+        assert_eq!((stats.evaluate, stats.value_set, stats.domain_errors), (2, 2, 2));
+    }
+    
+    #[test]
+    fn counts_interval_calls() {
+        let p = InstrumentedPolifunction::new(BasicIntervalValuedPolifunction::new(
+            |x: &i64| Interval::new(*x, x + 1, true, true),
+            IntegerRange::new(0, 5).unwrap(),
+            Unbounded::new(),
+        ));
+        p.value_interval(&1).unwrap();
+        assert!(p.value_interval(&7).unwrap_err().is_domain_error());
+        assert_eq!(p.interval_width(&1), Ok(1));
+        
+        let stats = p.stats();
+        assert_eq!((stats.value_interval, stats.domain_errors, stats.evaluate), (2, 1, 0));
+    }
+    
+    #[test]
+    fn reset_clears_every_counter() {
+        let p = plus_minus();
+        p.evaluate(&1).unwrap();
+        p.evaluate(&9).unwrap_err();
+        p.value_set(&2).unwrap();
+        p.in_domain(&3);
+        assert_ne!(p.stats(), EvaluationStats::default());
+        
+        p.reset_stats();
+        assert_eq!(p.stats(), EvaluationStats::default());
+        
+        // Counting resumes from zero
+        p.evaluate(&1).unwrap();
+        assert_eq!(p.stats().evaluate, 1);
+    }
+    
+    #[test]
+    fn handle_reads_and_resets_after_the_wrapper_is_moved() {
+        let p = plus_minus();
+        let handle = p.stats_handle();
+        let boxed: Box<dyn PolifunctionBase<Domain = IntegerRange, Codomain = Unbounded<i64>>> = Box::new(p);
+        
+        boxed.evaluate(&1).unwrap();
+        boxed.evaluate(&2).unwrap();
+        assert_eq!(handle.stats().evaluate, 2);
+        
+        handle.reset();
+        assert_eq!(handle.stats(), EvaluationStats::default());
+        boxed.evaluate(&3).unwrap();
+        assert_eq!(handle.clone().stats().evaluate, 1);
+    }
+}
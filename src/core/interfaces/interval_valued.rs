@@ -4,8 +4,10 @@
 //! that map inputs to intervals of output values.
 
 use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, Interval};
+use super::operations::{ValueAlgebra, AddAlgebra, SubAlgebra, MulAlgebra};
 use std::cmp::PartialOrd;
-use std::ops::{Add, Sub};
+use std::ops::Sub;
+use std::sync::Arc;
 
 /// Trait for interval-valued polifunctions
 pub trait IntervalValuedPolifunction: PolifunctionBase {
@@ -25,21 +27,90 @@ pub trait IntervalValuedPolifunction: PolifunctionBase {
         <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone;
 }
 
+/// Implement `IntervalValuedPolifunction` for pointer types by forwarding to the pointee
+macro_rules! forward_interval_valued {
+    ($($pointer:ty),*) => {
+        $(
+            impl<P: IntervalValuedPolifunction + ?Sized> IntervalValuedPolifunction for $pointer {
+                fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+                    (**self).value_interval(input)
+                }
+                
+                fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                                 value: &<Self::Codomain as Codomain>::Element)
+                    -> Result<bool, PolifunctionError> {
+                    (**self).contains_value(input, value)
+                }
+                
+                fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+                where
+                    <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+                {
+                    (**self).interval_width(input)
+                }
+            }
+        )*
+    };
+}
+
+forward_interval_valued!(&P, Box<P>, Arc<P>);
+
+/// Mapping function of a [`BasicIntervalValuedPolifunction`], stored behind an `Arc`
+pub type IntervalMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<Interval<<C as Codomain>::Element>, PolifunctionError>;
+
+/// Mapping function of a [`BasicIntervalValuedPolifunction`] that can be shared between threads
+pub type SyncIntervalMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<Interval<<C as Codomain>::Element>, PolifunctionError> + Send + Sync;
+
 /// Basic implementation of an interval-valued polifunction
-pub struct BasicIntervalValuedPolifunction<D, C>
+///
+/// The mapping function is kept behind an `Arc`, so clones share it.
+pub struct BasicIntervalValuedPolifunction<D, C, F: ?Sized = IntervalMapping<D, C>>
 where
     D: Domain,
     C: Codomain,
     C::Element: PartialOrd + Clone,
 {
     /// Function that maps inputs to intervals of outputs
-    mapping_function: Box<dyn Fn(&D::Element) -> Result<Interval<C::Element>, PolifunctionError>>,
+    mapping_function: Arc<F>,
     /// Domain of the function
     domain: D,
     /// Codomain of the function
     codomain: C,
 }
 
+impl<D, C, F: ?Sized> Clone for BasicIntervalValuedPolifunction<D, C, F>
+where
+    D: Domain + Clone,
+    C: Codomain + Clone,
+    C::Element: PartialOrd + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            mapping_function: Arc::clone(&self.mapping_function),
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+        }
+    }
+}
+
+/// The mapping function is shown as a placeholder
+impl<D, C, F: ?Sized> std::fmt::Debug for BasicIntervalValuedPolifunction<D, C, F>
+where
+    D: Domain + std::fmt::Debug,
+    C: Codomain + std::fmt::Debug,
+    C::Element: PartialOrd + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicIntervalValuedPolifunction")
+            .field("mapping_function", &format_args!("<function>"))
+            .field("domain", &self.domain)
+            .field("codomain", &self.codomain)
+            .finish()
+    }
+}
+
 impl<D, C> BasicIntervalValuedPolifunction<D, C>
 where
     D: Domain,
@@ -53,18 +124,61 @@ where
         codomain: C,
     ) -> Self {
         Self {
-            mapping_function: Box::new(mapping_function),
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> BasicIntervalValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    C::Element: PartialOrd + Clone,
+    F: Fn(&D::Element) -> Result<Interval<C::Element>, PolifunctionError>,
+{
+    /// Create a new interval-valued polifunction that keeps the concrete type of the mapping function
+    ///
+    /// The result is `Send + Sync` whenever the function, domain and codomain are.
+    pub fn from_fn(mapping_function: F, domain: D, codomain: C) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C> BasicIntervalValuedPolifunction<D, C, SyncIntervalMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+    C::Element: PartialOrd + Clone,
+{
+    /// Create a new interval-valued polifunction whose mapping function can be shared between threads
+    ///
+    /// The result is `Send + Sync` whenever the domain and codomain are.
+    pub fn new_sync(
+        mapping_function: impl Fn(&D::Element) -> Result<Interval<C::Element>, PolifunctionError> + Send + Sync + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
             domain,
             codomain,
         }
     }
 }
 
-impl<D, C> PolifunctionBase for BasicIntervalValuedPolifunction<D, C>
+impl<D, C, F> PolifunctionBase for BasicIntervalValuedPolifunction<D, C, F>
 where
     D: Domain,
     C: Codomain,
+    D::Element: std::fmt::Debug,
     C::Element: PartialOrd + Clone,
+    F: Fn(&D::Element) -> Result<Interval<C::Element>, PolifunctionError> + ?Sized,
 {
     type Domain = D;
     type Codomain = C;
@@ -72,7 +186,7 @@ where
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
         let interval = (self.mapping_function)(input)?;
@@ -82,41 +196,40 @@ where
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
         self.domain.contains(input)
     }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let interval = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::Interval(interval))
+    }
 }
 
-impl<D, C> IntervalValuedPolifunction for BasicIntervalValuedPolifunction<D, C>
+impl<D, C, F> IntervalValuedPolifunction for BasicIntervalValuedPolifunction<D, C, F>
 where
     D: Domain,
     C: Codomain,
+    D::Element: std::fmt::Debug,
     C::Element: PartialOrd + Clone,
+    F: Fn(&D::Element) -> Result<Interval<C::Element>, PolifunctionError> + ?Sized,
 {
     fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
         (self.mapping_function)(input)
     }
     
+    /// Check whether `value` is a possible output at `input`
+    ///
+    /// Values outside the codomain are never possible outputs, even if the
+    /// mapping function returns an interval that reaches them.
     fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
                      value: &<Self::Codomain as Codomain>::Element)
         -> Result<bool, PolifunctionError> {
         let interval = self.value_interval(input)?;
-        
-        let lower_check = match (&interval.lower_inclusive, value.partial_cmp(&interval.lower)) {
-            (true, Some(std::cmp::Ordering::Equal)) => true,
-            (_, Some(std::cmp::Ordering::Greater)) => true,
-            _ => false,
-        };
-        
-        let upper_check = match (&interval.upper_inclusive, value.partial_cmp(&interval.upper)) {
-            (true, Some(std::cmp::Ordering::Equal)) => true,
-            (_, Some(std::cmp::Ordering::Less)) => true,
-            _ => false,
-        };
-        
-        Ok(lower_check && upper_check)
+        Ok(self.codomain.contains(value) && interval.contains(value))
     }
     
     fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
@@ -125,7 +238,7 @@ where
         <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
     {
         let interval = self.value_interval(input)?;
-        Ok(interval.upper.clone() - interval.lower.clone())
+        Ok(interval.width())
     }
 }
 
@@ -191,7 +304,7 @@ where
         let interval1 = match self.p1.value_interval(input) {
             Ok(i) => i,
             Err(e) => {
-                if matches!(e, PolifunctionError::DomainError) {
+                if e.is_domain_error() {
                     // If it's a domain error, try the second function only
                     return self.p2.value_interval(input);
                 } else {
@@ -203,7 +316,7 @@ where
         let interval2 = match self.p2.value_interval(input) {
             Ok(i) => i,
             Err(e) => {
-                if matches!(e, PolifunctionError::DomainError) {
+                if e.is_domain_error() {
                     // If it's a domain error, use just the first interval
                     return Ok(interval1);
                 } else {
@@ -212,47 +325,107 @@ where
             }
         };
         
-        // Compute the hull (smallest interval containing both intervals)
-        let lower = match interval1.lower.partial_cmp(&interval2.lower) {
-            Some(std::cmp::Ordering::Less) => (interval1.lower.clone(), interval1.lower_inclusive),
-            Some(std::cmp::Ordering::Equal) => (interval1.lower.clone(), interval1.lower_inclusive || interval2.lower_inclusive),
-            Some(std::cmp::Ordering::Greater) => (interval2.lower.clone(), interval2.lower_inclusive),
-            None => return Err(PolifunctionError::ComputationError),
-        };
-        
-        let upper = match interval1.upper.partial_cmp(&interval2.upper) {
-            Some(std::cmp::Ordering::Greater) => (interval1.upper.clone(), interval1.upper_inclusive),
-            Some(std::cmp::Ordering::Equal) => (interval1.upper.clone(), interval1.upper_inclusive || interval2.upper_inclusive),
-            Some(std::cmp::Ordering::Less) => (interval2.upper.clone(), interval2.upper_inclusive),
-            None => return Err(PolifunctionError::ComputationError),
-        };
-        
-        Ok(Interval {
-            lower: lower.0,
-            upper: upper.0,
-            lower_inclusive: lower.1,
-            upper_inclusive: upper.1,
-        })
+        // Incomparable endpoints have no well-defined hull
+        interval1.hull(&interval2).ok_or(PolifunctionError::ComputationError)
     }
     
     fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
                      value: &<Self::Codomain as Codomain>::Element)
         -> Result<bool, PolifunctionError> {
         let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        let interval = self.value_interval(input)?;
+        Ok(interval.width())
+    }
+}
+
+/// Intersection of two interval-valued polifunctions
+///
+/// Defined only where both operands are defined. Intervals that do not overlap,
+/// or only touch at an excluded endpoint, produce `PolifunctionError::EmptyResult`
+/// (see [`Interval::intersect`]).
+pub struct IntervalIntersectionPolifunction<P1, P2>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    p1: P1,
+    p2: P2,
+}
+
+impl<P1, P2> IntervalIntersectionPolifunction<P1, P2>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    /// Create a new intersection of two interval-valued polifunctions
+    pub fn new(p1: P1, p2: P2) -> Self {
+        Self { p1, p2 }
+    }
+}
+
+impl<P1, P2> PolifunctionBase for IntervalIntersectionPolifunction<P1, P2>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
         
-        let lower_check = match (&interval.lower_inclusive, value.partial_cmp(&interval.lower)) {
-            (true, Some(std::cmp::Ordering::Equal)) => true,
-            (_, Some(std::cmp::Ordering::Greater)) => true,
-            _ => false,
-        };
+        let interval = self.value_interval(input)?;
+        Ok(PolifunctionValue::Interval(interval))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.p1.in_domain(input) && self.p2.in_domain(input)
+    }
+}
+
+impl<P1, P2> IntervalValuedPolifunction for IntervalIntersectionPolifunction<P1, P2>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
         
-        let upper_check = match (&interval.upper_inclusive, value.partial_cmp(&interval.upper)) {
-            (true, Some(std::cmp::Ordering::Equal)) => true,
-            (_, Some(std::cmp::Ordering::Less)) => true,
-            _ => false,
+        let interval1 = self.p1.value_interval(input)?;
+        let interval2 = self.p2.value_interval(input)?;
+        
+        interval1.intersect(&interval2).ok_or(PolifunctionError::EmptyResult)
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let interval = match self.value_interval(input) {
+            Ok(interval) => interval,
+            // Nothing is contained in an empty intersection
+            Err(PolifunctionError::EmptyResult) => return Ok(false),
+            Err(e) => return Err(e),
         };
         
-        Ok(lower_check && upper_check)
+        Ok(interval.contains(value))
     }
     
     fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
@@ -261,6 +434,276 @@ where
         <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
     {
         let interval = self.value_interval(input)?;
-        Ok(interval.upper.clone() - interval.lower.clone())
+        Ok(interval.width())
+    }
+}
+
+/// Pointwise interval arithmetic on two interval-valued polifunctions
+///
+/// The output interval at each input is the algebra's combination of the two
+/// operands' intervals, so the result can be fed into other interval-valued
+/// combinators such as [`HullPolifunction`].
+pub struct IntervalArithmeticPolifunction<P1, P2, A>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    p1: P1,
+    p2: P2,
+    algebra: A,
+}
+
+impl<P1, P2, A> IntervalArithmeticPolifunction<P1, P2, A>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    /// Create a new combination of two interval-valued polifunctions
+    pub fn new(p1: P1, p2: P2) -> Self
+    where
+        A: Default,
+    {
+        Self { p1, p2, algebra: A::default() }
+    }
+}
+
+impl<P1, P2, A> PolifunctionBase for IntervalArithmeticPolifunction<P1, P2, A>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        let interval = self.value_interval(input)?;
+        Ok(PolifunctionValue::Interval(interval))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.p1.in_domain(input) && self.p2.in_domain(input)
+    }
+}
+
+impl<P1, P2, A> IntervalValuedPolifunction for IntervalArithmeticPolifunction<P1, P2, A>
+where
+    P1: IntervalValuedPolifunction,
+    P2: IntervalValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
+    <P1::Codomain as Codomain>::Element: PartialOrd + Clone,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        let interval1 = self.p1.value_interval(input)?;
+        let interval2 = self.p2.value_interval(input)?;
+        self.algebra.combine_intervals(interval1, interval2)
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+    {
+        let interval = self.value_interval(input)?;
+        Ok(interval.width())
+    }
+}
+
+/// Pointwise sum of two interval-valued polifunctions
+pub type IntervalSumPolifunction<P1, P2> = IntervalArithmeticPolifunction<P1, P2, AddAlgebra>;
+
+/// Pointwise difference of two interval-valued polifunctions
+pub type IntervalDifferencePolifunction<P1, P2> = IntervalArithmeticPolifunction<P1, P2, SubAlgebra>;
+
+/// Pointwise product of two interval-valued polifunctions
+///
+/// The product interval spans the extrema of the four endpoint products, so
+/// operands spanning zero are handled correctly.
+pub type IntervalProductPolifunction<P1, P2> = IntervalArithmeticPolifunction<P1, P2, MulAlgebra>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{RealInterval, RealLine};
+    
+    type Constant = BasicIntervalValuedPolifunction<RealInterval, RealLine>;
+    
+    /// The same interval at every input in [0, 10]
+    fn constant(lower: f64, upper: f64, lower_inclusive: bool, upper_inclusive: bool) -> Constant {
+        let interval = Interval::new(lower, upper, lower_inclusive, upper_inclusive).unwrap();
+        BasicIntervalValuedPolifunction::new(move |_: &f64| Ok(interval.clone()), RealInterval::closed(0.0, 10.0), RealLine)
+    }
+    
+    fn closed(lower: f64, upper: f64) -> Constant {
+        constant(lower, upper, true, true)
+    }
+    
+    fn bounds(interval: Interval<f64>) -> (f64, f64, bool, bool) {
+        (interval.lower, interval.upper, interval.lower_inclusive, interval.upper_inclusive)
+    }
+    
+    #[test]
+    fn contains_value_consults_the_codomain() {
+        // [x - 1, x + 1] with outputs restricted to [0, 10]
+        let around = BasicIntervalValuedPolifunction::new(
+            |x: &f64| Interval::new(x - 1.0, x + 1.0, true, true),
+            RealInterval::closed(0.0, 10.0),
+            RealInterval::closed(0.0, 10.0),
+        );
+        
+        assert_eq!(around.contains_value(&0.0, &0.5), Ok(true));
+        assert_eq!(around.contains_value(&0.0, &0.0), Ok(true));
+        assert_eq!(around.contains_value(&10.0, &10.0), Ok(true));
+        // Inside the interval but outside the codomain
+        assert_eq!(around.contains_value(&0.0, &-0.5), Ok(false));
+        assert_eq!(around.contains_value(&10.0, &10.5), Ok(false));
+        // Inside the codomain but outside the interval
+        assert_eq!(around.contains_value(&0.0, &5.0), Ok(false));
+        assert!(around.contains_value(&11.0, &10.5).unwrap_err().is_domain_error());
+    }
+    
+    fn assert_send_sync<T: Send + Sync>() {}
+    
+    type IntervalFn = fn(&f64) -> Result<Interval<f64>, PolifunctionError>;
+    
+    #[test]
+    fn thread_safe_variants_cross_threads() {
+        assert_send_sync::<BasicIntervalValuedPolifunction<RealInterval, RealLine, IntervalFn>>();
+        assert_send_sync::<BasicIntervalValuedPolifunction<RealInterval, RealLine, SyncIntervalMapping<RealInterval, RealLine>>>();
+        
+        let radius = 0.5;
+        let around = Arc::new(BasicIntervalValuedPolifunction::from_fn(
+            move |x: &f64| Interval::new(x - radius, x + radius, true, false),
+            RealInterval::closed(0.0, 10.0),
+            RealLine,
+        ));
+        let doubled = Arc::new(BasicIntervalValuedPolifunction::new_sync(
+            |x: &f64| Interval::new(*x, 2.0 * x, true, true),
+            RealInterval::closed(0.0, 10.0),
+            RealLine,
+        ));
+        
+        let handle = {
+            let (around, doubled) = (Arc::clone(&around), Arc::clone(&doubled));
+            std::thread::spawn(move || (around.value_interval(&2.0), doubled.value_interval(&3.0)))
+        };
+        let (around_two, doubled_three) = handle.join().unwrap();
+        assert_eq!(around_two, Interval::new(1.5, 2.5, true, false));
+        assert_eq!(doubled_three, Interval::new(3.0, 6.0, true, true));
+        assert_eq!(around.contains_value(&2.0, &2.5), Ok(false));
+    }
+    
+    #[test]
+    fn one_instance_cloned_into_two_combinators() {
+        let p = closed(1.0, 3.0);
+        let hull = HullPolifunction::new(p.clone(), closed(5.0, 6.0));
+        let intersection = IntervalIntersectionPolifunction::new(p.clone(), closed(2.0, 6.0));
+        
+        assert_eq!(hull.value_interval(&0.0), Interval::new(1.0, 6.0, true, true));
+        assert_eq!(intersection.value_interval(&0.0), Interval::new(2.0, 3.0, true, true));
+        assert_eq!(p.value_interval(&0.0), Interval::new(1.0, 3.0, true, true));
+        
+        let debug = format!("{:?}", p);
+        assert!(debug.starts_with("BasicIntervalValuedPolifunction { mapping_function: <function>, domain: RealInterval { lower: 0.0, upper: 10.0"));
+        assert!(debug.ends_with("codomain: RealLine }"));
+    }
+    
+    #[test]
+    fn hull_of_borrowed_and_shared_polifunctions() {
+        let (left, right) = (closed(1.0, 2.0), closed(4.0, 5.0));
+        let borrowed = HullPolifunction::new(&left, &right);
+        assert_eq!(borrowed.value_interval(&0.0), Interval::new(1.0, 5.0, true, true));
+        
+        // Sharing through Arc wants a thread-safe mapping function
+        let constant_sync = |lower: f64, upper: f64| BasicIntervalValuedPolifunction::new_sync(
+            move |_: &f64| Interval::new(lower, upper, true, true),
+            RealInterval::closed(0.0, 10.0),
+            RealLine,
+        );
+        let (left, right) = (Arc::new(constant_sync(1.0, 2.0)), Arc::new(constant_sync(4.0, 5.0)));
+        let shared = HullPolifunction::new(Arc::clone(&left), Arc::clone(&right));
+        let mixed = HullPolifunction::new(left.as_ref(), Arc::clone(&right));
+        assert_eq!(shared.value_interval(&3.0), Interval::new(1.0, 5.0, true, true));
+        assert_eq!(mixed.value_interval(&3.0), shared.value_interval(&3.0));
+    }
+    
+    #[test]
+    fn intersection_of_disjoint_intervals_is_empty() {
+        let disjoint = IntervalIntersectionPolifunction::new(closed(0.0, 1.0), closed(2.0, 3.0));
+        assert_eq!(disjoint.value_interval(&5.0), Err(PolifunctionError::EmptyResult));
+        assert_eq!(disjoint.evaluate(&5.0), Err(PolifunctionError::EmptyResult));
+        assert_eq!(disjoint.contains_value(&5.0, &0.5), Ok(false));
+    }
+    
+    #[test]
+    fn intersection_of_touching_intervals_depends_on_inclusivity() {
+        let touching_open = IntervalIntersectionPolifunction::new(constant(0.0, 1.0, true, false), closed(1.0, 2.0));
+        assert_eq!(touching_open.value_interval(&5.0), Err(PolifunctionError::EmptyResult));
+        assert_eq!(touching_open.contains_value(&5.0, &1.0), Ok(false));
+        
+        let touching_closed = IntervalIntersectionPolifunction::new(closed(0.0, 1.0), closed(1.0, 2.0));
+        assert_eq!(touching_closed.value_interval(&5.0).map(bounds), Ok((1.0, 1.0, true, true)));
+        assert_eq!(touching_closed.interval_width(&5.0), Ok(0.0));
+    }
+    
+    #[test]
+    fn intersection_of_nested_intervals_is_the_inner_one() {
+        let nested = IntervalIntersectionPolifunction::new(closed(0.0, 10.0), constant(2.0, 3.0, false, true));
+        assert_eq!(nested.value_interval(&5.0).map(bounds), Ok((2.0, 3.0, false, true)));
+        assert_eq!(nested.contains_value(&5.0, &2.0), Ok(false));
+        assert_eq!(nested.contains_value(&5.0, &3.0), Ok(true));
+        assert_eq!(nested.value_interval(&11.0), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn hull_spans_both_intervals() {
+        let hull = HullPolifunction::new(constant(0.0, 1.0, false, true), closed(2.0, 3.0));
+        assert_eq!(hull.value_interval(&5.0).map(bounds), Ok((0.0, 3.0, false, true)));
+        assert_eq!(hull.contains_value(&5.0, &1.5), Ok(true));
+    }
+    
+    #[test]
+    fn interval_arithmetic_combines_endpoints() {
+        let sum = IntervalSumPolifunction::new(closed(1.0, 2.0), constant(-1.0, 3.0, false, true));
+        assert_eq!(sum.value_interval(&5.0).map(bounds), Ok((0.0, 5.0, false, true)));
+        
+        let difference = IntervalDifferencePolifunction::new(closed(1.0, 2.0), closed(-1.0, 3.0));
+        assert_eq!(difference.value_interval(&5.0).map(bounds), Ok((-2.0, 3.0, true, true)));
+        assert_eq!(difference.interval_width(&5.0), Ok(5.0));
+        
+        // Operands spanning zero take the extrema of the endpoint products
+        let product = IntervalProductPolifunction::new(closed(-2.0, 3.0), closed(-1.0, 4.0));
+        assert_eq!(product.value_interval(&5.0).map(bounds), Ok((-8.0, 12.0, true, true)));
+        assert_eq!(product.evaluate(&5.0).unwrap().as_interval().map(|i| (i.lower, i.upper)), Some((-8.0, 12.0)));
+        assert_eq!(product.value_interval(&-1.0), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn combinators_nest() {
+        // ([0, 1] + [1, 2]) hull [5, 6]
+        let combined = HullPolifunction::new(IntervalSumPolifunction::new(closed(0.0, 1.0), closed(1.0, 2.0)), closed(5.0, 6.0));
+        assert_eq!(combined.value_interval(&0.0).map(bounds), Ok((1.0, 6.0, true, true)));
     }
 }
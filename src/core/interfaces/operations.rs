@@ -3,13 +3,21 @@
 //! This module provides common operations that can be performed on polifunctions,
 //! such as composition, inversion, and algebraic operations.
 
-use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain};
-use super::set_valued::{SetValuedPolifunction};
+use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain, Interval, FiniteDomain, ProbabilityDistribution, FuzzySet, ComposedPolifunction, SetSupport, HashSets, NoSets, PROBABILITY_EPSILON};
+use super::set_valued::{SetValuedPolifunction, TabularSetValuedPolifunction};
 use super::interval_valued::{IntervalValuedPolifunction};
+use super::fuzzy_valued::FuzzyValuedPolifunction;
+use super::distribution_valued::DistributionValuedPolifunction;
+use super::domains::{RealInterval, Unbounded, ProductDomain};
 use std::collections::HashSet;
+use std::hash::Hash;
 use std::marker::PhantomData;
 
+#[cfg(feature = "parallel")]
+use super::polifunction::TrustLevel;
+
 /// Lift a standard function to a polifunction
+#[derive(Clone)]
 pub struct LiftedPolifunction<F, D, C>
 where
     F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
@@ -41,6 +49,16 @@ where
             _phantom: PhantomData,
         }
     }
+    
+    /// Domain the function was lifted with
+    pub fn domain(&self) -> &D {
+        &self.domain
+    }
+    
+    /// Codomain the function was lifted with
+    pub fn codomain(&self) -> &C {
+        &self.codomain
+    }
 }
 
 impl<F, D, C> PolifunctionBase for LiftedPolifunction<F, D, C>
@@ -66,36 +84,78 @@ where
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
         self.domain.contains(input)
     }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let value = (self.function)(input)?;
+        Ok(PolifunctionValue::Single(value))
+    }
 }
 
 /// Invert a polifunction (domain and codomain are swapped)
+///
+/// The inverse maps an output `y` to the set of all inputs `x` with `y` among
+/// the values of `p(x)`: equal to a Single value, a member of a Set, or
+/// contained in an Interval or MultiInterval, or with a positive probability or
+/// membership degree in a Distribution or FuzzySet. It is computed by scanning every
+/// element of the original (finite) domain, so each evaluation costs one
+/// evaluation of the original polifunction per domain element. Inputs of the
+/// original domain where it is not defined are skipped.
 pub struct InvertedPolifunction<P>
 where
     P: PolifunctionBase,
 {
     /// The original polifunction
     original: P,
+    /// Domain of the original polifunction, enumerated to find preimages
+    domain: P::Domain,
 }
 
 impl<P> InvertedPolifunction<P>
 where
     P: PolifunctionBase,
+    P::Domain: FiniteDomain,
 {
-    /// Create a new inverted polifunction
-    pub fn new(original: P) -> Self {
+    /// Create a new inverted polifunction over the finite domain of the original
+    pub fn new(original: P, domain: P::Domain) -> Self {
         Self {
-                original,
+            original,
+            domain,
+        }
+    }
+    
+    /// All inputs of the original polifunction that map to `value`
+    fn preimage(&self, value: &<P::Codomain as Codomain>::Element)
+        -> Result<HashSet<<P::Domain as Domain>::Element>, PolifunctionError>
+    where
+        <P::Domain as Domain>::Element: Hash + Eq,
+        <P::Codomain as Codomain>::Element: PartialOrd + Hash + Eq,
+    {
+        let mut preimage = HashSet::new();
+        
+        for x in self.domain.elements() {
+            let output = match self.original.evaluate(&x) {
+                Ok(output) => output,
+                Err(e) if e.is_domain_error() => continue,
+                Err(e) => return Err(e),
+            };
+            
+            if output.contains(value) {
+                preimage.insert(x);
+            }
         }
+        
+        Ok(preimage)
     }
 }
 
-// Note: Implementing a true inverse is complex and would require additional type machinery.
-// This is a simplified version that just provides a conceptual framework.
 impl<P> PolifunctionBase for InvertedPolifunction<P>
 where
     P: PolifunctionBase,
-    <P::Domain as Domain>::Element: Clone,
-    <P::Codomain as Codomain>::Element: Clone + Eq + std::hash::Hash,
+    P::Domain: FiniteDomain + Codomain<Element = <P::Domain as Domain>::Element>,
+    P::Codomain: Domain<Element = <P::Codomain as Codomain>::Element>,
+    <P::Domain as Domain>::Element: Clone + Eq + Hash,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd + Eq + Hash,
 {
     // For an inverted function, the domain and codomain are swapped
     type Domain = P::Codomain;
@@ -103,48 +163,473 @@ where
     
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-        // This is a simplified implementation that would need to be expanded
-        // for a real-world use case. In general, computing the inverse of a function
-        // is a complex operation that often requires additional constraints.
-        return Err(PolifunctionError::Other("Not implemented yet".to_string()));
+        // Scanning once both decides domain membership and computes the value
+        let preimage = self.preimage(input)?;
+        if preimage.is_empty() {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        Ok(PolifunctionValue::Set(preimage))
     }
     
-    fn in_domain(&self, _input: &<Self::Domain as Domain>::Element) -> bool {
-        // Determining if a value is in the domain of the inverse function
-        // would require evaluating the original function for all possible inputs,
-        // which is generally not feasible.
-        false
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        // The inverse is defined exactly where some input maps to the value
+        self.preimage(input).is_ok_and(|preimage| !preimage.is_empty())
     }
 }
 
-/// Sum of two polifunctions with compatible domains and codomains
-pub struct SumPolifunction<P1, P2>
+/// Inverse of a monotone interval-valued polifunction over a bounded real input range
+///
+/// The polifunction must be monotone on `input_range` (both endpoints of its
+/// output intervals nondecreasing, or both nonincreasing) and defined at the
+/// endpoints of the range. For a target `y`, the inverse brackets the set of
+/// inputs `x` with `y` in `p(x)` by bisecting for the first input whose upper
+/// endpoint reaches `y` and the last input whose lower endpoint does not
+/// exceed it. Both searches stop once their bracket is narrower than
+/// `tolerance`, and the outer ends of the brackets are returned, so the
+/// resulting interval always contains the true preimage. A search that needs
+/// more than `max_iter` steps fails with `ConvergenceError`; a target outside
+/// the range of `p` is a `DomainError`.
+pub struct MonotoneInverse<P> {
+    /// The monotone polifunction being inverted
+    original: P,
+    /// Range of inputs searched by bisection
+    input_range: RealInterval,
+    /// Width below which a bisection bracket is accepted
+    tolerance: f64,
+    /// Maximum number of bisection steps per search
+    max_iter: usize,
+}
+
+impl<P> MonotoneInverse<P>
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    /// Create a new inverse of `original` over `input_range`
+    pub fn new(original: P, input_range: RealInterval, tolerance: f64, max_iter: usize) -> Self {
+        Self {
+            original,
+            input_range,
+            tolerance,
+            max_iter,
+        }
+    }
+    
+    /// Output intervals at both ends of the input range, and whether `p` is increasing
+    fn endpoints(&self) -> Result<(Interval<f64>, Interval<f64>, bool), PolifunctionError> {
+        let at_lower = self.original.value_interval(&self.input_range.lower)?;
+        let at_upper = self.original.value_interval(&self.input_range.upper)?;
+        let increasing = at_upper.lower + at_upper.upper >= at_lower.lower + at_lower.upper;
+        Ok((at_lower, at_upper, increasing))
+    }
+    
+    /// Bracket the point where `predicate` switches from false to true along the range
+    ///
+    /// The input is traversed from the end where `p` is smallest, so the
+    /// predicates are monotone in the search variable `t`. Returns the
+    /// bracket in `t`, or None if the predicate never becomes true.
+    fn bisect(&self, increasing: bool, predicate: impl Fn(&Interval<f64>) -> bool)
+        -> Result<Option<(f64, f64)>, PolifunctionError> {
+        let (start, end) = (self.input_range.lower, self.input_range.upper);
+        let at = |t: f64| if increasing { t } else { start + end - t };
+        let holds = |t: f64| self.original.value_interval(&at(t)).map(|i| predicate(&i));
+        
+        if holds(start)? {
+            return Ok(Some((start, start)));
+        }
+        if !holds(end)? {
+            return Ok(None);
+        }
+        
+        let (mut low, mut high) = (start, end);
+        let mut iterations = 0;
+        while high - low >= self.tolerance {
+            if iterations == self.max_iter {
+                return Err(PolifunctionError::ConvergenceError);
+            }
+            iterations += 1;
+            
+            let mid = low + (high - low) / 2.0;
+            if holds(mid)? {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        
+        Ok(Some((low, high)))
+    }
+}
+
+impl<P> PolifunctionBase for MonotoneInverse<P>
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    type Domain = Unbounded<f64>;
+    type Codomain = RealInterval;
+    
+    fn evaluate(&self, input: &f64) -> Result<PolifunctionValue<f64>, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(PolifunctionValue::Interval(interval))
+    }
+    
+    fn in_domain(&self, input: &f64) -> bool {
+        // The range of a monotone polifunction is spanned by its values at the ends
+        match self.endpoints() {
+            Ok((at_lower, at_upper, _)) => {
+                let lowest = at_lower.lower.min(at_upper.lower);
+                let highest = at_lower.upper.max(at_upper.upper);
+                *input >= lowest && *input <= highest
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+impl<P> IntervalValuedPolifunction for MonotoneInverse<P>
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    fn value_interval(&self, input: &f64) -> Result<Interval<f64>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        let target = *input;
+        let (_, _, increasing) = self.endpoints()?;
+        
+        // First t whose output interval reaches the target from below
+        let first = self.bisect(increasing, |i| i.upper >= target)?
+            .ok_or(PolifunctionError::DomainError)?;
+        // First t whose output interval lies entirely above the target
+        let past_last = self.bisect(increasing, |i| i.lower > target)?;
+        
+        let (start, end) = (self.input_range.lower, self.input_range.upper);
+        let t_lower = first.0;
+        let t_upper = past_last.map_or(end, |bracket| bracket.1);
+        
+        // Map the search variable back to inputs
+        let (lower, upper) = if increasing {
+            (t_lower, t_upper)
+        } else {
+            (start + end - t_upper, start + end - t_lower)
+        };
+        
+        Ok(Interval {
+            lower,
+            upper,
+            lower_inclusive: true,
+            upper_inclusive: true,
+        })
+    }
+    
+    fn contains_value(&self, input: &f64, value: &f64) -> Result<bool, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.contains(value))
+    }
+    
+    fn interval_width(&self, input: &f64) -> Result<f64, PolifunctionError> {
+        let interval = self.value_interval(input)?;
+        Ok(interval.upper - interval.lower)
+    }
+}
+
+/// Invert a monotone interval-valued polifunction over a bounded real input range
+///
+/// See [`MonotoneInverse`] for the requirements on `p` and how the inverse is computed.
+pub fn invert_monotone<P>(p: P, input_range: RealInterval, tolerance: f64, max_iter: usize) -> MonotoneInverse<P>
+where
+    P: IntervalValuedPolifunction,
+    P::Domain: Domain<Element = f64>,
+    P::Codomain: Codomain<Element = f64>,
+{
+    MonotoneInverse::new(p, input_range, tolerance, max_iter)
+}
+
+/// A pointwise binary operation on polifunction values
+///
+/// Implementations define how single values and intervals combine. Sets are
+/// combined elementwise with `combine_single` by the combinators themselves,
+/// once enabled with their `with_sets` method, since collecting the combined
+/// elements needs `Hash + Eq`, which many numeric types (e.g. f64) do not provide.
+pub trait ValueAlgebra<T> {
+    /// Combine two single values
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError>;
+    
+    /// Combine two intervals into the interval of all possible results
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError>;
+}
+
+/// A value algebra with an identity element, usable for N-ary folds
+pub trait AlgebraIdentity<T>: ValueAlgebra<T> {
+    /// The element `e` with `combine_single(e, x) == x` for every `x`
+    fn identity(&self) -> T;
+}
+
+/// Addition of values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AddAlgebra;
+
+impl<T> ValueAlgebra<T> for AddAlgebra
+where
+    T: std::ops::Add<Output = T> + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        Ok(a + b)
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        Ok(a + b)
+    }
+}
+
+impl<T> AlgebraIdentity<T> for AddAlgebra
+where
+    T: std::ops::Add<Output = T> + Clone + Default,
+{
+    fn identity(&self) -> T {
+        T::default()
+    }
+}
+
+/// Subtraction of values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubAlgebra;
+
+impl<T> ValueAlgebra<T> for SubAlgebra
+where
+    T: std::ops::Sub<Output = T> + PartialOrd + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        Ok(a - b)
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        Ok(a - b)
+    }
+}
+
+/// Multiplication of values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MulAlgebra;
+
+impl<T> ValueAlgebra<T> for MulAlgebra
+where
+    T: std::ops::Mul<Output = T> + PartialOrd + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        Ok(a * b)
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        Ok(a * b)
+    }
+}
+
+impl<T> AlgebraIdentity<T> for MulAlgebra
+where
+    T: std::ops::Mul<Output = T> + PartialOrd + Clone + From<u8>,
+{
+    fn identity(&self) -> T {
+        T::from(1)
+    }
+}
+
+/// Division of values
+///
+/// Dividing by `zero`, or by an interval containing it, produces a
+/// `ComputationError`.
+#[derive(Debug, Clone, Copy)]
+pub struct DivAlgebra<T> {
+    zero: T,
+}
+
+impl<T> DivAlgebra<T> {
+    /// Create a division algebra that rejects divisors equal to `zero`
+    pub fn new(zero: T) -> Self {
+        Self { zero }
+    }
+}
+
+impl<T> ValueAlgebra<T> for DivAlgebra<T>
+where
+    T: std::ops::Div<Output = T> + PartialOrd + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        if b == self.zero {
+            return Err(PolifunctionError::ComputationError);
+        }
+        Ok(a / b)
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        a.checked_div(b, &self.zero)
+    }
+}
+
+/// Maximum of values
+///
+/// Incomparable values (such as NaN) produce a `ComputationError`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxAlgebra;
+
+impl<T> ValueAlgebra<T> for MaxAlgebra
+where
+    T: PartialOrd + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        match a.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Less) => Ok(b),
+            Some(_) => Ok(a),
+            None => Err(PolifunctionError::ComputationError),
+        }
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        // max(x, y) can only equal a tied lower bound if both operands reach it,
+        // but equals a tied upper bound as soon as either operand does
+        let (lower, lower_inclusive) = pick_endpoint(
+            (a.lower, a.lower_inclusive), (b.lower, b.lower_inclusive), std::cmp::Ordering::Greater, false)?;
+        let (upper, upper_inclusive) = pick_endpoint(
+            (a.upper, a.upper_inclusive), (b.upper, b.upper_inclusive), std::cmp::Ordering::Greater, true)?;
+        Ok(Interval { lower, upper, lower_inclusive, upper_inclusive })
+    }
+}
+
+/// Minimum of values
+///
+/// Incomparable values (such as NaN) produce a `ComputationError`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinAlgebra;
+
+impl<T> ValueAlgebra<T> for MinAlgebra
+where
+    T: PartialOrd + Clone,
+{
+    fn combine_single(&self, a: T, b: T) -> Result<T, PolifunctionError> {
+        match a.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Greater) => Ok(b),
+            Some(_) => Ok(a),
+            None => Err(PolifunctionError::ComputationError),
+        }
+    }
+    
+    fn combine_intervals(&self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        // Mirror image of MaxAlgebra
+        let (lower, lower_inclusive) = pick_endpoint(
+            (a.lower, a.lower_inclusive), (b.lower, b.lower_inclusive), std::cmp::Ordering::Less, true)?;
+        let (upper, upper_inclusive) = pick_endpoint(
+            (a.upper, a.upper_inclusive), (b.upper, b.upper_inclusive), std::cmp::Ordering::Less, false)?;
+        Ok(Interval { lower, upper, lower_inclusive, upper_inclusive })
+    }
+}
+
+/// Pick the endpoint that compares as `preferred` against the other one
+///
+/// On a tie the inclusivity flags are combined with OR if `tie_either` is set
+/// and with AND otherwise.
+fn pick_endpoint<T: PartialOrd>(a: (T, bool), b: (T, bool), preferred: std::cmp::Ordering, tie_either: bool)
+    -> Result<(T, bool), PolifunctionError> {
+    match a.0.partial_cmp(&b.0) {
+        Some(std::cmp::Ordering::Equal) => {
+            let inclusive = if tie_either { a.1 || b.1 } else { a.1 && b.1 };
+            Ok((a.0, inclusive))
+        },
+        Some(ordering) if ordering == preferred => Ok(a),
+        Some(_) => Ok(b),
+        None => Err(PolifunctionError::ComputationError),
+    }
+}
+
+/// Combine the elements of two sets pairwise and collect the results into a set
+///
+/// A single value is passed as a one-element operand. The results are
+/// collected with the set support `S`.
+fn combine_sets<T: Clone, A: ValueAlgebra<T>, S: SetSupport<T>>(algebra: &A, a: Vec<T>, b: Vec<T>)
+    -> Result<PolifunctionValue<T>, PolifunctionError> {
+    let mut combined = Vec::with_capacity(a.len() * b.len());
+    for v1 in &a {
+        for v2 in &b {
+            combined.push(algebra.combine_single(v1.clone(), v2.clone())?);
+        }
+    }
+    Ok(PolifunctionValue::Set(S::collect_set(combined)?))
+}
+
+/// Pointwise combination of two polifunctions with compatible domains and codomains
+///
+/// Errors from the operands are wrapped as coming from the left or right operand.
+/// `Set` values are combined according to the [`SetSupport`] `S`: the
+/// constructors give [`NoSets`], which only works for element types without
+/// set values such as f64, and [`BinaryOpPolifunction::with_sets`] switches to
+/// [`HashSets`] for `Hash + Eq` elements.
+pub struct BinaryOpPolifunction<P1, P2, A, S = NoSets>
 where
     P1: PolifunctionBase,
     P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
-    <P1::Codomain as Codomain>::Element: std::ops::Add<Output = <P1::Codomain as Codomain>::Element> + Clone,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
 {
     p1: P1,
     p2: P2,
+    algebra: A,
+    sets: PhantomData<S>,
 }
 
-impl<P1, P2> SumPolifunction<P1, P2>
+impl<P1, P2, A> BinaryOpPolifunction<P1, P2, A>
 where
     P1: PolifunctionBase,
     P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
-    <P1::Codomain as Codomain>::Element: std::ops::Add<Output = <P1::Codomain as Codomain>::Element> + Clone,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
 {
-    /// Create a new sum of two polifunctions
-    pub fn new(p1: P1, p2: P2) -> Self {
-        Self { p1, p2 }
+    /// Create a new combination of two polifunctions using the default algebra
+    pub fn new(p1: P1, p2: P2) -> Self
+    where
+        A: Default,
+    {
+        Self::with_algebra(p1, p2, A::default())
+    }
+    
+    /// Create a new combination of two polifunctions using the given algebra
+    pub fn with_algebra(p1: P1, p2: P2, algebra: A) -> Self {
+        Self {
+            p1,
+            p2,
+            algebra,
+            sets: PhantomData,
+        }
+    }
+    
+    /// Combine Set values element by element
+    ///
+    /// Every pair of elements is combined and the results are collected into a
+    /// set; a single value combined with a set acts as a one-element set.
+    pub fn with_sets(self) -> BinaryOpPolifunction<P1, P2, A, HashSets>
+    where
+        <P1::Codomain as Codomain>::Element: Hash + Eq,
+    {
+        BinaryOpPolifunction {
+            p1: self.p1,
+            p2: self.p2,
+            algebra: self.algebra,
+            sets: PhantomData,
+        }
     }
 }
 
-impl<P1, P2> PolifunctionBase for SumPolifunction<P1, P2>
+impl<P1, P2, A, S> PolifunctionBase for BinaryOpPolifunction<P1, P2, A, S>
 where
     P1: PolifunctionBase,
     P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
-    <P1::Codomain as Codomain>::Element: std::ops::Add<Output = <P1::Codomain as Codomain>::Element> + Clone,
+    A: ValueAlgebra<<P1::Codomain as Codomain>::Element>,
+    S: SetSupport<<P1::Codomain as Codomain>::Element>,
+    <P1::Codomain as Codomain>::Element: Clone,
+    <P1::Domain as Domain>::Element: std::fmt::Debug,
 {
     type Domain = P1::Domain;
     type Codomain = P1::Codomain;
@@ -152,21 +637,39 @@ where
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
         // Evaluate both polifunctions
-        let result1 = self.p1.evaluate(input)?;
-        let result2 = self.p2.evaluate(input)?;
+        let result1 = self.p1.evaluate(input).map_err(|e| e.context("left operand"))?;
+        let result2 = self.p2.evaluate(input).map_err(|e| e.context("right operand"))?;
         
         // Combine the results based on their types
-        // This is a simplified implementation that only handles Single values
         match (result1, result2) {
             (PolifunctionValue::Single(v1), PolifunctionValue::Single(v2)) => {
-                Ok(PolifunctionValue::Single(v1 + v2))
+                Ok(PolifunctionValue::Single(self.algebra.combine_single(v1, v2)?))
+            },
+            (PolifunctionValue::Set(s1), PolifunctionValue::Set(s2)) => {
+                combine_sets::<_, _, S>(&self.algebra, s1.into_iter().collect(), s2.into_iter().collect())
+            },
+            (PolifunctionValue::Single(v), PolifunctionValue::Set(s)) => {
+                combine_sets::<_, _, S>(&self.algebra, vec![v], s.into_iter().collect())
+            },
+            (PolifunctionValue::Set(s), PolifunctionValue::Single(v)) => {
+                combine_sets::<_, _, S>(&self.algebra, s.into_iter().collect(), vec![v])
             },
-            // Other combinations would require more complex handling
-            _ => Err(PolifunctionError::Other("Complex operation not yet implemented".to_string())),
+            (PolifunctionValue::Interval(i1), PolifunctionValue::Interval(i2)) => {
+                Ok(PolifunctionValue::Interval(self.algebra.combine_intervals(i1, i2)?))
+            },
+            // A single value acts as the degenerate interval [v, v]
+            (PolifunctionValue::Single(v), PolifunctionValue::Interval(i)) => {
+                Ok(PolifunctionValue::Interval(self.algebra.combine_intervals(degenerate_interval(v), i)?))
+            },
+            (PolifunctionValue::Interval(i), PolifunctionValue::Single(v)) => {
+                Ok(PolifunctionValue::Interval(self.algebra.combine_intervals(i, degenerate_interval(v))?))
+            },
+            // Mixed sets and intervals, distributions and fuzzy sets are not supported yet
+            _ => Err(PolifunctionError::InvalidOperation),
         }
     }
     
@@ -176,249 +679,2663 @@ where
     }
 }
 
-/// Create a constant polifunction that always returns the same value
-pub fn constant<D, C>(value: C::Element, domain: D, codomain: C) -> impl PolifunctionBase<Domain = D, Codomain = C>
+/// The closed interval [v, v] containing only `v`
+fn degenerate_interval<T: Clone>(v: T) -> Interval<T> {
+    Interval {
+        lower: v.clone(),
+        upper: v,
+        lower_inclusive: true,
+        upper_inclusive: true,
+    }
+}
+
+/// Sum of two polifunctions with compatible domains and codomains
+///
+/// Single values and intervals are added for any `T: Add + Clone`. Sets of
+/// `Hash + Eq` elements are added by their Minkowski sum once enabled with
+/// [`BinaryOpPolifunction::with_sets`].
+pub type SumPolifunction<P1, P2> = BinaryOpPolifunction<P1, P2, AddAlgebra>;
+
+/// Difference of two polifunctions with compatible domains and codomains
+///
+/// Intervals are subtracted crosswise, `[a, b] - [c, d] = [a - d, b - c]`.
+/// Sets of `Hash + Eq` elements are subtracted pairwise once enabled with
+/// [`BinaryOpPolifunction::with_sets`].
+pub type DiffPolifunction<P1, P2> = BinaryOpPolifunction<P1, P2, SubAlgebra>;
+
+/// Product of two polifunctions with compatible domains and codomains
+///
+/// Intervals are multiplied as the hull of the endpoint products. Sets of
+/// `Hash + Eq` elements are combined into all pairwise products once enabled
+/// with [`BinaryOpPolifunction::with_sets`].
+pub type ProductPolifunction<P1, P2> = BinaryOpPolifunction<P1, P2, MulAlgebra>;
+
+/// Quotient of two polifunctions with compatible domains and codomains
+///
+/// Construct it with `QuotientPolifunction::with_algebra(p1, p2, DivAlgebra::new(zero))`.
+/// Intervals are divided by the hull of the endpoint quotients. A divisor
+/// equal to `zero`, or a divisor interval containing it, produces a
+/// `ComputationError`. Sets of `Hash + Eq` elements are divided pairwise once
+/// enabled with [`BinaryOpPolifunction::with_sets`].
+pub type QuotientPolifunction<P1, P2> = BinaryOpPolifunction<
+    P1, P2, DivAlgebra<<<P1 as PolifunctionBase>::Codomain as Codomain>::Element>>;
+
+/// Pointwise minimum of two polifunctions with compatible domains and codomains
+///
+/// Intervals are combined endpoint by endpoint. Incomparable values (such as
+/// NaN) produce a `ComputationError`. Sets of `Hash + Eq` elements are combined
+/// pairwise once enabled with [`BinaryOpPolifunction::with_sets`].
+pub type MinPolifunction<P1, P2> = BinaryOpPolifunction<P1, P2, MinAlgebra>;
+
+/// Pointwise maximum of two polifunctions with compatible domains and codomains
+///
+/// Intervals are combined endpoint by endpoint. Incomparable values (such as
+/// NaN) produce a `ComputationError`. Sets of `Hash + Eq` elements are combined
+/// pairwise once enabled with [`BinaryOpPolifunction::with_sets`].
+pub type MaxPolifunction<P1, P2> = BinaryOpPolifunction<P1, P2, MaxAlgebra>;
+
+/// Polifunction whose outputs are multiplied by a constant factor
+///
+/// `Set` values are scaled according to the [`SetSupport`] `S`, [`NoSets`]
+/// unless switched to [`HashSets`] with [`ScaledPolifunction::with_sets`].
+pub struct ScaledPolifunction<P, A, S = NoSets>
 where
-    D: Domain,
-    C: Codomain,
-    C::Element: Clone,
+    P: PolifunctionBase,
+    A: ValueAlgebra<<P::Codomain as Codomain>::Element>,
 {
-    LiftedPolifunction::new(
-        move |_| Ok(value.clone()),
-        domain,
-        codomain,
-    )
+    inner: P,
+    factor: <P::Codomain as Codomain>::Element,
+    algebra: A,
+    sets: PhantomData<S>,
 }
 
-/// Compose two polifunctions
-pub fn compose<P1, P2>(p1: P1, p2: P2) -> impl PolifunctionBase<Domain = P2::Domain, Codomain = P1::Codomain>
+impl<P, A> ScaledPolifunction<P, A>
 where
-    P1: PolifunctionBase,
-    P2: PolifunctionBase,
-    <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element>,
-    <P1::Codomain as Codomain>::Element: Clone,
+    P: PolifunctionBase,
+    A: ValueAlgebra<<P::Codomain as Codomain>::Element>,
 {
-    struct ComposedPolifunction<P1, P2> {
-        p1: P1,
-        p2: P2,
+    /// Create a new scaled polifunction combining outputs with the factor using the given algebra
+    pub fn with_algebra(inner: P, factor: <P::Codomain as Codomain>::Element, algebra: A) -> Self {
+        Self {
+            inner,
+            factor,
+            algebra,
+            sets: PhantomData,
+        }
     }
     
-    impl<P1, P2> PolifunctionBase for ComposedPolifunction<P1, P2>
+    /// Scale Set values element by element
+    pub fn with_sets(self) -> ScaledPolifunction<P, A, HashSets>
     where
-        P1: PolifunctionBase,
-        P2: PolifunctionBase,
-        <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element>,
-        <P1::Codomain as Codomain>::Element: Clone,
+        <P::Codomain as Codomain>::Element: Hash + Eq,
     {
-        type Domain = P2::Domain;
-        type Codomain = P1::Codomain;
-        
-        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-            if !self.in_domain(input) {
-                return Err(PolifunctionError::DomainError);
-            }
-            
-            // Evaluate p2 first
-            let intermediate_result = self.p2.evaluate(input)?;
-            
-            // This is a simplified implementation that only handles Single values
-            match intermediate_result {
-                PolifunctionValue::Single(v) => {
-                    let p1_input = v.into();
-                    self.p1.evaluate(&p1_input)
-                },
-                // Other cases would require more complex handling
-                _ => Err(PolifunctionError::Other("Complex composition not yet implemented".to_string())),
-            }
+        ScaledPolifunction {
+            inner: self.inner,
+            factor: self.factor,
+            algebra: self.algebra,
+            sets: PhantomData,
+        }
+    }
+}
+
+/// Combine the values of (value, weight) pairs with a factor, keeping the weights
+fn scale_pairs<T: Clone, A: ValueAlgebra<T>>(algebra: &A, factor: &T, pairs: &[(T, f64)])
+    -> Result<Vec<(T, f64)>, PolifunctionError> {
+    pairs
+        .iter()
+        .map(|(v, w)| Ok((algebra.combine_single(v.clone(), factor.clone())?, *w)))
+        .collect()
+}
+
+impl<P, A, S> PolifunctionBase for ScaledPolifunction<P, A, S>
+where
+    P: PolifunctionBase,
+    A: ValueAlgebra<<P::Codomain as Codomain>::Element>,
+    S: SetSupport<<P::Codomain as Codomain>::Element>,
+    <P::Codomain as Codomain>::Element: Clone,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
-            self.p2.in_domain(input)
+        match self.inner.evaluate(input)? {
+            PolifunctionValue::Single(v) => {
+                Ok(PolifunctionValue::Single(self.algebra.combine_single(v, self.factor.clone())?))
+            },
+            PolifunctionValue::Set(s) => {
+                combine_sets::<_, _, S>(&self.algebra, s.into_iter().collect(), vec![self.factor.clone()])
+            },
+            // Interval arithmetic keeps the result well-formed for negative factors
+            PolifunctionValue::Interval(i) => {
+                Ok(PolifunctionValue::Interval(self.algebra.combine_intervals(i, degenerate_interval(self.factor.clone()))?))
+            },
+            // Scaling the values leaves their probabilities and degrees unchanged
+            PolifunctionValue::Distribution(d) => {
+                let pairs = scale_pairs(&self.algebra, &self.factor, d.pairs())?;
+                Ok(PolifunctionValue::Distribution(ProbabilityDistribution::new(pairs)?))
+            },
+            PolifunctionValue::FuzzySet(f) => {
+                let pairs = scale_pairs(&self.algebra, &self.factor, f.pairs())?;
+                Ok(PolifunctionValue::FuzzySet(FuzzySet::new(pairs)?))
+            },
+            _ => Err(PolifunctionError::InvalidOperation),
         }
     }
     
-    ComposedPolifunction { p1, p2 }
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
 }
 
-/// Convert a set-valued polifunction to an interval-valued one by taking the extrema
-pub fn to_interval<P>(p: P) -> impl IntervalValuedPolifunction<Domain = P::Domain, Codomain = P::Codomain>
+/// Multiply every output value of a polifunction by a constant factor
+///
+/// Interval endpoints are swapped for a negative factor, so `[1, 2)` scaled by
+/// -2 becomes `(-4, -2]`; this is what the `PartialOrd` bound is needed for.
+/// The values of distributions and fuzzy sets are scaled and their
+/// probabilities and degrees kept.
+///
+/// Call [`ScaledPolifunction::with_sets`] on the result to scale set values
+/// of `Hash + Eq` elements elementwise.
+pub fn scale<P>(p: P, factor: <P::Codomain as Codomain>::Element) -> ScaledPolifunction<P, MulAlgebra>
 where
-    P: SetValuedPolifunction,
-    <P::Codomain as Codomain>::Element: Clone + Ord,
+    P: PolifunctionBase,
+    <P::Codomain as Codomain>::Element: std::ops::Mul<Output = <P::Codomain as Codomain>::Element> + PartialOrd + Clone,
 {
-    struct SetToIntervalPolifunction<P> {
-        original: P,
-    }
-    
-    impl<P> PolifunctionBase for SetToIntervalPolifunction<P>
-    where
-        P: SetValuedPolifunction,
-        <P::Codomain as Codomain>::Element: Clone + Ord,
-    {
-        type Domain = P::Domain;
-        type Codomain = P::Codomain;
+    ScaledPolifunction::with_algebra(p, factor, MulAlgebra)
+}
+
+/// Polifunction restricted to a subset of its original domain
+///
+/// Set-valued and interval-valued polifunctions stay set-valued and
+/// interval-valued when restricted.
+pub struct RestrictedPolifunction<P, D2>
+where
+    P: PolifunctionBase,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    /// The original polifunction
+    inner: P,
+    /// Domain the inputs are additionally restricted to
+    restriction: D2,
+}
+
+impl<P, D2> RestrictedPolifunction<P, D2>
+where
+    P: PolifunctionBase,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    /// Create a new restriction of a polifunction to a smaller domain
+    pub fn new(inner: P, restriction: D2) -> Self {
+        Self { inner, restriction }
+    }
+}
+
+impl<P, D2> PolifunctionBase for RestrictedPolifunction<P, D2>
+where
+    P: PolifunctionBase,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
         
-        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-            if !self.in_domain(input) {
-                return Err(PolifunctionError::DomainError);
-            }
-            
-            let set = self.original.value_set(input)?;
-            if set.is_empty() {
-                return Err(PolifunctionError::ComputationError);
-            }
-            
-            let min = set.iter().min().unwrap().clone();
-            let max = set.iter().max().unwrap().clone();
-            
-            Ok(PolifunctionValue::Interval(super::polifunction::Interval {
-                lower: min,
-                upper: max,
-                lower_inclusive: true,
-                upper_inclusive: true,
-            }))
+        self.inner.evaluate(input)
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        // The input must be in both the original and the restricting domain
+        self.restriction.contains(input) && self.inner.in_domain(input)
+    }
+}
+
+impl<P, D2> SetValuedPolifunction for RestrictedPolifunction<P, D2>
+where
+    P: SetValuedPolifunction,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
-            self.original.in_domain(input)
+        self.inner.value_set(input)
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
+        
+        self.inner.contains_value(input, value)
     }
     
-    impl<P> IntervalValuedPolifunction for SetToIntervalPolifunction<P>
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        self.inner.cardinality(input)
+    }
+}
+
+impl<P, D2> IntervalValuedPolifunction for RestrictedPolifunction<P, D2>
+where
+    P: IntervalValuedPolifunction,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        self.inner.value_interval(input)
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        self.inner.contains_value(input, value)
+    }
+    
+    fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
     where
-        P: SetValuedPolifunction,
-        <P::Codomain as Codomain>::Element: Clone + Ord,
+        <Self::Codomain as Codomain>::Element: std::ops::Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
     {
-        fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<super::polifunction::Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-            let set = self.original.value_set(input)?;
-            if set.is_empty() {
-                return Err(PolifunctionError::ComputationError);
-            }
-            
-            let min = set.iter().min().unwrap().clone();
-            let max = set.iter().max().unwrap().clone();
-            
-            Ok(super::polifunction::Interval {
-                lower: min,
-                upper: max,
-                lower_inclusive: true,
-                upper_inclusive: true,
-            })
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
-                         value: &<Self::Codomain as Codomain>::Element)
-            -> Result<bool, PolifunctionError> {
-            let interval = self.value_interval(input)?;
-            
-            Ok(value >= &interval.lower && value <= &interval.upper)
+        self.inner.interval_width(input)
+    }
+}
+
+/// Restrict a polifunction to the inputs contained in a smaller domain
+pub fn restrict<P, D2>(p: P, new_domain: D2) -> RestrictedPolifunction<P, D2>
+where
+    P: PolifunctionBase,
+    D2: Domain<Element = <P::Domain as Domain>::Element>,
+{
+    RestrictedPolifunction::new(p, new_domain)
+}
+
+/// Polifunction that falls back to a secondary polifunction where the primary one is not defined
+///
+/// An input is in the domain if it is in the domain of either polifunction.
+/// The primary polifunction is used wherever it is defined and succeeds; a
+/// `DomainError` from it falls back to the secondary one. Other errors are
+/// returned as they are unless [`FallbackPolifunction::fall_back_on_errors`]
+/// is set. If the secondary polifunction cannot be used either, the error of
+/// the primary one is returned.
+pub struct FallbackPolifunction<P1, P2>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    primary: P1,
+    secondary: P2,
+    /// Whether errors other than `DomainError` also fall back
+    fall_back_on_errors: bool,
+}
+
+impl<P1, P2> FallbackPolifunction<P1, P2>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    /// Create a new fallback from a primary to a secondary polifunction
+    pub fn new(primary: P1, secondary: P2) -> Self {
+        Self {
+            primary,
+            secondary,
+            fall_back_on_errors: false,
+        }
+    }
+    
+    /// Fall back to the secondary polifunction on any error of the primary one
+    pub fn fall_back_on_errors(mut self) -> Self {
+        self.fall_back_on_errors = true;
+        self
+    }
+}
+
+impl<P1, P2> PolifunctionBase for FallbackPolifunction<P1, P2>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
-        where
-            <Self::Codomain as Codomain>::Element: std::ops::Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
-        {
-            let interval = self.value_interval(input)?;
-            Ok(interval.upper.clone() - interval.lower.clone())
+        let primary_error = if self.primary.in_domain(input) {
+            match self.primary.evaluate(input) {
+                Ok(value) => return Ok(value),
+                Err(e) if e.is_domain_error() || self.fall_back_on_errors => e,
+                Err(e) => return Err(e),
+            }
+        } else {
+            PolifunctionError::DomainError
+        };
+        
+        if !self.secondary.in_domain(input) {
+            return Err(primary_error);
         }
+        self.secondary.evaluate(input)
     }
     
-    SetToIntervalPolifunction { original: p }
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.primary.in_domain(input) || self.secondary.in_domain(input)
+    }
 }
 
-/// Convert a standard function to a set-valued polifunction
-pub fn lift_to_set<F, D, C>(f: F, domain: D, codomain: C) -> impl SetValuedPolifunction<Domain = D, Codomain = C>
+/// Polifunction that picks one of two polifunctions per input by a predicate
+///
+/// Inputs for which the predicate holds go to `if_true`, all others to
+/// `if_false`. Only the selected branch decides whether an input is in the
+/// domain. With [`ConditionalPolifunction::precheck`], inputs outside a given
+/// domain are rejected before the predicate is called.
+pub struct ConditionalPolifunction<F, P1, P2>
 where
-    F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
-    D: Domain,
-    C: Codomain,
-    C::Element: Clone + std::hash::Hash + Eq,
+    P1: PolifunctionBase,
+{
+    predicate: F,
+    if_true: P1,
+    if_false: P2,
+    /// Domain checked before calling the predicate, if any
+    precheck: Option<PieceDomain<<P1::Domain as Domain>::Element>>,
+}
+
+impl<F, P1, P2> ConditionalPolifunction<F, P1, P2>
+where
+    F: Fn(&<P1::Domain as Domain>::Element) -> bool,
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Codomain = P1::Codomain>,
+    P2::Domain: Domain<Element = <P1::Domain as Domain>::Element>,
 {
-    struct LiftedSetValuedPolifunction<F, D, C> {
-        function: F,
-        domain: D,
-        codomain: C,
-        _phantom: PhantomData<(D::Element, C::Element)>,
+    /// Create a new conditional polifunction from a predicate and its two branches
+    pub fn new(predicate: F, if_true: P1, if_false: P2) -> Self {
+        Self {
+            predicate,
+            if_true,
+            if_false,
+            precheck: None,
+        }
     }
     
-    impl<F, D, C> PolifunctionBase for LiftedSetValuedPolifunction<F, D, C>
-    where
-        F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
-        D: Domain,
-        C: Codomain,
-        C::Element: Clone + std::hash::Hash + Eq,
-    {
-        type Domain = D;
-        type Codomain = C;
+    /// Reject inputs outside `domain` before calling the predicate
+    ///
+    /// Use this when the predicate is only meaningful (or only safe to call)
+    /// for inputs in `domain`.
+    pub fn precheck(mut self, domain: impl Domain<Element = <P1::Domain as Domain>::Element> + 'static) -> Self {
+        self.precheck = Some(Box::new(domain));
+        self
+    }
+    
+    /// Check the precheck domain, if any
+    fn passes_precheck(&self, input: &<P1::Domain as Domain>::Element) -> bool {
+        self.precheck.as_ref().is_none_or(|domain| domain.contains(input))
+    }
+}
+
+impl<F, P1, P2> PolifunctionBase for ConditionalPolifunction<F, P1, P2>
+where
+    F: Fn(&<P1::Domain as Domain>::Element) -> bool,
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Codomain = P1::Codomain>,
+    P2::Domain: Domain<Element = <P1::Domain as Domain>::Element>,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.passes_precheck(input) {
+            return Err(PolifunctionError::DomainError);
+        }
         
-        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-            if !self.in_domain(input) {
-                return Err(PolifunctionError::DomainError);
-            }
-            
-            let value = (self.function)(input)?;
-            let mut set = HashSet::new();
-            set.insert(value);
-            
-            Ok(PolifunctionValue::Set(set))
+        if (self.predicate)(input) {
+            self.if_true.evaluate(input)
+        } else {
+            self.if_false.evaluate(input)
+        }
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        if !self.passes_precheck(input) {
+            return false;
         }
         
-        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
-            self.domain.contains(input)
+        if (self.predicate)(input) {
+            self.if_true.in_domain(input)
+        } else {
+            self.if_false.in_domain(input)
+        }
+    }
+}
+
+/// Pick one of two polifunctions per input by a predicate
+///
+/// See [`ConditionalPolifunction`]; call `precheck` on the result to guard
+/// the predicate with a domain.
+pub fn conditional<F, P1, P2>(predicate: F, if_true: P1, if_false: P2) -> ConditionalPolifunction<F, P1, P2>
+where
+    F: Fn(&<P1::Domain as Domain>::Element) -> bool,
+    P1: PolifunctionBase,
+    P2: PolifunctionBase<Codomain = P1::Codomain>,
+    P2::Domain: Domain<Element = <P1::Domain as Domain>::Element>,
+{
+    ConditionalPolifunction::new(predicate, if_true, if_false)
+}
+
+/// How a [`PiecewisePolifunction`] evaluates inputs contained in more than one piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Use the first piece containing the input
+    #[default]
+    FirstMatch,
+    /// Evaluate every piece containing the input and merge the values
+    ///
+    /// Intervals (with single values as degenerate intervals) are merged into
+    /// their hull, and equal single values into one. Distinct single values
+    /// and sets are united into a set only if set merging was enabled with
+    /// [`PiecewisePolifunction::with_sets`]; otherwise, as always for element
+    /// types without `Hash + Eq` such as f64, they fail with an
+    /// `InvalidOperation` saying so.
+    UnionValues,
+    /// Fail with an `InvalidOperation` if more than one piece contains the input
+    Error,
+}
+
+/// Boxed domain of one piece of a [`PiecewisePolifunction`]
+pub type PieceDomain<X> = Box<dyn Domain<Element = X>>;
+
+/// Polifunction defined piecewise by a list of (domain, polifunction) pieces
+///
+/// A piece applies to an input if its domain contains the input and its
+/// polifunction is defined there. An input is in the domain if any piece
+/// applies to it; inputs claimed by several pieces are handled according to
+/// the [`OverlapPolicy`]. All pieces share one polifunction type; use
+/// [`BoxedPolifunction`](super::dynamic::BoxedPolifunction) to mix types.
+pub struct PiecewisePolifunction<P, S = NoSets>
+where
+    P: PolifunctionBase,
+{
+    pieces: Vec<(PieceDomain<<P::Domain as Domain>::Element>, P)>,
+    policy: OverlapPolicy,
+    sets: PhantomData<S>,
+}
+
+impl<P> PiecewisePolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// Create a new piecewise polifunction from its pieces, in priority order
+    pub fn new(pieces: Vec<(PieceDomain<<P::Domain as Domain>::Element>, P)>, policy: OverlapPolicy) -> Self {
+        Self {
+            pieces,
+            policy,
+            sets: PhantomData,
         }
     }
     
-    impl<F, D, C> SetValuedPolifunction for LiftedSetValuedPolifunction<F, D, C>
+    /// Unite distinct single values and sets into a set under [`OverlapPolicy::UnionValues`]
+    pub fn with_sets(self) -> PiecewisePolifunction<P, HashSets>
     where
-        F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
-        D: Domain,
-        C: Codomain,
-        C::Element: Clone + std::hash::Hash + Eq,
+        <P::Codomain as Codomain>::Element: Hash + Eq,
     {
-        fn value_set(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-            if !self.in_domain(input) {
-                return Err(PolifunctionError::DomainError);
+        PiecewisePolifunction {
+            pieces: self.pieces,
+            policy: self.policy,
+            sets: PhantomData,
+        }
+    }
+}
+
+impl<P, S> PiecewisePolifunction<P, S>
+where
+    P: PolifunctionBase,
+{
+    
+    /// Iterate over the polifunctions of the pieces that apply to an input
+    fn matching_pieces<'a>(&'a self, input: &'a <P::Domain as Domain>::Element) -> impl Iterator<Item = &'a P> + 'a {
+        self.pieces
+            .iter()
+            .filter(move |(domain, p)| domain.contains(input) && p.in_domain(input))
+            .map(|(_, p)| p)
+    }
+}
+
+impl<P, S> PiecewisePolifunction<P, S>
+where
+    P: PolifunctionBase,
+    S: SetSupport<<P::Codomain as Codomain>::Element>,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd,
+{
+    /// Merge the values of overlapping pieces for [`OverlapPolicy::UnionValues`]
+    fn merge(&self, values: Vec<PolifunctionValue<<P::Codomain as Codomain>::Element>>)
+        -> Result<PolifunctionValue<<P::Codomain as Codomain>::Element>, PolifunctionError> {
+        let any_interval = values.iter().any(|v| matches!(v, PolifunctionValue::Interval(_)));
+        let any_set = values.iter().any(|v| matches!(v, PolifunctionValue::Set(_)));
+        
+        if any_interval && !any_set {
+            let mut hull: Option<Interval<_>> = None;
+            for value in values {
+                let interval = match value {
+                    PolifunctionValue::Interval(i) => i,
+                    PolifunctionValue::Single(v) => degenerate_interval(v),
+                    _ => return Err(PolifunctionError::InvalidOperation),
+                };
+                hull = Some(match hull {
+                    None => interval,
+                    Some(h) => h.hull(&interval).ok_or(PolifunctionError::ComputationError)?,
+                });
             }
-            
-            let value = (self.function)(input)?;
-            let mut set = HashSet::new();
-            set.insert(value);
-            
-            Ok(set)
+            return hull.map(PolifunctionValue::Interval).ok_or(PolifunctionError::EmptyResult);
         }
         
-        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
-                         value: &<Self::Codomain as Codomain>::Element)
-            -> Result<bool, PolifunctionError> {
-            if !self.in_domain(input) {
-                return Err(PolifunctionError::DomainError);
+        let mut elements = Vec::new();
+        for value in values {
+            match value {
+                PolifunctionValue::Single(v) => elements.push(v),
+                PolifunctionValue::Set(s) => elements.extend(s),
+                _ => return Err(PolifunctionError::InvalidOperation),
             }
-            
-            let result = (self.function)(input)?;
-            Ok(&result == value)
         }
         
-        fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
-            -> Result<usize, PolifunctionError> {
+        if !any_set && elements.windows(2).all(|pair| pair[0] == pair[1]) {
+            return elements.pop().map(PolifunctionValue::Single).ok_or(PolifunctionError::EmptyResult);
+        }
+        let set = S::collect_set(elements)
+            .map_err(|e| e.context("overlapping pieces have distinct values and with_sets is not enabled"))?;
+        Ok(PolifunctionValue::Set(set))
+    }
+}
+
+impl<P, S> PolifunctionBase for PiecewisePolifunction<P, S>
+where
+    P: PolifunctionBase,
+    S: SetSupport<<P::Codomain as Codomain>::Element>,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let mut matching = self.matching_pieces(input);
+        let first = matching.next().ok_or(PolifunctionError::DomainError)?;
+        
+        match self.policy {
+            OverlapPolicy::FirstMatch => first.evaluate(input),
+            OverlapPolicy::Error => {
+                let others = matching.count();
+                if others > 0 {
+                    return Err(PolifunctionError::InvalidOperation
+                        .context(format!("input is contained in {} overlapping pieces", others + 1)));
+                }
+                first.evaluate(input)
+            },
+            OverlapPolicy::UnionValues => {
+                let mut values = vec![first.evaluate(input)?];
+                for p in matching {
+                    values.push(p.evaluate(input)?);
+                }
+                
+                if values.len() == 1 {
+                    return Ok(values.remove(0));
+                }
+                self.merge(values)
+            },
+        }
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.matching_pieces(input).next().is_some()
+    }
+}
+
+/// Polifunction whose output values are transformed by a function
+///
+/// `Single` values are mapped directly, and every mapped value must lie in the
+/// new codomain. The first error returned by the function fails the whole
+/// evaluation. `Set` values are mapped according to the [`SetSupport`] `S`,
+/// [`NoSets`] unless switched to [`HashSets`] with
+/// [`MappedPolifunction::with_sets`], since collecting the mapped elements
+/// needs `Hash + Eq`. Mapping the endpoints of
+/// an interval only yields the image interval if the function is monotone, so
+/// `Interval` values are likewise rejected unless the map was declared
+/// monotone with [`MappedPolifunction::assume_monotone`]. The mapped endpoints
+/// are reordered if the function is decreasing.
+pub struct MappedPolifunction<P, F, C2, S = NoSets>
+where
+    P: PolifunctionBase,
+    F: Fn(&<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError>,
+    C2: Codomain,
+{
+    /// The original polifunction
+    inner: P,
+    /// Function applied to every output value
+    function: F,
+    /// Codomain of the mapped values
+    codomain: C2,
+    /// Set by `assume_monotone` to order the mapped interval endpoints
+    compare_endpoints: Option<EndpointOrder<C2::Element>>,
+    sets: PhantomData<S>,
+}
+
+/// Function comparing two interval endpoints, available for `PartialOrd` elements
+type EndpointOrder<T> = fn(&T, &T) -> Option<std::cmp::Ordering>;
+
+impl<P, F, C2> MappedPolifunction<P, F, C2>
+where
+    P: PolifunctionBase,
+    F: Fn(&<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError>,
+    C2: Codomain,
+{
+    /// Create a new mapped polifunction
+    pub fn new(inner: P, function: F, codomain: C2) -> Self {
+        Self {
+            inner,
+            function,
+            codomain,
+            compare_endpoints: None,
+            sets: PhantomData,
+        }
+    }
+    
+    /// Map Set values element by element
+    pub fn with_sets(self) -> MappedPolifunction<P, F, C2, HashSets>
+    where
+        C2::Element: Hash + Eq,
+    {
+        MappedPolifunction {
+            inner: self.inner,
+            function: self.function,
+            codomain: self.codomain,
+            compare_endpoints: self.compare_endpoints,
+            sets: PhantomData,
+        }
+    }
+}
+
+impl<P, F, C2, S> MappedPolifunction<P, F, C2, S>
+where
+    P: PolifunctionBase,
+    F: Fn(&<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError>,
+    C2: Codomain,
+{
+    
+    /// Declare the function monotone, allowing interval values to be mapped
+    pub fn assume_monotone(mut self) -> Self
+    where
+        C2::Element: PartialOrd,
+    {
+        self.compare_endpoints = Some(PartialOrd::partial_cmp);
+        self
+    }
+    
+    /// Apply the function to one value and check the result against the codomain
+    fn map_value(&self, value: &<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError> {
+        let mapped = (self.function)(value)?;
+        if !self.codomain.contains(&mapped) {
+            return Err(PolifunctionError::ComputationError.context("mapped value is outside the codomain"));
+        }
+        Ok(mapped)
+    }
+}
+
+impl<P, F, C2, S> PolifunctionBase for MappedPolifunction<P, F, C2, S>
+where
+    P: PolifunctionBase,
+    F: Fn(&<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError>,
+    C2: Codomain,
+    S: SetSupport<C2::Element>,
+{
+    type Domain = P::Domain;
+    type Codomain = C2;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        match self.inner.evaluate(input)? {
+            PolifunctionValue::Single(v) => Ok(PolifunctionValue::Single(self.map_value(&v)?)),
+            PolifunctionValue::Set(s) => {
+                let mapped = s.iter().map(|v| self.map_value(v)).collect::<Result<Vec<_>, _>>()?;
+                Ok(PolifunctionValue::Set(S::collect_set(mapped)?))
+            },
+            PolifunctionValue::Interval(i) => {
+                let compare = self.compare_endpoints.ok_or(PolifunctionError::InvalidOperation)?;
+                let lower = (self.map_value(&i.lower)?, i.lower_inclusive);
+                let upper = (self.map_value(&i.upper)?, i.upper_inclusive);
+                
+                // A decreasing map reverses the endpoints
+                let (lower, upper) = match compare(&lower.0, &upper.0) {
+                    Some(std::cmp::Ordering::Greater) => (upper, lower),
+                    Some(_) => (lower, upper),
+                    None => return Err(PolifunctionError::ComputationError),
+                };
+                
+                Ok(PolifunctionValue::Interval(Interval {
+                    lower: lower.0,
+                    upper: upper.0,
+                    lower_inclusive: lower.1,
+                    upper_inclusive: upper.1,
+                }))
+            },
+            _ => Err(PolifunctionError::InvalidOperation),
+        }
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+/// Transform every output value of a polifunction with a fallible function
+///
+/// Call [`MappedPolifunction::with_sets`] on the result to map set values.
+/// Interval endpoints are only mapped if the function is monotone: call
+/// [`MappedPolifunction::assume_monotone`] on the result to map interval values.
+pub fn map_codomain<P, F, C2>(p: P, function: F, new_codomain: C2) -> MappedPolifunction<P, F, C2>
+where
+    P: PolifunctionBase,
+    F: Fn(&<P::Codomain as Codomain>::Element) -> Result<C2::Element, PolifunctionError>,
+    C2: Codomain,
+{
+    MappedPolifunction::new(p, function, new_codomain)
+}
+
+/// Polifunction whose inputs are converted by a function before evaluation
+pub struct ContramappedPolifunction<P, G, D2>
+where
+    P: PolifunctionBase,
+    G: Fn(&D2::Element) -> <P::Domain as Domain>::Element,
+    D2: Domain,
+{
+    /// The original polifunction
+    inner: P,
+    /// Function converting new inputs into inputs of the original polifunction
+    function: G,
+    /// Domain of the new inputs
+    domain: D2,
+}
+
+impl<P, G, D2> ContramappedPolifunction<P, G, D2>
+where
+    P: PolifunctionBase,
+    G: Fn(&D2::Element) -> <P::Domain as Domain>::Element,
+    D2: Domain,
+{
+    /// Create a new polifunction evaluating `inner` at converted inputs
+    pub fn new(inner: P, function: G, domain: D2) -> Self {
+        Self {
+            inner,
+            function,
+            domain,
+        }
+    }
+}
+
+impl<P, G, D2> PolifunctionBase for ContramappedPolifunction<P, G, D2>
+where
+    P: PolifunctionBase,
+    G: Fn(&D2::Element) -> <P::Domain as Domain>::Element,
+    D2: Domain,
+{
+    type Domain = D2;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.domain.contains(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        // The inner polifunction checks the converted input against its own domain
+        self.inner.evaluate(&(self.function)(input))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.domain.contains(input) && self.inner.in_domain(&(self.function)(input))
+    }
+}
+
+/// Adapt a polifunction to a new input type by converting inputs with a function
+pub fn contramap_domain<P, G, D2>(p: P, function: G, new_domain: D2) -> ContramappedPolifunction<P, G, D2>
+where
+    P: PolifunctionBase,
+    G: Fn(&D2::Element) -> <P::Domain as Domain>::Element,
+    D2: Domain,
+{
+    ContramappedPolifunction::new(p, function, new_domain)
+}
+
+/// Polifunction whose output values are negated
+///
+/// `Set` values are negated according to the [`SetSupport`] `S`, [`NoSets`]
+/// unless switched to [`HashSets`] with [`NegatedPolifunction::with_sets`].
+pub struct NegatedPolifunction<P, S = NoSets>
+where
+    P: PolifunctionBase,
+{
+    inner: P,
+    sets: PhantomData<S>,
+}
+
+impl<P> NegatedPolifunction<P>
+where
+    P: PolifunctionBase,
+{
+    /// Create a new negated polifunction
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            sets: PhantomData,
+        }
+    }
+    
+    /// Negate Set values element by element
+    pub fn with_sets(self) -> NegatedPolifunction<P, HashSets>
+    where
+        <P::Codomain as Codomain>::Element: Hash + Eq,
+    {
+        NegatedPolifunction {
+            inner: self.inner,
+            sets: PhantomData,
+        }
+    }
+}
+
+impl<P, S> PolifunctionBase for NegatedPolifunction<P, S>
+where
+    P: PolifunctionBase,
+    S: SetSupport<<P::Codomain as Codomain>::Element>,
+    <P::Codomain as Codomain>::Element: std::ops::Neg<Output = <P::Codomain as Codomain>::Element>,
+{
+    type Domain = P::Domain;
+    type Codomain = P::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
+        }
+        
+        match self.inner.evaluate(input)? {
+            PolifunctionValue::Single(v) => Ok(PolifunctionValue::Single(-v)),
+            PolifunctionValue::Set(s) => {
+                Ok(PolifunctionValue::Set(S::collect_set(s.into_iter().map(|v| -v).collect())?))
+            },
+            PolifunctionValue::Interval(i) => Ok(PolifunctionValue::Interval(Interval {
+                lower: -i.upper,
+                upper: -i.lower,
+                lower_inclusive: i.upper_inclusive,
+                upper_inclusive: i.lower_inclusive,
+            })),
+            _ => Err(PolifunctionError::InvalidOperation),
+        }
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.inner.in_domain(input)
+    }
+}
+
+/// Negate every output value of a polifunction
+///
+/// Intervals are negated by swapping and negating their endpoints, so `[2, 5)`
+/// becomes `(-5, -2]`. Call [`NegatedPolifunction::with_sets`] on the result
+/// to negate set values of `Hash + Eq` elements element by element.
+pub fn negate<P>(p: P) -> NegatedPolifunction<P>
+where
+    P: PolifunctionBase,
+    <P::Codomain as Codomain>::Element: std::ops::Neg<Output = <P::Codomain as Codomain>::Element>,
+{
+    NegatedPolifunction::new(p)
+}
+
+/// Fix the first argument of a polifunction over a product domain
+///
+/// The result is a polifunction of the second argument alone.
+pub fn partial_apply<P, D1, D2>(p: P, first_arg: D1::Element) -> impl PolifunctionBase<Domain = D2, Codomain = P::Codomain>
+where
+    P: PolifunctionBase<Domain = ProductDomain<D1, D2>>,
+    D1: Domain,
+    D2: Domain,
+    D1::Element: Clone,
+    D2::Element: Clone,
+{
+    struct PartiallyAppliedPolifunction<P, D1, D2>
+    where
+        D1: Domain,
+    {
+        original: P,
+        first_arg: D1::Element,
+        _phantom: PhantomData<D2>,
+    }
+    
+    impl<P, D1, D2> PolifunctionBase for PartiallyAppliedPolifunction<P, D1, D2>
+    where
+        P: PolifunctionBase<Domain = ProductDomain<D1, D2>>,
+        D1: Domain,
+        D2: Domain,
+        D1::Element: Clone,
+        D2::Element: Clone,
+    {
+        type Domain = D2;
+        type Codomain = P::Codomain;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
             if !self.in_domain(input) {
                 return Err(PolifunctionError::DomainError);
             }
             
-            // Standard functions always return a single value
-            Ok(1)
+            self.original.evaluate(&(self.first_arg.clone(), input.clone()))
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.original.in_domain(&(self.first_arg.clone(), input.clone()))
         }
     }
     
-    LiftedSetValuedPolifunction {
-        function: f,
+    PartiallyAppliedPolifunction {
+        original: p,
+        first_arg,
+        _phantom: PhantomData,
+    }
+}
+
+/// Inputs of a polifunction paired with their evaluation results
+pub type Tabulation<P> = Vec<(
+    <<P as PolifunctionBase>::Domain as Domain>::Element,
+    Result<PolifunctionValue<<<P as PolifunctionBase>::Codomain as Codomain>::Element>, PolifunctionError>,
+)>;
+
+/// Evaluate a polifunction at every element of a finite domain
+///
+/// Returns each element with its evaluation result, in the domain's iteration
+/// order. With `skip_errors` set, elements whose evaluation fails are left out.
+pub fn tabulate<P>(p: &P, domain: &P::Domain, skip_errors: bool) -> Tabulation<P>
+where
+    P: PolifunctionBase,
+    P::Domain: FiniteDomain,
+{
+    domain.elements()
+        .map(|x| {
+            let value = p.evaluate(&x);
+            (x, value)
+        })
+        .filter(|(_, value)| !(skip_errors && value.is_err()))
+        .collect()
+}
+
+/// Evaluate a polifunction at every input of a slice in parallel
+///
+/// The slice is split into one chunk per rayon thread and each chunk is passed
+/// to `evaluate_batch` with the same `trust`, so the results match
+/// `evaluate_batch` element for element, also for polifunctions that override it.
+#[cfg(feature = "parallel")]
+pub fn par_evaluate_batch<P>(p: &P, inputs: &[<P::Domain as Domain>::Element], trust: TrustLevel)
+    -> Vec<Result<PolifunctionValue<<P::Codomain as Codomain>::Element>, PolifunctionError>>
+where
+    P: PolifunctionBase + Sync,
+    <P::Domain as Domain>::Element: Sync,
+    <P::Codomain as Codomain>::Element: Send,
+{
+    use rayon::prelude::*;
+    
+    let chunk_size = inputs.len().div_ceil(rayon::current_num_threads()).max(1);
+    inputs.par_chunks(chunk_size).flat_map_iter(|chunk| p.evaluate_batch(chunk, trust)).collect()
+}
+
+/// Converse of a relation: `y` relates to `x` iff `x` relates to `y`
+pub fn converse<K, V>(p: &TabularSetValuedPolifunction<K, V>) -> TabularSetValuedPolifunction<V, K>
+where
+    K: Clone + Hash + Eq,
+    V: Clone + Hash + Eq,
+{
+    p.pairs().map(|(k, v)| (v.clone(), k.clone())).collect()
+}
+
+/// Relational composition: `x` relates to `z` iff `x -> y` in `q` and `y -> z` in `p` for some `y`
+///
+/// Like `compose(p, q)`, `q` is applied first. Every input of `q` is an input
+/// of the result, even if none of its outputs is an input of `p`.
+pub fn relational_compose<X, Y, Z>(p: &TabularSetValuedPolifunction<Y, Z>, q: &TabularSetValuedPolifunction<X, Y>)
+    -> TabularSetValuedPolifunction<X, Z>
+where
+    X: Clone + Hash + Eq,
+    Y: Hash + Eq,
+    Z: Clone + Hash + Eq,
+{
+    let mut result = TabularSetValuedPolifunction::new();
+    
+    for x in q.inputs() {
+        result.insert_input(x.clone());
+        for y in q.get(x).into_iter().flatten() {
+            for z in p.get(y).into_iter().flatten() {
+                result.insert(x.clone(), z.clone());
+            }
+        }
+    }
+    
+    result
+}
+
+/// Transitive closure of a relation
+///
+/// Repeatedly adds the composition of the relation with itself until nothing
+/// changes. Fails with `ConvergenceError` if no fixed point is reached within
+/// `max_steps` compositions.
+pub fn transitive_closure<T>(p: &TabularSetValuedPolifunction<T, T>, max_steps: usize)
+    -> Result<TabularSetValuedPolifunction<T, T>, PolifunctionError>
+where
+    T: Clone + Hash + Eq,
+{
+    let mut closure = p.clone();
+    let mut size = closure.pairs().count();
+    
+    for _ in 0..max_steps {
+        // Squaring doubles the path length covered on every step
+        let squared = relational_compose(&closure, &closure);
+        for (x, z) in squared.pairs() {
+            closure.insert(x.clone(), z.clone());
+        }
+        
+        let new_size = closure.pairs().count();
+        if new_size == size {
+            return Ok(closure);
+        }
+        size = new_size;
+    }
+    
+    Err(PolifunctionError::ConvergenceError)
+}
+
+/// Create a constant polifunction that always returns the same value
+pub fn constant<D, C>(value: C::Element, domain: D, codomain: C) -> impl PolifunctionBase<Domain = D, Codomain = C>
+where
+    D: Domain,
+    C: Codomain,
+    C::Element: Clone,
+{
+    LiftedPolifunction::new(
+        move |_| Ok(value.clone()),
         domain,
         codomain,
-        _phantom: PhantomData,
+    )
+}
+
+/// Compose two polifunctions
+///
+/// The inner polifunction `p2` is evaluated first and a Single intermediate
+/// value is passed to `p1`. Errors from either polifunction are wrapped with
+/// the function they came from. This works for any output element, including
+/// f64; a non-empty Set intermediate value is an `InvalidOperation`. Use
+/// [`compose_sets`] to unite the results over a Set intermediate value.
+pub fn compose<P1, P2>(p1: P1, p2: P2) -> ComposedPolifunction<P1, P2>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase,
+    <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element>,
+{
+    ComposedPolifunction::new(p1, p2).skip_outside_domain()
+}
+
+/// Compose two polifunctions, mapping Set intermediate values element by element
+///
+/// Like [`compose`], but every element of a Set intermediate value is mapped
+/// through `p1` and the Single/Set results are united into a Set, which needs
+/// `Hash + Eq` output elements. Elements outside `p1`'s domain are skipped
+/// rather than aborting the whole evaluation, so the result may be an empty set.
+pub fn compose_sets<P1, P2>(p1: P1, p2: P2) -> ComposedPolifunction<P1, P2, HashSets>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase,
+    <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element>,
+    <P1::Codomain as Codomain>::Element: Hash + Eq,
+{
+    ComposedPolifunction::new(p1, p2).with_sets().skip_outside_domain()
+}
+
+/// How an Interval intermediate value is fed into the outer polifunction of a composition
+pub enum IntervalInput<M> {
+    /// Interval intermediate values are rejected
+    Reject,
+    /// The outer polifunction is monotone and interval-valued: take the hull of
+    /// its output intervals at the two endpoints of the intermediate interval
+    MonotoneEndpoints,
+    /// Evaluate the outer polifunction at explicitly chosen sample points
+    Discretize(Discretizer<M>),
+}
+
+/// Function choosing the sample points of an interval for [`IntervalInput::Discretize`]
+pub type Discretizer<M> = Box<dyn Fn(&Interval<M>) -> Vec<M>>;
+
+/// Configuration for [`compose_with_strategy`]
+pub struct CompositionStrategy<M> {
+    /// Handling of Interval intermediate values
+    pub interval_input: IntervalInput<M>,
+}
+
+impl<M> Default for CompositionStrategy<M> {
+    fn default() -> Self {
+        Self {
+            interval_input: IntervalInput::Reject,
+        }
+    }
+}
+
+/// Compose two polifunctions, handling mixed value kinds according to a strategy
+///
+/// The inner polifunction `p2` is evaluated first; its value is fed into `p1`:
+///
+/// | inner value | outer Single/Set values       | outer Interval values           |
+/// |-------------|-------------------------------|---------------------------------|
+/// | Single      | outer value as-is             | outer value as-is               |
+/// | Set         | union of per-element outputs  | hull of per-element intervals   |
+/// | Interval    | requires `Discretize`: union  | `MonotoneEndpoints` or `Discretize`: hull |
+///
+/// Interval intermediates are never sampled unless a discretization is given.
+/// Unsupported cells (including distributions, fuzzy sets, and outer values of
+/// mixed kinds, even if the Set values are empty) return an `InvalidOperation`
+/// with the cell as context.
+pub fn compose_with_strategy<P1, P2>(p1: P1, p2: P2, strategy: CompositionStrategy<<P2::Codomain as Codomain>::Element>)
+    -> impl PolifunctionBase<Domain = P2::Domain, Codomain = P1::Codomain>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase,
+    <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element> + Clone,
+    <P1::Codomain as Codomain>::Element: Clone + PartialOrd + std::hash::Hash + Eq,
+{
+    struct StrategicComposition<P1, P2>
+    where
+        P2: PolifunctionBase,
+    {
+        p1: P1,
+        p2: P2,
+        strategy: CompositionStrategy<<P2::Codomain as Codomain>::Element>,
+    }
+    
+    impl<P1, P2> StrategicComposition<P1, P2>
+    where
+        P1: PolifunctionBase,
+        P2: PolifunctionBase,
+        <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element> + Clone,
+        <P1::Codomain as Codomain>::Element: Clone + PartialOrd + std::hash::Hash + Eq,
+    {
+        /// Evaluate p1 at every intermediate value and merge the outputs
+        fn evaluate_all<I>(&self, values: I, inner_kind: &str)
+            -> Result<PolifunctionValue<<P1::Codomain as Codomain>::Element>, PolifunctionError>
+        where
+            I: IntoIterator<Item = <P2::Codomain as Codomain>::Element>,
+        {
+            let mut set = HashSet::new();
+            // Tracked separately from `set`, since an empty Set still fixes the kind
+            let mut seen_set = false;
+            let mut hull: Option<Interval<<P1::Codomain as Codomain>::Element>> = None;
+            
+            for v in values {
+                match self.p1.evaluate(&v.into())? {
+                    PolifunctionValue::Single(r) if hull.is_none() => {
+                        seen_set = true;
+                        set.insert(r);
+                    },
+                    PolifunctionValue::Set(rs) if hull.is_none() => {
+                        seen_set = true;
+                        set.extend(rs);
+                    },
+                    PolifunctionValue::Interval(i) if !seen_set => {
+                        hull = Some(match hull {
+                            Some(h) => h.hull(&i).ok_or(PolifunctionError::ComputationError)?,
+                            None => i,
+                        });
+                    },
+                    PolifunctionValue::Single(_) | PolifunctionValue::Set(_) | PolifunctionValue::Interval(_) => {
+                        return Err(unsupported_composition(inner_kind, "mixed Single/Set and Interval"));
+                    },
+                    PolifunctionValue::Distribution(_) => return Err(unsupported_composition(inner_kind, "Distribution")),
+                    PolifunctionValue::FuzzySet(_) => return Err(unsupported_composition(inner_kind, "FuzzySet")),
+                    PolifunctionValue::MultiInterval(_) => return Err(unsupported_composition(inner_kind, "MultiInterval")),
+                }
+            }
+            
+            Ok(match hull {
+                Some(h) => PolifunctionValue::Interval(h),
+                None => PolifunctionValue::Set(set),
+            })
+        }
+    }
+    
+    impl<P1, P2> PolifunctionBase for StrategicComposition<P1, P2>
+    where
+        P1: PolifunctionBase,
+        P2: PolifunctionBase,
+        <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element> + Clone,
+        <P1::Codomain as Codomain>::Element: Clone + PartialOrd + std::hash::Hash + Eq,
+    {
+        type Domain = P2::Domain;
+        type Codomain = P1::Codomain;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            match self.p2.evaluate(input)? {
+                PolifunctionValue::Single(v) => self.p1.evaluate(&v.into()),
+                PolifunctionValue::Set(set) => self.evaluate_all(set, "Set"),
+                PolifunctionValue::Interval(interval) => match &self.strategy.interval_input {
+                    IntervalInput::Reject => {
+                        Err(unsupported_composition("Interval", "any (no interval input strategy)"))
+                    },
+                    IntervalInput::MonotoneEndpoints => {
+                        let lower = self.p1.evaluate(&interval.lower.clone().into())?;
+                        let upper = self.p1.evaluate(&interval.upper.into())?;
+                        match (lower, upper) {
+                            (PolifunctionValue::Interval(i1), PolifunctionValue::Interval(i2)) => {
+                                Ok(PolifunctionValue::Interval(i1.hull(&i2).ok_or(PolifunctionError::ComputationError)?))
+                            },
+                            _ => Err(unsupported_composition("Interval", "non-Interval (use Discretize)")),
+                        }
+                    },
+                    IntervalInput::Discretize(discretize) => self.evaluate_all(discretize(&interval), "Interval"),
+                },
+                PolifunctionValue::Distribution(_) => Err(unsupported_composition("Distribution", "any")),
+                PolifunctionValue::FuzzySet(_) => Err(unsupported_composition("FuzzySet", "any")),
+                PolifunctionValue::MultiInterval(_) => Err(unsupported_composition("MultiInterval", "any")),
+            }
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.p2.in_domain(input)
+        }
+    }
+    
+    StrategicComposition { p1, p2, strategy }
+}
+
+/// Error for a cell of the composition matrix that has no defined semantics
+///
+/// An `InvalidOperation` with the cell as context.
+fn unsupported_composition(inner_kind: &str, outer_kind: &str) -> PolifunctionError {
+    PolifunctionError::InvalidOperation.context(format!(
+        "{} intermediate value with {} outer values",
+        inner_kind, outer_kind
+    ))
+}
+
+/// Convert a set-valued polifunction to an interval-valued one by taking the extrema
+///
+/// `evaluate` reports a degenerate interval, from a one-element set, as `Single`.
+pub fn to_interval<P>(p: P) -> impl IntervalValuedPolifunction<Domain = P::Domain, Codomain = P::Codomain>
+where
+    P: SetValuedPolifunction,
+    <P::Codomain as Codomain>::Element: Clone + Ord,
+{
+    struct SetToIntervalPolifunction<P> {
+        original: P,
+    }
+    
+    impl<P> PolifunctionBase for SetToIntervalPolifunction<P>
+    where
+        P: SetValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + Ord,
+    {
+        type Domain = P::Domain;
+        type Codomain = P::Codomain;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            let interval = self.value_interval(input)?;
+            // A one-element set gives a degenerate interval, reported as Single
+            Ok(PolifunctionValue::Interval(interval).normalize())
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.original.in_domain(input)
+        }
+    }
+    
+    impl<P> IntervalValuedPolifunction for SetToIntervalPolifunction<P>
+    where
+        P: SetValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + Ord,
+    {
+        fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            let set = self.original.value_set(input)?;
+            if set.is_empty() {
+                return Err(PolifunctionError::ComputationError);
+            }
+            
+            let min = set.iter().min().unwrap().clone();
+            let max = set.iter().max().unwrap().clone();
+            
+            Interval::new(min, max, true, true)
+        }
+        
+        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                         value: &<Self::Codomain as Codomain>::Element)
+            -> Result<bool, PolifunctionError> {
+            let interval = self.value_interval(input)?;
+            
+            Ok(interval.contains(value))
+        }
+        
+        fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+        where
+            <Self::Codomain as Codomain>::Element: std::ops::Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+        {
+            let interval = self.value_interval(input)?;
+            Ok(interval.width())
+        }
+    }
+    
+    SetToIntervalPolifunction { original: p }
+}
+
+/// Convert a fuzzy-valued polifunction to a set-valued one by taking the alpha-cut
+///
+/// At each input the result is the crisp set of elements whose membership
+/// degree is at least `alpha`. Returns an error if `alpha` is outside [0, 1].
+pub fn fuzzy_to_set<P>(p: P, alpha: f64)
+    -> Result<impl SetValuedPolifunction<Domain = P::Domain, Codomain = P::Codomain>, PolifunctionError>
+where
+    P: FuzzyValuedPolifunction,
+    <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    struct AlphaCutPolifunction<P> {
+        original: P,
+        alpha: f64,
+    }
+    
+    impl<P> PolifunctionBase for AlphaCutPolifunction<P>
+    where
+        P: FuzzyValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+    {
+        type Domain = P::Domain;
+        type Codomain = P::Codomain;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            let set = self.value_set(input)?;
+            Ok(PolifunctionValue::Set(set))
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.original.in_domain(input)
+        }
+    }
+    
+    impl<P> SetValuedPolifunction for AlphaCutPolifunction<P>
+    where
+        P: FuzzyValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + Hash + Eq,
+    {
+        fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            let fuzzy_set = self.original.value_fuzzy_set(input)?;
+            Ok(fuzzy_set.alpha_cut(self.alpha))
+        }
+        
+        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                         value: &<Self::Codomain as Codomain>::Element)
+            -> Result<bool, PolifunctionError> {
+            let fuzzy_set = self.original.value_fuzzy_set(input)?;
+            Ok(fuzzy_set.pairs().iter().any(|(v, d)| v == value && *d >= self.alpha))
+        }
+        
+        fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<usize, PolifunctionError> {
+            Ok(self.value_set(input)?.len())
+        }
+    }
+    
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(PolifunctionError::Other(format!("Alpha level {} is outside [0, 1]", alpha)));
+    }
+    
+    Ok(AlphaCutPolifunction { original: p, alpha })
+}
+
+/// Convert a distribution-valued polifunction to an interval-valued one by taking a confidence interval
+///
+/// At each input the result is the narrowest closed interval `[a, b]` whose
+/// support points carry at least `coverage` of the probability mass. Among
+/// equally narrow intervals the leftmost one is chosen. Values that cannot be
+/// ordered (such as NaN) produce a `ComputationError`. Returns an error if
+/// `coverage` is outside (0, 1].
+pub fn distribution_to_interval<P>(p: P, coverage: f64)
+    -> Result<impl IntervalValuedPolifunction<Domain = P::Domain, Codomain = P::Codomain>, PolifunctionError>
+where
+    P: DistributionValuedPolifunction,
+    <P::Codomain as Codomain>::Element: Clone + PartialOrd + std::ops::Sub<Output = <P::Codomain as Codomain>::Element>,
+{
+    struct ConfidenceIntervalPolifunction<P> {
+        original: P,
+        coverage: f64,
+    }
+    
+    impl<P> PolifunctionBase for ConfidenceIntervalPolifunction<P>
+    where
+        P: DistributionValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + PartialOrd + std::ops::Sub<Output = <P::Codomain as Codomain>::Element>,
+    {
+        type Domain = P::Domain;
+        type Codomain = P::Codomain;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            let interval = self.value_interval(input)?;
+            Ok(PolifunctionValue::Interval(interval))
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.original.in_domain(input)
+        }
+    }
+    
+    impl<P> IntervalValuedPolifunction for ConfidenceIntervalPolifunction<P>
+    where
+        P: DistributionValuedPolifunction,
+        <P::Codomain as Codomain>::Element: Clone + PartialOrd + std::ops::Sub<Output = <P::Codomain as Codomain>::Element>,
+    {
+        fn value_interval(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<Interval<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            let distribution = self.original.value_distribution(input)?;
+            let mut pairs = distribution.pairs().to_vec();
+            if pairs.iter().any(|(v, _)| v.partial_cmp(v).is_none()) {
+                return Err(PolifunctionError::ComputationError);
+            }
+            pairs.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            
+            // Slide a window over the sorted support, shrinking it from the
+            // left whenever it still covers enough mass without its first point
+            let target = self.coverage - PROBABILITY_EPSILON;
+            let mut best: Option<(usize, usize)> = None;
+            let mut start = 0;
+            let mut mass = 0.0;
+            for end in 0..pairs.len() {
+                mass += pairs[end].1;
+                while start < end && mass - pairs[start].1 >= target {
+                    mass -= pairs[start].1;
+                    start += 1;
+                }
+                if mass >= target {
+                    let narrower = match best {
+                        None => true,
+                        Some((s, e)) => {
+                            pairs[end].0.clone() - pairs[start].0.clone() < pairs[e].0.clone() - pairs[s].0.clone()
+                        }
+                    };
+                    if narrower {
+                        best = Some((start, end));
+                    }
+                }
+            }
+            
+            let (start, end) = best.ok_or(PolifunctionError::EmptyResult)?;
+            Interval::new(pairs[start].0.clone(), pairs[end].0.clone(), true, true)
+        }
+        
+        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                         value: &<Self::Codomain as Codomain>::Element)
+            -> Result<bool, PolifunctionError> {
+            let interval = self.value_interval(input)?;
+            
+            Ok(interval.contains(value))
+        }
+        
+        fn interval_width(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+        where
+            <Self::Codomain as Codomain>::Element: std::ops::Sub<Output = <Self::Codomain as Codomain>::Element> + Clone,
+        {
+            let interval = self.value_interval(input)?;
+            Ok(interval.width())
+        }
+    }
+    
+    if !(coverage > 0.0 && coverage <= 1.0) {
+        return Err(PolifunctionError::Other(format!("Coverage {} is outside (0, 1]", coverage)));
+    }
+    
+    Ok(ConfidenceIntervalPolifunction { original: p, coverage })
+}
+
+/// Convert a standard function to a set-valued polifunction
+///
+/// `value_set` returns one-element sets, while `evaluate` reports the value as
+/// `Single` so it can feed `Single`-only code paths such as `compose`.
+pub fn lift_to_set<F, D, C>(f: F, domain: D, codomain: C) -> impl SetValuedPolifunction<Domain = D, Codomain = C>
+where
+    F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
+    D: Domain,
+    C: Codomain,
+    C::Element: Clone + std::hash::Hash + Eq,
+{
+    struct LiftedSetValuedPolifunction<F, D, C>
+    where
+        F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
+        D: Domain,
+        C: Codomain,
+    {
+        lifted: LiftedPolifunction<F, D, C>,
+    }
+    
+    impl<F, D, C> PolifunctionBase for LiftedSetValuedPolifunction<F, D, C>
+    where
+        F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
+        D: Domain,
+        C: Codomain,
+        C::Element: Clone + std::hash::Hash + Eq,
+    {
+        type Domain = D;
+        type Codomain = C;
+        
+        fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            // The set always has one element, so evaluate reports it as Single
+            self.lifted.evaluate(input)
+        }
+        
+        fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+            self.lifted.in_domain(input)
+        }
+    }
+    
+    impl<F, D, C> SetValuedPolifunction for LiftedSetValuedPolifunction<F, D, C>
+    where
+        F: Fn(&D::Element) -> Result<C::Element, PolifunctionError>,
+        D: Domain,
+        C: Codomain,
+        C::Element: Clone + std::hash::Hash + Eq,
+    {
+        fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            let value = (self.lifted.function)(input)?;
+            let mut set = HashSet::new();
+            set.insert(value);
+            
+            Ok(set)
+        }
+        
+        fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                         value: &<Self::Codomain as Codomain>::Element)
+            -> Result<bool, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            let result = (self.lifted.function)(input)?;
+            Ok(&result == value)
+        }
+        
+        fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+            -> Result<usize, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            
+            // Standard functions always return a single value
+            Ok(1)
+        }
+    }
+    
+    LiftedSetValuedPolifunction {
+        lifted: LiftedPolifunction::new(f, domain, codomain),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, PredicateDomain};
+    use crate::core::interfaces::polifunction::TrustLevel;
+    use crate::core::interfaces::set_valued::{BasicSetValuedPolifunction, UnionPolifunction};
+    use crate::core::interfaces::interval_valued::BasicIntervalValuedPolifunction;
+    use crate::core::interfaces::fuzzy_valued::BasicFuzzyValuedPolifunction;
+    use crate::core::interfaces::distribution_valued::BasicDistributionValuedPolifunction;
+    
+    type Ints = Unbounded<i64>;
+    
+    fn small_range() -> IntegerRange {
+        IntegerRange::new(-5, 5).unwrap()
+    }
+    
+    fn lift_int(f: fn(i64) -> i64) -> LiftedPolifunction<impl Fn(&i64) -> Result<i64, PolifunctionError>, IntegerRange, Ints> {
+        LiftedPolifunction::new(move |x: &i64| Ok(f(*x)), small_range(), Ints::new())
+    }
+    
+    fn lift_real(f: fn(f64) -> f64) -> LiftedPolifunction<impl Fn(&f64) -> Result<f64, PolifunctionError>, Unbounded<f64>, Unbounded<f64>> {
+        LiftedPolifunction::new(move |x: &f64| Ok(f(*x)), Unbounded::new(), Unbounded::new())
+    }
+    
+    fn int_set(f: fn(i64) -> Vec<i64>) -> BasicSetValuedPolifunction<IntegerRange, Ints> {
+        BasicSetValuedPolifunction::new(move |x: &i64| Ok(f(*x).into_iter().collect()), small_range(), Ints::new())
+    }
+    
+    fn int_interval(f: fn(i64) -> (i64, i64)) -> BasicIntervalValuedPolifunction<IntegerRange, Ints> {
+        BasicIntervalValuedPolifunction::new(
+            move |x: &i64| {
+                let (lower, upper) = f(*x);
+                Interval::new(lower, upper, true, true)
+            },
+            small_range(),
+            Ints::new(),
+        )
+    }
+    
+    fn set_of(values: &[i64]) -> PolifunctionValue<i64> {
+        PolifunctionValue::Set(values.iter().copied().collect())
+    }
+    
+    fn closed_int(lower: i64, upper: i64) -> PolifunctionValue<i64> {
+        PolifunctionValue::Interval(Interval::new(lower, upper, true, true).unwrap())
+    }
+    
+    /// Polifunction on the small range returning arbitrary values, for value kinds no basic polifunction mixes
+    struct ValueFn<F>(F);
+    
+    impl<F: Fn(i64) -> PolifunctionValue<i64>> PolifunctionBase for ValueFn<F> {
+        type Domain = IntegerRange;
+        type Codomain = Ints;
+        
+        fn evaluate(&self, input: &i64) -> Result<PolifunctionValue<i64>, PolifunctionError> {
+            if !self.in_domain(input) {
+                return Err(PolifunctionError::DomainError);
+            }
+            Ok((self.0)(*input))
+        }
+        
+        fn in_domain(&self, input: &i64) -> bool {
+            Domain::contains(&small_range(), input)
+        }
+    }
+    
+    /// Assert that an error is an `InvalidOperation` reported for the given composition cell
+    fn assert_unsupported_cell(error: PolifunctionError, cell: &str) {
+        assert_eq!(error.to_string(), format!("{}: {}", cell, PolifunctionError::InvalidOperation));
+    }
+    
+    #[test]
+    fn sum_of_singles_and_intervals() {
+        let sum = SumPolifunction::new(lift_int(|x| x * x), lift_int(|x| x + 1));
+        assert_eq!(sum.evaluate(&3), Ok(PolifunctionValue::Single(13)));
+        assert_eq!(sum.evaluate(&7), Err(PolifunctionError::DomainError));
+        
+        let sum = SumPolifunction::new(int_interval(|x| (x, x + 2)), int_interval(|x| (-x, 1)));
+        assert_eq!(sum.evaluate(&2), Ok(closed_int(0, 5)));
+        
+        let sum = SumPolifunction::new(lift_int(|x| x), int_interval(|x| (x, x + 2)));
+        assert_eq!(sum.evaluate(&2), Ok(closed_int(4, 6)));
+        
+        let sum = SumPolifunction::new(int_interval(|x| (x, x + 2)), lift_int(|x| x));
+        assert_eq!(sum.evaluate(&2), Ok(closed_int(4, 6)));
+    }
+    
+    #[test]
+    fn sum_and_compose_of_borrowed_and_shared_polifunctions() {
+        let square = lift_int(|x| x * x);
+        let spread = int_set(|x| vec![x, -x]);
+        
+        let borrowed = SumPolifunction::new(&square, &square);
+        assert_eq!(borrowed.evaluate(&3), Ok(PolifunctionValue::Single(18)));
+        let mixed = SumPolifunction::new(&square, &spread).with_sets();
+        assert_eq!(mixed.evaluate(&2), Ok(set_of(&[6, 2])));
+        
+        let shared = std::sync::Arc::new(square);
+        let sum = SumPolifunction::new(std::sync::Arc::clone(&shared), std::sync::Arc::clone(&shared));
+        assert_eq!(sum.evaluate(&-2), Ok(PolifunctionValue::Single(8)));
+        
+        let composed = compose_sets(std::sync::Arc::clone(&shared), &spread);
+        assert_eq!(composed.evaluate(&2), Ok(set_of(&[4])));
+        assert_eq!(std::sync::Arc::strong_count(&shared), 4);
+    }
+    
+    #[test]
+    fn sum_of_sets_with_sets() {
+        let sum = SumPolifunction::new(int_set(|x| vec![x, -x]), int_set(|_| vec![0, 10])).with_sets();
+        assert_eq!(sum.evaluate(&2), Ok(set_of(&[2, -2, 12, 8])));
+        
+        let sum = SumPolifunction::new(lift_int(|x| x), int_set(|x| vec![x, -x])).with_sets();
+        assert_eq!(sum.evaluate(&3), Ok(set_of(&[6, 0])));
+        
+        let sum = SumPolifunction::new(int_set(|x| vec![x, -x]), lift_int(|x| x)).with_sets();
+        assert_eq!(sum.evaluate(&3), Ok(set_of(&[6, 0])));
+        
+        // Combining with an empty set leaves nothing to combine
+        let sum = SumPolifunction::new(int_set(|_| vec![]), int_set(|x| vec![x])).with_sets();
+        assert_eq!(sum.evaluate(&1), Ok(set_of(&[])));
+        let sum = SumPolifunction::new(lift_int(|x| x), int_set(|_| vec![])).with_sets();
+        assert_eq!(sum.evaluate(&1), Ok(set_of(&[])));
+        
+        // Sets and intervals do not mix
+        let sum = SumPolifunction::new(int_set(|x| vec![x]), int_interval(|x| (x, x))).with_sets();
+        assert_eq!(sum.evaluate(&1), Err(PolifunctionError::InvalidOperation));
+    }
+
+    #[test]
+    fn set_support_is_part_of_the_type() {
+        let hashed: BinaryOpPolifunction<_, _, AddAlgebra, HashSets> =
+            SumPolifunction::new(int_set(|x| vec![x, -x]), lift_int(|x| x)).with_sets();
+        assert_eq!(hashed.evaluate(&1), Ok(set_of(&[2, 0])));
+
+        // Without set support only the empty set can be built
+        let unhashed: BinaryOpPolifunction<_, _, AddAlgebra, NoSets> = SumPolifunction::new(int_set(|_| vec![]), lift_int(|x| x));
+        assert_eq!(unhashed.evaluate(&1), Ok(set_of(&[])));
+        let unhashed = SumPolifunction::new(int_set(|x| vec![x]), lift_int(|x| x));
+        assert_eq!(unhashed.evaluate(&1), Err(PolifunctionError::InvalidOperation));
+    }
+
+    #[test]
+    fn every_alias_combines_sets_with_sets() {
+        let (left, right) = (|| int_set(|x| vec![x, x + 1]), || int_set(|_| vec![2, 3]));
+        assert_eq!(SumPolifunction::new(left(), right()).with_sets().evaluate(&1), Ok(set_of(&[3, 4, 5])));
+        assert_eq!(DiffPolifunction::new(left(), right()).with_sets().evaluate(&1), Ok(set_of(&[-2, -1, 0])));
+        assert_eq!(ProductPolifunction::new(left(), right()).with_sets().evaluate(&1), Ok(set_of(&[2, 3, 4, 6])));
+        assert_eq!(MinPolifunction::new(left(), right()).with_sets().evaluate(&1), Ok(set_of(&[1, 2])));
+        assert_eq!(MaxPolifunction::new(left(), right()).with_sets().evaluate(&1), Ok(set_of(&[2, 3])));
+        
+        let quotient = QuotientPolifunction::with_algebra(int_set(|x| vec![6 * x, 12]), right(), DivAlgebra::new(0)).with_sets();
+        assert_eq!(quotient.evaluate(&1), Ok(set_of(&[3, 2, 6, 4])));
+        let by_zero = QuotientPolifunction::with_algebra(left(), int_set(|_| vec![1, 0]), DivAlgebra::new(0)).with_sets();
+        assert_eq!(by_zero.evaluate(&1), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn sum_of_real_polifunctions() {
+        let sum = SumPolifunction::new(lift_real(|x| x * 0.5), lift_real(f64::sin));
+        assert_eq!(sum.evaluate(&0.0).unwrap().as_single(), Some(&0.0));
+        assert_eq!(sum.evaluate(&3.0).unwrap().as_single(), Some(&(1.5 + 3.0f64.sin())));
+    }
+    
+    #[test]
+    fn product_of_real_polifunctions() {
+        let product = ProductPolifunction::new(lift_real(|x| x + 1.0), lift_real(|x| x * 0.5));
+        assert_eq!(product.evaluate(&3.0).unwrap().as_single(), Some(&6.0));
+        assert_eq!(product.evaluate(&-2.0).unwrap().as_single(), Some(&1.0));
+    }
+    
+    /// Small deterministic pseudo-random generator (xorshift64) for property tests
+    struct XorShift(u64);
+    
+    impl XorShift {
+        fn next_in(&mut self, lower: i64, upper: i64) -> i64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            lower + (self.0 % (upper - lower + 1) as u64) as i64
+        }
+    }
+    
+    /// Random affine operand, viewed as a single-, interval- or set-valued function
+    #[derive(Clone, Copy)]
+    struct Affine {
+        a: i64,
+        b: i64,
+        width: i64,
+    }
+    
+    impl Affine {
+        fn random(rng: &mut XorShift) -> Self {
+            Self { a: rng.next_in(-4, 4), b: rng.next_in(-10, 10), width: rng.next_in(0, 3) }
+        }
+        
+        fn single(self, x: i64) -> i64 {
+            self.a * x + self.b
+        }
+        
+        fn interval(self, x: i64) -> (i64, i64) {
+            (self.single(x), self.single(x) + self.width)
+        }
+        
+        fn set(self, x: i64) -> Vec<i64> {
+            (0..=self.width).map(|k| self.a * x + self.b * k).collect()
+        }
+    }
+    
+    fn single_fn(f: impl Fn(i64) -> i64) -> ValueFn<impl Fn(i64) -> PolifunctionValue<i64>> {
+        ValueFn(move |x| PolifunctionValue::Single(f(x)))
+    }
+    
+    fn interval_fn(f: impl Fn(i64) -> (i64, i64)) -> ValueFn<impl Fn(i64) -> PolifunctionValue<i64>> {
+        ValueFn(move |x| {
+            let (lower, upper) = f(x);
+            closed_int(lower, upper)
+        })
+    }
+    
+    fn set_fn(f: impl Fn(i64) -> Vec<i64>) -> ValueFn<impl Fn(i64) -> PolifunctionValue<i64>> {
+        ValueFn(move |x| PolifunctionValue::Set(f(x).into_iter().collect()))
+    }
+    
+    #[test]
+    fn aliases_match_reference_semantics_on_random_inputs() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..200 {
+            let (p, q) = (Affine::random(&mut rng), Affine::random(&mut rng));
+            let (f, fi, g, gi) = (move |x| p.single(x), move |x| p.interval(x), move |x| q.single(x), move |x| q.interval(x));
+            let x = rng.next_in(-5, 4);
+            
+            let (u, v) = (f(x), g(x));
+            let single = PolifunctionValue::Single;
+            assert_eq!(SumPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x), Ok(single(u + v)));
+            assert_eq!(DiffPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x), Ok(single(u - v)));
+            assert_eq!(ProductPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x), Ok(single(u * v)));
+            assert_eq!(MinPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x), Ok(single(u.min(v))));
+            assert_eq!(MaxPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x), Ok(single(u.max(v))));
+            
+            let ((a, b), (c, d)) = (fi(x), gi(x));
+            let corners = [a * c, a * d, b * c, b * d];
+            let (lowest, highest) = (*corners.iter().min().unwrap(), *corners.iter().max().unwrap());
+            assert_eq!(SumPolifunction::new(interval_fn(fi), interval_fn(gi)).evaluate(&x), Ok(closed_int(a + c, b + d)));
+            assert_eq!(DiffPolifunction::new(interval_fn(fi), interval_fn(gi)).evaluate(&x), Ok(closed_int(a - d, b - c)));
+            assert_eq!(ProductPolifunction::new(interval_fn(fi), interval_fn(gi)).evaluate(&x), Ok(closed_int(lowest, highest)));
+            assert_eq!(MinPolifunction::new(interval_fn(fi), interval_fn(gi)).evaluate(&x), Ok(closed_int(a.min(c), b.min(d))));
+            assert_eq!(MaxPolifunction::new(interval_fn(fi), interval_fn(gi)).evaluate(&x), Ok(closed_int(a.max(c), b.max(d))));
+        }
+    }
+    
+    #[test]
+    fn with_sets_matches_reference_minkowski_sum_on_random_inputs() {
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+        for _ in 0..200 {
+            let (p, q) = (Affine::random(&mut rng), Affine::random(&mut rng));
+            let (f, fi, fs) = (move |x| p.single(x), move |x| p.interval(x), move |x| p.set(x));
+            let (g, gs) = (move |x| q.single(x), move |x| q.set(x));
+            let x = rng.next_in(-5, 4);
+            
+            let expected: HashSet<i64> = fs(x).into_iter().flat_map(|a| gs(x).into_iter().map(move |b| a + b)).collect();
+            assert_eq!(SumPolifunction::new(set_fn(fs), set_fn(gs)).with_sets().evaluate(&x), Ok(PolifunctionValue::Set(expected)));
+            
+            // Singles and intervals combine exactly as without with_sets
+            assert_eq!(
+                SumPolifunction::new(single_fn(f), single_fn(g)).with_sets().evaluate(&x),
+                SumPolifunction::new(single_fn(f), single_fn(g)).evaluate(&x),
+            );
+            assert_eq!(
+                SumPolifunction::new(single_fn(g), interval_fn(fi)).with_sets().evaluate(&x),
+                SumPolifunction::new(single_fn(g), interval_fn(fi)).evaluate(&x),
+            );
+        }
+    }
+    
+    #[test]
+    fn product_over_small_integer_domains() {
+        let product = ProductPolifunction::new(lift_int(|x| x - 1), lift_int(|x| x + 1));
+        for x in -5..5 {
+            assert_eq!(product.evaluate(&x), Ok(PolifunctionValue::Single(x * x - 1)));
+        }
+        
+        let product = ProductPolifunction::new(int_set(|x| vec![x, -x]), int_set(|_| vec![2, 3])).with_sets();
+        assert_eq!(product.evaluate(&2), Ok(set_of(&[4, 6, -4, -6])));
+        assert_eq!(product.evaluate(&0), Ok(set_of(&[0])));
+    }
+    
+    #[test]
+    fn scale_over_small_integer_domain() {
+        let scaled = scale(lift_int(|x| x + 1), 3);
+        for x in -5..5 {
+            assert_eq!(scaled.evaluate(&x), Ok(PolifunctionValue::Single(3 * (x + 1))));
+        }
+        assert_eq!(scaled.evaluate(&5), Err(PolifunctionError::DomainError));
+        
+        assert_eq!(scale(int_interval(|x| (x, x + 2)), -1).evaluate(&1), Ok(closed_int(-3, -1)));
+        
+        // Sets need with_sets
+        assert_eq!(scale(int_set(|x| vec![x, -x]), 2).evaluate(&1), Err(PolifunctionError::InvalidOperation));
+        let scaled = scale(int_set(|x| vec![x, -x]), 2).with_sets();
+        assert_eq!(scaled.evaluate(&1), Ok(set_of(&[2, -2])));
+        assert_eq!(scaled.evaluate(&0), Ok(set_of(&[0])));
+        assert_eq!(scale(int_set(|_| vec![]), 2).with_sets().evaluate(&1), Ok(set_of(&[])));
+        
+        // Other value kinds are scaled as without with_sets
+        assert_eq!(scale(int_interval(|x| (x, x + 2)), -1).with_sets().evaluate(&1), Ok(closed_int(-3, -1)));
+        assert_eq!(scale(lift_int(|x| x + 1), 3).with_sets().evaluate(&2), Ok(PolifunctionValue::Single(9)));
+    }
+    
+    #[test]
+    fn scale_real_polifunction() {
+        let scaled = scale(lift_real(|x| x + 0.5), -2.0);
+        assert_eq!(scaled.evaluate(&1.0).unwrap().as_single(), Some(&-3.0));
+    }
+    
+    fn real_interval(lower: f64, upper: f64, lower_inclusive: bool, upper_inclusive: bool)
+        -> BasicIntervalValuedPolifunction<Unbounded<f64>, Unbounded<f64>> {
+        BasicIntervalValuedPolifunction::new(
+            move |x: &f64| Interval::new(lower + x, upper + x, lower_inclusive, upper_inclusive),
+            Unbounded::new(),
+            Unbounded::new(),
+        )
+    }
+    
+    #[test]
+    fn scale_keeps_interval_endpoints_ordered() {
+        let scaled = scale(real_interval(1.0, 2.0, true, false), 2.5).evaluate(&0.0).unwrap();
+        let interval = scaled.as_interval().unwrap();
+        assert_eq!((interval.lower, interval.upper), (2.5, 5.0));
+        assert_eq!((interval.lower_inclusive, interval.upper_inclusive), (true, false));
+        
+        // A negative factor swaps the endpoints together with their inclusivity
+        let scaled = scale(real_interval(1.0, 2.0, true, false), -2.0).evaluate(&0.0).unwrap();
+        let interval = scaled.as_interval().unwrap();
+        assert_eq!((interval.lower, interval.upper), (-4.0, -2.0));
+        assert_eq!((interval.lower_inclusive, interval.upper_inclusive), (false, true));
+        
+        let scaled = scale(real_interval(-3.0, 1.0, true, true), -0.5).evaluate(&0.0).unwrap();
+        let interval = scaled.as_interval().unwrap();
+        assert!(interval.lower <= interval.upper);
+        assert_eq!((interval.lower, interval.upper), (-0.5, 1.5));
+    }
+    
+    #[test]
+    fn restrict_rejects_inputs_outside_the_new_domain() {
+        let restricted = restrict(lift_int(|x| x * 10), IntegerRange::new(0, 3).unwrap());
+        assert_eq!(restricted.evaluate(&2), Ok(PolifunctionValue::Single(20)));
+        assert_eq!(restricted.evaluate(&-1), Err(PolifunctionError::DomainError));
+        assert!(!restricted.in_domain(&3));
+        
+        // The restriction cannot extend the original domain
+        let restricted = restrict(lift_int(|x| x), IntegerRange::new(0, 100).unwrap());
+        assert!(restricted.in_domain(&4));
+        assert!(!restricted.in_domain(&5));
+    }
+    
+    #[test]
+    fn restrict_real_polifunction() {
+        let restricted = restrict(lift_real(f64::sqrt), RealInterval::closed(0.0, 4.0));
+        assert_eq!(restricted.evaluate(&4.0).unwrap().as_single(), Some(&2.0));
+        assert_eq!(restricted.evaluate(&-4.0).unwrap_err(), PolifunctionError::DomainError);
+    }
+    
+    #[test]
+    fn restrict_keeps_set_valued_behaviour() {
+        let restricted = restrict(int_set(|x| vec![x, -x]), IntegerRange::new(0, 3).unwrap());
+        assert_eq!(restricted.value_set(&2), Ok([2, -2].into_iter().collect()));
+        assert_eq!(SetValuedPolifunction::contains_value(&restricted, &2, &-2), Ok(true));
+        assert_eq!(SetValuedPolifunction::cardinality(&restricted, &0), Ok(1));
+        assert_eq!(restricted.value_set(&-2), Err(PolifunctionError::DomainError));
+        assert_eq!(SetValuedPolifunction::cardinality(&restricted, &4), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn restrict_keeps_interval_valued_behaviour() {
+        let restricted = restrict(int_interval(|x| (x, x + 2)), IntegerRange::new(0, 3).unwrap());
+        assert_eq!(restricted.value_interval(&1), Interval::new(1, 3, true, true));
+        assert_eq!(IntervalValuedPolifunction::contains_value(&restricted, &1, &2), Ok(true));
+        assert_eq!(restricted.interval_width(&2), Ok(2));
+        assert_eq!(restricted.value_interval(&3), Err(PolifunctionError::DomainError));
+        assert_eq!(IntervalValuedPolifunction::contains_value(&restricted, &-1, &0), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn negate_real_values() {
+        let negated = negate(lift_real(|x| x * 1.5));
+        assert_eq!(negated.evaluate(&2.0).unwrap().as_single(), Some(&-3.0));
+    }
+    
+    #[test]
+    fn negate_real_interval_swaps_inclusivity() {
+        for (lower_inclusive, upper_inclusive) in [(true, true), (true, false), (false, true), (false, false)] {
+            let negated = negate(real_interval(2.0, 5.0, lower_inclusive, upper_inclusive)).evaluate(&0.0).unwrap();
+            assert_eq!(
+                negated,
+                PolifunctionValue::Interval(Interval::new(-5.0, -2.0, upper_inclusive, lower_inclusive).unwrap()),
+                "[2, 5] with inclusivity {:?}", (lower_inclusive, upper_inclusive),
+            );
+        }
+        assert_eq!(negate(real_interval(2.0, 5.0, true, true)).evaluate(&0.0).unwrap().to_string(), "[-5, -2]");
+    }
+    
+    #[test]
+    fn negate_sets() {
+        assert_eq!(negate(int_set(|x| vec![x, x + 1])).with_sets().evaluate(&1), Ok(set_of(&[-1, -2])));
+        assert_eq!(negate(int_interval(|x| (x, x + 2))).evaluate(&1), Ok(closed_int(-3, -1)));
+        
+        // Without with_sets there is no way to collect the negated set
+        assert_eq!(negate(int_set(|x| vec![x, x + 1])).evaluate(&1), Err(PolifunctionError::InvalidOperation));
+        assert_eq!(negate(int_set(|_| vec![])).evaluate(&1), Ok(set_of(&[])));
+    }
+    
+    #[test]
+    fn map_codomain_real_values() {
+        let mapped = map_codomain(lift_real(|x| x + 1.0), |v: &f64| Ok(v.exp()), Unbounded::<f64>::new());
+        assert_eq!(mapped.evaluate(&-1.0).unwrap().as_single(), Some(&1.0));
+        
+        // Decreasing maps reorder the endpoints
+        let mapped = map_codomain(real_interval(1.0, 2.0, true, false), |v: &f64| Ok(-2.0 * v), Unbounded::<f64>::new());
+        assert_eq!(mapped.evaluate(&0.0).unwrap_err(), PolifunctionError::InvalidOperation);
+        let mapped = mapped.assume_monotone().evaluate(&0.0).unwrap();
+        let interval = mapped.as_interval().unwrap();
+        assert_eq!((interval.lower, interval.upper), (-4.0, -2.0));
+        assert_eq!((interval.lower_inclusive, interval.upper_inclusive), (false, true));
+    }
+    
+    #[test]
+    fn map_codomain_checks_the_new_codomain() {
+        let mapped = map_codomain(lift_real(|x| x), |v: &f64| Ok(v * v), RealInterval::closed(0.0, 4.0));
+        assert_eq!(mapped.evaluate(&2.0).unwrap().as_single(), Some(&4.0));
+        let error = mapped.evaluate(&3.0).unwrap_err();
+        assert_eq!(error.to_string(), format!("mapped value is outside the codomain: {}", PolifunctionError::ComputationError));
+        
+        let mapped = map_codomain(int_set(|x| vec![x, -x]), |v: &i64| Ok(v * v), IntegerRange::new(0, 10).unwrap()).with_sets();
+        assert_eq!(mapped.evaluate(&3), Ok(set_of(&[9])));
+        assert!(mapped.evaluate(&4).is_err());
+    }
+    
+    fn sum_and_difference() -> BasicSetValuedPolifunction<ProductDomain<IntegerRange, IntegerRange>, Ints> {
+        BasicSetValuedPolifunction::new(
+            |(a, b): &(i64, i64)| Ok([a + b, a - b].into_iter().collect()),
+            ProductDomain::new(IntegerRange::new(0, 4).unwrap(), IntegerRange::new(-2, 2).unwrap()),
+            Ints::new(),
+        )
+    }
+    
+    #[test]
+    fn two_argument_polifunction_over_product_domain() {
+        let p = sum_and_difference();
+        assert_eq!(p.evaluate(&(3, 1)), Ok(set_of(&[4, 2])));
+        assert_eq!(p.evaluate(&(2, 0)), Ok(set_of(&[2])));
+        assert_eq!(p.evaluate(&(4, 0)), Err(PolifunctionError::DomainError));
+        assert_eq!(p.evaluate(&(0, 2)), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn partial_apply_fixes_first_argument() {
+        let p = partial_apply(sum_and_difference(), 3);
+        assert_eq!(p.evaluate(&1), Ok(set_of(&[4, 2])));
+        assert_eq!(p.evaluate(&-2), Ok(set_of(&[1, 5])));
+        assert!(!p.in_domain(&2));
+        assert_eq!(p.evaluate(&2), Err(PolifunctionError::DomainError));
+        
+        // A first argument outside its range leaves nothing in the domain
+        let p = partial_apply(sum_and_difference(), 4);
+        assert!((-2..2).all(|y| !p.in_domain(&y)));
+    }
+    
+    #[test]
+    fn map_codomain_to_strings() {
+        let mapped = map_codomain(lift_int(|x| x * 2), |v: &i64| Ok(v.to_string()), Unbounded::<String>::new());
+        assert_eq!(mapped.evaluate(&-3), Ok(PolifunctionValue::Single("-6".to_string())));
+        
+        let mapped = map_codomain(int_set(|x| vec![x, x + 10]), |v: &i64| Ok(v.to_string()), Unbounded::<String>::new()).with_sets();
+        assert_eq!(mapped.evaluate(&1), Ok(PolifunctionValue::Set(["1".to_string(), "11".to_string()].into_iter().collect())));
+        assert_eq!(mapped.evaluate(&5), Err(PolifunctionError::DomainError));
+        
+        // Errors from the function fail the whole evaluation
+        let non_negative = |v: &i64| if *v < 0 { Err(PolifunctionError::ComputationError) } else { Ok(v.to_string()) };
+        let mapped = map_codomain(int_set(|x| vec![x, -x]), non_negative, Unbounded::<String>::new()).with_sets();
+        assert_eq!(mapped.evaluate(&2), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn contramap_domain_accepts_integer_inputs() {
+        let root = LiftedPolifunction::new(|x: &f64| Ok(x.sqrt()), RealInterval::closed(0.0, 100.0), Unbounded::<f64>::new());
+        let adapted = contramap_domain(root, |n: &i32| *n as f64, Unbounded::<i32>::new());
+        assert_eq!(adapted.evaluate(&49).unwrap().as_single(), Some(&7.0));
+        
+        // The converted input must lie in the original domain
+        assert!(!adapted.in_domain(&-1));
+        assert!(!adapted.in_domain(&101));
+        assert_eq!(adapted.evaluate(&-1).unwrap_err(), PolifunctionError::DomainError);
+    }
+    
+    #[test]
+    fn contramap_domain_checks_the_new_domain() {
+        let root = LiftedPolifunction::new(|x: &f64| Ok(x.sqrt()), RealInterval::closed(0.0, 100.0), Unbounded::<f64>::new());
+        let even = PredicateDomain::new(|n: &i32| n % 2 == 0);
+        let adapted = contramap_domain(root, |n: &i32| *n as f64, even);
+        assert!(adapted.in_domain(&4));
+        assert!(!adapted.in_domain(&9));
+        assert_eq!(adapted.evaluate(&9).unwrap_err(), PolifunctionError::DomainError);
+    }
+    
+    /// Fine on [0, 3) except for a computation error at 2, and a DomainError from evaluate at 1
+    fn narrow_primary() -> LiftedPolifunction<impl Fn(&i64) -> Result<i64, PolifunctionError>, IntegerRange, Ints> {
+        LiftedPolifunction::new(
+            |x: &i64| match x {
+                1 => Err(PolifunctionError::DomainErrorAt("1".to_string())),
+                2 => Err(PolifunctionError::ComputationError),
+                _ => Ok(100 + x),
+            },
+            IntegerRange::new(0, 3).unwrap(),
+            Ints::new(),
+        )
+    }
+    
+    #[test]
+    fn fallback_membership_and_failure_combinations() {
+        let fallback = FallbackPolifunction::new(narrow_primary(), lift_int(|x| -x));
+        
+        // In the primary domain, primary succeeds
+        assert_eq!(fallback.evaluate(&0), Ok(PolifunctionValue::Single(100)));
+        // In the primary domain, primary fails with a non-domain error
+        assert_eq!(fallback.evaluate(&2), Err(PolifunctionError::ComputationError));
+        // Outside the primary domain, inside the secondary one
+        assert_eq!(fallback.evaluate(&-4), Ok(PolifunctionValue::Single(4)));
+        // Outside both domains
+        assert_eq!(fallback.evaluate(&7), Err(PolifunctionError::DomainError));
+        assert!(!fallback.in_domain(&7));
+        
+        // A DomainError reported by evaluate falls back as well
+        assert_eq!(fallback.evaluate(&1), Ok(PolifunctionValue::Single(-1)));
+    }
+    
+    #[test]
+    fn fallback_on_errors_when_requested() {
+        let fallback = FallbackPolifunction::new(narrow_primary(), lift_int(|x| -x)).fall_back_on_errors();
+        assert_eq!(fallback.evaluate(&0), Ok(PolifunctionValue::Single(100)));
+        assert_eq!(fallback.evaluate(&2), Ok(PolifunctionValue::Single(-2)));
+        assert_eq!(fallback.evaluate(&-4), Ok(PolifunctionValue::Single(4)));
+        
+        // Without a usable secondary the primary error is returned
+        let secondary = LiftedPolifunction::new(|x: &i64| Ok(-x), IntegerRange::new(10, 20).unwrap(), Ints::new());
+        let fallback = FallbackPolifunction::new(narrow_primary(), secondary).fall_back_on_errors();
+        assert_eq!(fallback.evaluate(&2), Err(PolifunctionError::ComputationError));
+    }
+    
+    type RealFn = LiftedPolifunction<fn(&f64) -> Result<f64, PolifunctionError>, Unbounded<f64>, Unbounded<f64>>;
+    
+    /// `x` on [0, 1] and `x + shift` on [1, 2], overlapping at 1
+    fn two_real_pieces(shift: fn(&f64) -> Result<f64, PolifunctionError>, policy: OverlapPolicy) -> PiecewisePolifunction<RealFn> {
+        let identity: fn(&f64) -> Result<f64, PolifunctionError> = |x| Ok(*x);
+        PiecewisePolifunction::new(vec![
+            (Box::new(RealInterval::closed(0.0, 1.0)), LiftedPolifunction::new(identity, Unbounded::new(), Unbounded::new())),
+            (Box::new(RealInterval::closed(1.0, 2.0)), LiftedPolifunction::new(shift, Unbounded::new(), Unbounded::new())),
+        ], policy)
+    }
+    
+    #[test]
+    fn piecewise_first_match_at_overlapping_boundary() {
+        let piecewise = two_real_pieces(|x| Ok(x + 1.0), OverlapPolicy::FirstMatch);
+        assert_eq!(piecewise.evaluate(&0.5).unwrap().as_single(), Some(&0.5));
+        assert_eq!(piecewise.evaluate(&1.0).unwrap().as_single(), Some(&1.0));
+        assert_eq!(piecewise.evaluate(&1.5).unwrap().as_single(), Some(&2.5));
+        assert_eq!(piecewise.evaluate(&3.0).unwrap_err(), PolifunctionError::DomainError);
+    }
+    
+    #[test]
+    fn piecewise_union_values_at_overlapping_boundary() {
+        // Pieces agreeing at the boundary give a single value
+        let piecewise = two_real_pieces(|x| Ok(2.0 - x), OverlapPolicy::UnionValues);
+        assert_eq!(piecewise.evaluate(&1.0).unwrap().as_single(), Some(&1.0));
+        
+        // Distinct f64 values cannot be collected into a set
+        let piecewise = two_real_pieces(|x| Ok(x + 1.0), OverlapPolicy::UnionValues);
+        assert_eq!(piecewise.evaluate(&1.5).unwrap().as_single(), Some(&2.5));
+        assert_eq!(
+            piecewise.evaluate(&1.0).unwrap_err().to_string(),
+            format!("overlapping pieces have distinct values and with_sets is not enabled: {}", PolifunctionError::InvalidOperation),
+        );
+        
+        // Intervals are merged into their hull
+        let piecewise = PiecewisePolifunction::new(vec![
+            (Box::new(RealInterval::closed(0.0, 1.0)) as PieceDomain<f64>, real_interval(0.0, 1.0, true, false)),
+            (Box::new(RealInterval::closed(1.0, 2.0)), real_interval(2.0, 3.0, false, true)),
+        ], OverlapPolicy::UnionValues);
+        let merged = piecewise.evaluate(&1.0).unwrap();
+        let interval = merged.as_interval().unwrap();
+        assert_eq!((interval.lower, interval.upper), (1.0, 4.0));
+        assert_eq!((interval.lower_inclusive, interval.upper_inclusive), (true, true));
+    }
+    
+    #[test]
+    fn piecewise_union_values_merges_integer_sets() {
+        let pieces = || -> Vec<(PieceDomain<i64>, BasicSetValuedPolifunction<IntegerRange, Ints>)> {
+            vec![
+                (Box::new(IntegerRange::new(-5, 1).unwrap()), int_set(|x| vec![x])),
+                (Box::new(IntegerRange::new(0, 5).unwrap()), int_set(|x| vec![x + 1, x + 2])),
+            ]
+        };
+        let piecewise = PiecewisePolifunction::new(pieces(), OverlapPolicy::UnionValues);
+        assert!(piecewise.evaluate(&0).is_err());
+        
+        let piecewise = PiecewisePolifunction::new(pieces(), OverlapPolicy::UnionValues).with_sets();
+        assert_eq!(piecewise.evaluate(&0), Ok(set_of(&[0, 1, 2])));
+        assert_eq!(piecewise.evaluate(&3), Ok(set_of(&[4, 5])));
+    }
+    
+    #[test]
+    fn piecewise_error_policy_at_overlapping_boundary() {
+        let piecewise = two_real_pieces(|x| Ok(2.0 - x), OverlapPolicy::Error);
+        assert_eq!(piecewise.evaluate(&0.5).unwrap().as_single(), Some(&0.5));
+        assert_eq!(piecewise.evaluate(&1.5).unwrap().as_single(), Some(&0.5));
+        let error = piecewise.evaluate(&1.0).unwrap_err();
+        assert_eq!(error.to_string(), format!("input is contained in 2 overlapping pieces: {}", PolifunctionError::InvalidOperation));
+        assert!(!error.is_domain_error());
+    }
+    
+    #[test]
+    fn conditional_dispatches_on_both_sides_of_threshold() {
+        let zero = LiftedPolifunction::new(|_: &f64| Ok(0.0), Unbounded::<f64>::new(), Unbounded::<f64>::new());
+        let branching = conditional(|x: &f64| *x < 1.0, zero, real_interval(-1.0, 1.0, true, true));
+        
+        assert_eq!(branching.evaluate(&0.5).unwrap().as_single(), Some(&0.0));
+        let above = branching.evaluate(&1.0).unwrap();
+        let interval = above.as_interval().unwrap();
+        assert_eq!((interval.lower, interval.upper), (0.0, 2.0));
+    }
+    
+    #[test]
+    fn conditional_domain_follows_selected_branch() {
+        let small = LiftedPolifunction::new(|x: &i64| Ok(x * 2), IntegerRange::new(0, 2).unwrap(), Ints::new());
+        let branching = conditional(|x: &i64| *x < 2, small, lift_int(|x| -x));
+        
+        assert!(branching.in_domain(&1));
+        assert!(!branching.in_domain(&-1));
+        assert_eq!(branching.evaluate(&-1), Err(PolifunctionError::DomainError));
+        // The narrow true-branch domain does not constrain false-branch inputs
+        assert!(branching.in_domain(&3));
+        assert_eq!(branching.evaluate(&3), Ok(PolifunctionValue::Single(-3)));
+    }
+    
+    #[test]
+    fn conditional_precheck_guards_the_predicate() {
+        let panicking = |x: &i64| {
+            assert!((0..5).contains(x), "predicate called outside its domain");
+            *x % 2 == 0
+        };
+        let branching = conditional(panicking, lift_int(|x| x), lift_int(|x| -x))
+            .precheck(IntegerRange::new(0, 5).unwrap());
+        
+        assert_eq!(branching.evaluate(&2), Ok(PolifunctionValue::Single(2)));
+        assert_eq!(branching.evaluate(&3), Ok(PolifunctionValue::Single(-3)));
+        assert!(!branching.in_domain(&-3));
+        assert_eq!(branching.evaluate(&-3), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn compose_real_singles() {
+        let composed = compose(lift_real(|x| x * x), lift_real(|x| x - 1.0));
+        assert_eq!(composed.evaluate(&4.0).unwrap().as_single(), Some(&9.0));
+        
+        let outer = LiftedPolifunction::new(|x: &f64| Ok(x.sqrt()), RealInterval::closed(0.0, 10.0), Unbounded::new());
+        let composed = compose(outer, lift_real(|x| x - 1.0));
+        assert_eq!(composed.evaluate(&5.0).unwrap().as_single(), Some(&2.0));
+        assert!(!composed.in_domain(&0.0));
+        assert!(composed.evaluate(&0.0).unwrap_err().is_domain_error());
+    }
+    
+    #[test]
+    fn lifted_reals_compose_through_composable() {
+        use crate::core::interfaces::polifunction::Composable;
+        
+        let sine = LiftedPolifunction::new(|x: &f64| Ok(x.sin()), Unbounded::new(), Unbounded::new());
+        let root = LiftedPolifunction::new(|x: &f64| Ok(x.sqrt()), RealInterval::closed(0.0, f64::INFINITY), Unbounded::new());
+        
+        let composed = (&root).compose(&sine).unwrap();
+        let value = composed.evaluate(&std::f64::consts::FRAC_PI_2).unwrap();
+        assert!((value.as_single().unwrap() - 1.0).abs() < 1e-12);
+        // sin(-π/2) = -1 lies outside the domain of the square root
+        assert!(composed.evaluate(&-std::f64::consts::FRAC_PI_2).unwrap_err().is_domain_error());
+        
+        let composed = sine.compose(root).unwrap();
+        assert_eq!(composed.evaluate(&0.0).unwrap().as_single(), Some(&0.0));
+    }
+    
+    #[test]
+    fn compose_squares_set_branches() {
+        let composed = compose_sets(lift_int(|x| x * x), int_set(|x| vec![x, -x]));
+        assert_eq!(composed.evaluate(&3), Ok(set_of(&[9])));
+        assert_eq!(composed.evaluate(&0), Ok(set_of(&[0])));
+        
+        let composed = compose_sets(lift_int(|x| x * x), int_set(|x| vec![x, x + 1]));
+        assert_eq!(composed.evaluate(&-1), Ok(set_of(&[1, 0])));
+        
+        // Plain compose does not merge sets
+        let composed = compose(lift_int(|x| x * x), int_set(|x| vec![x, -x]));
+        assert_eq!(composed.evaluate(&3), Err(PolifunctionError::InvalidOperation));
+    }
+    
+    #[test]
+    fn compose_skips_set_elements_outside_outer_domain() {
+        let naturals = LiftedPolifunction::new(|x: &i64| Ok(x * x), IntegerRange::new(0, 5).unwrap(), Ints::new());
+        let composed = compose_sets(naturals, int_set(|x| vec![x, -x]));
+        assert_eq!(composed.evaluate(&-2), Ok(set_of(&[4])));
+        assert!(composed.in_domain(&-2));
+        
+        let naturals = LiftedPolifunction::new(|x: &i64| Ok(x * x), IntegerRange::new(10, 20).unwrap(), Ints::new());
+        let composed = compose_sets(naturals, int_set(|x| vec![x, -x]));
+        assert_eq!(composed.evaluate(&2), Ok(set_of(&[])));
+        assert!(composed.in_domain(&2));
+    }
+    
+    #[test]
+    fn compose_accepts_singleton_sets_from_normalizing_operands() {
+        // Both operands give 1 at 1, so the union reports Single(1)
+        let union = UnionPolifunction::new(int_set(|x| vec![x * x]), int_set(|x| vec![x * x * x]));
+        let composed = compose(lift_int(|x| x + 1), union);
+        assert_eq!(composed.evaluate(&1), Ok(PolifunctionValue::Single(2)));
+        assert_eq!(composed.evaluate(&0), Ok(PolifunctionValue::Single(1)));
+        
+        // A genuine two-element set still needs compose_sets
+        assert_eq!(composed.evaluate(&-1), Err(PolifunctionError::InvalidOperation));
+        
+        let composed = compose(lift_int(|x| x + 1), lift_to_set(|x: &i64| Ok(x * x), small_range(), Ints::new()));
+        assert_eq!(composed.evaluate(&-2), Ok(PolifunctionValue::Single(5)));
+    }
+    
+    #[test]
+    fn to_interval_spans_the_set_and_collapses_singletons() {
+        let spread = to_interval(int_set(|x| vec![x, -x, 0]));
+        assert_eq!(spread.value_interval(&3), Interval::new(-3, 3, true, true));
+        assert_eq!(spread.evaluate(&-2), Ok(PolifunctionValue::Interval(Interval::new(-2, 2, true, true).unwrap())));
+        assert_eq!(spread.contains_value(&3, &1), Ok(true));
+        assert_eq!(spread.interval_width(&3), Ok(6));
+        
+        // A one-element set is a degenerate interval, which evaluate reports as Single
+        assert_eq!(spread.value_interval(&0), Interval::new(0, 0, true, true));
+        assert_eq!(spread.evaluate(&0), Ok(PolifunctionValue::Single(0)));
+        
+        assert!(spread.evaluate(&5).unwrap_err().is_domain_error());
+        let empty = to_interval(int_set(|_| vec![]));
+        assert_eq!(empty.value_interval(&1), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn lift_to_set_reports_one_element_sets_as_single() {
+        let squares = lift_to_set(|x: &i64| Ok(x * x), small_range(), Ints::new());
+        assert_eq!(squares.value_set(&-3), Ok([9].into_iter().collect()));
+        assert_eq!(squares.evaluate(&-3), Ok(PolifunctionValue::Single(9)));
+        assert_eq!(squares.contains_value(&2, &4), Ok(true));
+        assert_eq!(squares.contains_value(&2, &-4), Ok(false));
+        assert_eq!(squares.cardinality(&2), Ok(1));
+        assert!(squares.value_set(&5).unwrap_err().is_domain_error());
+        assert!(squares.cardinality(&5).unwrap_err().is_domain_error());
+    }
+    
+    fn discretize_all() -> CompositionStrategy<i64> {
+        CompositionStrategy {
+            interval_input: IntervalInput::Discretize(Box::new(|i: &Interval<i64>| (i.lower..=i.upper).collect())),
+        }
+    }
+    
+    fn monotone() -> CompositionStrategy<i64> {
+        CompositionStrategy {
+            interval_input: IntervalInput::MonotoneEndpoints,
+        }
+    }
+    
+    #[test]
+    fn strategy_single_intermediate_passes_outer_value_through() {
+        let composed = compose_with_strategy(lift_int(|x| x * x), lift_int(|x| x - 1), CompositionStrategy::default());
+        assert_eq!(composed.evaluate(&3), Ok(PolifunctionValue::Single(4)));
+        
+        let composed = compose_with_strategy(int_interval(|x| (x, x + 1)), lift_int(|x| x - 1), CompositionStrategy::default());
+        assert_eq!(composed.evaluate(&3), Ok(closed_int(2, 3)));
+    }
+    
+    #[test]
+    fn strategy_set_intermediate() {
+        let composed = compose_with_strategy(lift_int(|x| x * x), int_set(|x| vec![x, -x]), CompositionStrategy::default());
+        assert_eq!(composed.evaluate(&2), Ok(set_of(&[4])));
+        
+        let composed = compose_with_strategy(int_set(|x| vec![x, x + 1]), int_set(|x| vec![x, -x]), CompositionStrategy::default());
+        assert_eq!(composed.evaluate(&2), Ok(set_of(&[2, 3, -2, -1])));
+        
+        let composed = compose_with_strategy(int_interval(|x| (x, x + 1)), int_set(|x| vec![x, -x]), CompositionStrategy::default());
+        assert_eq!(composed.evaluate(&2), Ok(closed_int(-2, 3)));
+    }
+    
+    #[test]
+    fn strategy_interval_intermediate() {
+        let composed = compose_with_strategy(lift_int(|x| x * x), int_interval(|x| (x - 1, x)), CompositionStrategy::default());
+        assert_unsupported_cell(composed.evaluate(&1).unwrap_err(), "Interval intermediate value with any (no interval input strategy) outer values");
+        
+        let composed = compose_with_strategy(int_interval(|x| (x, x + 1)), int_interval(|x| (x - 1, x)), monotone());
+        assert_eq!(composed.evaluate(&1), Ok(closed_int(0, 2)));
+        
+        let composed = compose_with_strategy(lift_int(|x| x * x), int_interval(|x| (x - 1, x)), monotone());
+        assert_unsupported_cell(composed.evaluate(&1).unwrap_err(), "Interval intermediate value with non-Interval (use Discretize) outer values");
+        
+        let composed = compose_with_strategy(lift_int(|x| x * x), int_interval(|x| (x - 1, x)), discretize_all());
+        assert_eq!(composed.evaluate(&1), Ok(set_of(&[0, 1])));
+        
+        let composed = compose_with_strategy(int_interval(|x| (x, x + 1)), int_interval(|x| (x - 2, x)), discretize_all());
+        assert_eq!(composed.evaluate(&1), Ok(closed_int(-1, 2)));
+    }
+    
+    #[test]
+    fn strategy_rejects_mixed_outer_kinds() {
+        let outer = || ValueFn(|x: i64| if x < 0 { set_of(&[]) } else { closed_int(x, x) });
+        
+        // An empty Set followed by an Interval is still a mix of kinds
+        let composed = compose_with_strategy(outer(), ValueFn(|_| PolifunctionValue::Set([-1, 1].into_iter().collect())), CompositionStrategy::default());
+        assert_unsupported_cell(composed.evaluate(&0).unwrap_err(), "Set intermediate value with mixed Single/Set and Interval outer values");
+        
+        let composed = compose_with_strategy(outer(), int_interval(|x| (x - 1, x)), discretize_all());
+        assert_unsupported_cell(composed.evaluate(&0).unwrap_err(), "Interval intermediate value with mixed Single/Set and Interval outer values");
+    }
+    
+    #[test]
+    fn strategy_rejects_distributions() {
+        let distribution = || PolifunctionValue::Distribution(ProbabilityDistribution::new(vec![(1, 1.0)]).unwrap());
+        
+        let composed = compose_with_strategy(lift_int(|x| x), ValueFn(move |_| distribution()), CompositionStrategy::default());
+        assert_unsupported_cell(composed.evaluate(&0).unwrap_err(), "Distribution intermediate value with any outer values");
+        
+        let composed = compose_with_strategy(ValueFn(move |_| distribution()), int_set(|x| vec![x]), CompositionStrategy::default());
+        assert_unsupported_cell(composed.evaluate(&0).unwrap_err(), "Set intermediate value with Distribution outer values");
+    }
+    
+    fn fuzzy(pairs: Vec<(i64, f64)>) -> BasicFuzzyValuedPolifunction<IntegerRange, Ints> {
+        BasicFuzzyValuedPolifunction::new(move |_: &i64| FuzzySet::new(pairs.clone()), small_range(), Ints::new())
+    }
+    
+    fn distribution(pairs: Vec<(i64, f64)>) -> BasicDistributionValuedPolifunction<IntegerRange, Ints> {
+        BasicDistributionValuedPolifunction::new(move |_: &i64| ProbabilityDistribution::new(pairs.clone()), small_range(), Ints::new())
+    }
+    
+    #[test]
+    fn fuzzy_to_set_keeps_degrees_at_or_above_alpha() {
+        let p = || fuzzy(vec![(1, 0.2), (2, 0.5), (3, 1.0)]);
+        assert_eq!(fuzzy_to_set(p(), 0.5).unwrap().evaluate(&0), Ok(set_of(&[2, 3])));
+        assert_eq!(fuzzy_to_set(p(), 0.0).unwrap().value_set(&0), Ok([1, 2, 3].into_iter().collect()));
+        assert_eq!(fuzzy_to_set(p(), 1.0).unwrap().value_set(&0), Ok([3].into_iter().collect()));
+        
+        let cut = fuzzy_to_set(p(), 0.5).unwrap();
+        assert_eq!(cut.contains_value(&0, &2), Ok(true));
+        assert_eq!(cut.contains_value(&0, &1), Ok(false));
+        assert_eq!(cut.cardinality(&0), Ok(2));
+        assert_eq!(cut.evaluate(&5), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn fuzzy_to_set_rejects_alpha_outside_unit_interval() {
+        for alpha in [-0.1, 1.1, f64::NAN] {
+            match fuzzy_to_set(fuzzy(vec![(1, 1.0)]), alpha) {
+                Err(PolifunctionError::Other(message)) => assert_eq!(message, format!("Alpha level {} is outside [0, 1]", alpha)),
+                _ => panic!("alpha {} was accepted", alpha),
+            }
+        }
+    }
+    
+    #[test]
+    fn distribution_to_interval_picks_the_narrowest_window() {
+        let p = || distribution(vec![(10, 0.1), (0, 0.1), (2, 0.4), (1, 0.4)]);
+        let interval = |coverage| distribution_to_interval(p(), coverage).unwrap().value_interval(&0).unwrap();
+        assert_eq!(interval(0.8), Interval::new(1, 2, true, true).unwrap());
+        assert_eq!(interval(0.9), Interval::new(0, 2, true, true).unwrap());
+        assert_eq!(interval(1.0), Interval::new(0, 10, true, true).unwrap());
+        assert_eq!(interval(0.3), Interval::new(1, 1, true, true).unwrap());
+        
+        let confidence = distribution_to_interval(p(), 0.8).unwrap();
+        assert_eq!(confidence.evaluate(&0), Ok(closed_int(1, 2)));
+        assert_eq!(confidence.evaluate(&5), Err(PolifunctionError::DomainError));
+    }
+    
+    #[test]
+    fn distribution_to_interval_prefers_the_leftmost_of_equal_windows() {
+        let uniform = || distribution(vec![(3, 0.25), (2, 0.25), (1, 0.25), (0, 0.25)]);
+        assert_eq!(distribution_to_interval(uniform(), 0.5).unwrap().value_interval(&0), Interval::new(0, 1, true, true));
+        assert_eq!(distribution_to_interval(uniform(), 0.75).unwrap().value_interval(&0), Interval::new(0, 2, true, true));
+    }
+    
+    #[test]
+    fn distribution_to_interval_rejects_coverage_outside_half_open_unit_interval() {
+        for coverage in [0.0, -0.5, 1.5, f64::NAN] {
+            match distribution_to_interval(distribution(vec![(1, 1.0)]), coverage) {
+                Err(PolifunctionError::Other(message)) => assert_eq!(message, format!("Coverage {} is outside (0, 1]", coverage)),
+                _ => panic!("coverage {} was accepted", coverage),
+            }
+        }
+        assert!(distribution_to_interval(distribution(vec![(1, 1.0)]), 1.0).is_ok());
+    }
+    
+    #[test]
+    fn distribution_to_interval_rejects_unordered_values() {
+        let nan = BasicDistributionValuedPolifunction::new(
+            |_: &f64| ProbabilityDistribution::new(vec![(0.0, 0.5), (f64::NAN, 0.5)]),
+            Unbounded::new(),
+            Unbounded::<f64>::new(),
+        );
+        let confidence = distribution_to_interval(nan, 0.5).unwrap();
+        assert_eq!(confidence.value_interval(&0.0), Err(PolifunctionError::ComputationError));
+    }
+    
+    fn relation(pairs: &[(i64, i64)]) -> TabularSetValuedPolifunction<i64, i64> {
+        pairs.iter().copied().collect()
+    }
+    
+    fn sorted_pairs<K: Ord + Copy + Hash + Eq, V: Ord + Copy + Hash + Eq>(p: &TabularSetValuedPolifunction<K, V>) -> Vec<(K, V)> {
+        let mut pairs: Vec<_> = p.pairs().map(|(k, v)| (*k, *v)).collect();
+        pairs.sort();
+        pairs
+    }
+    
+    #[test]
+    fn converse_swaps_pairs() {
+        let p: TabularSetValuedPolifunction<i64, &str> = [(1, "a"), (2, "a"), (2, "b")].into_iter().collect();
+        let inverse = converse(&p);
+        assert_eq!(sorted_pairs(&inverse), vec![("a", 1), ("a", 2), ("b", 2)]);
+        assert_eq!(inverse.value_set(&"a"), Ok([1, 2].into_iter().collect()));
+        assert_eq!(sorted_pairs(&converse(&inverse)), sorted_pairs(&p));
+    }
+    
+    #[test]
+    fn relational_compose_applies_q_first() {
+        let q = relation(&[(1, 10), (1, 20), (2, 30), (3, 10)]);
+        let p = relation(&[(10, 100), (20, 200), (20, 201)]);
+        let composed = relational_compose(&p, &q);
+        assert_eq!(sorted_pairs(&composed), vec![(1, 100), (1, 200), (1, 201), (3, 100)]);
+        
+        // 2 only reaches 30, which p does not relate, but it stays in the domain
+        assert!(composed.in_domain(&2));
+        assert_eq!(composed.cardinality(&2), Ok(0));
+        assert!(!composed.in_domain(&10));
+    }
+    
+    #[test]
+    fn transitive_closure_of_a_chain() {
+        let chain = relation(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        let closure = transitive_closure(&chain, 3).unwrap();
+        let expected: Vec<_> = (1..5).flat_map(|x| (x + 1..=5).map(move |y| (x, y))).collect();
+        assert_eq!(sorted_pairs(&closure), expected);
+        
+        // Paths of length 4 need two squarings and a third step to see the fixed point
+        assert_eq!(transitive_closure(&chain, 2).unwrap_err(), PolifunctionError::ConvergenceError);
+        assert_eq!(transitive_closure(&chain, 0).unwrap_err(), PolifunctionError::ConvergenceError);
+    }
+    
+    #[test]
+    fn transitive_closure_of_a_cycle() {
+        let cycle = relation(&[(1, 2), (2, 3), (3, 1), (4, 1)]);
+        let closure = transitive_closure(&cycle, 10).unwrap();
+        for x in 1..=4 {
+            assert_eq!(closure.value_set(&x), Ok([1, 2, 3].into_iter().collect()), "input {}", x);
+        }
+        
+        // Already transitive relations are their own closure
+        let reflexive = relation(&[(1, 1), (2, 2)]);
+        assert_eq!(sorted_pairs(&transitive_closure(&reflexive, 1).unwrap()), vec![(1, 1), (2, 2)]);
+    }
+    
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_evaluate_batch_matches_evaluate_batch() {
+        let divisors = BasicSetValuedPolifunction::new_sync(
+            |x: &i64| if x % 7 == 0 {
+                Err(PolifunctionError::ComputationError)
+            } else {
+                Ok((1..=*x).filter(|d| x % d == 0).collect())
+            },
+            IntegerRange::new(1, 3000).unwrap(),
+            Ints::new(),
+        );
+        let inputs: Vec<i64> = (-100..3100).collect();
+        let parallel = par_evaluate_batch(&divisors, &inputs, TrustLevel::Checked);
+        assert_eq!(parallel.len(), inputs.len());
+        assert_eq!(parallel, divisors.evaluate_batch(&inputs, TrustLevel::Checked));
+        assert!(par_evaluate_batch(&divisors, &[], TrustLevel::Checked).is_empty());
+        
+        // Trusted evaluation skips the domain check, so -6 gets its (empty) set of divisors
+        let trusted = par_evaluate_batch(&divisors, &inputs, TrustLevel::Trusted);
+        assert_eq!(trusted, divisors.evaluate_batch(&inputs, TrustLevel::Trusted));
+        assert!(parallel[94].as_ref().unwrap_err().is_domain_error());
+        assert_eq!(trusted[94], Ok(PolifunctionValue::Set(HashSet::new())));
+    }
+    
+    #[test]
+    fn trusted_batch_skips_the_domain_check() {
+        let square = lift_int(|x| x * x);
+        let inputs = [-2, 7];
+        
+        let checked = square.evaluate_batch(&inputs, TrustLevel::Checked);
+        assert_eq!(checked[0], Ok(PolifunctionValue::Single(4)));
+        assert!(checked[1].as_ref().unwrap_err().is_domain_error());
+        
+        // 7 is outside [-5, 5), but the caller vouched for it
+        let trusted = square.evaluate_batch(&inputs, TrustLevel::Trusted);
+        assert_eq!(trusted, vec![Ok(PolifunctionValue::Single(4)), Ok(PolifunctionValue::Single(49))]);
     }
 }
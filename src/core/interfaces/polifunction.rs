@@ -3,41 +3,128 @@
 //! This module defines the fundamental abstractions for working with
 //! polifunctions - mathematical functions that can return multiple values.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
 
 /// Error type for polifunction operations
+///
+/// Errors compare equal if their [`root`](PolifunctionError::root) errors are
+/// the same kind, so tests can `assert_eq!` on the kind of error however deeply
+/// it was wrapped. Messages, inputs and contexts are ignored, and
+/// `DomainErrorAt` is the same kind as `DomainError`; compare `to_string()` to
+/// check them.
 #[derive(Debug)]
 pub enum PolifunctionError {
     /// Input is outside the function's domain
     DomainError,
+    /// Input, rendered as a string, is outside the function's domain
+    DomainErrorAt(String),
     /// Error during computation or evaluation
     ComputationError,
     /// Failed to converge to a result
     ConvergenceError,
     /// Invalid operation for this polifunction type
     InvalidOperation,
+    /// Evaluation exceeded its time budget
+    Timeout,
+    /// The result would be empty (e.g. intersecting disjoint intervals)
+    EmptyResult,
     /// Other errors with description
     Other(String),
+    /// Error annotated with where it occurred, e.g. which operand of a combinator failed
+    Wrapped {
+        /// Description of where the error occurred
+        context: String,
+        /// The underlying error
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl PolifunctionError {
+    /// Create a domain error that records the offending input
+    pub fn domain_error_at(input: &impl Debug) -> Self {
+        PolifunctionError::DomainErrorAt(format!("{:?}", input))
+    }
+    
+    /// Wrap the error with a description of where it occurred
+    pub fn context(self, context: impl Into<String>) -> Self {
+        PolifunctionError::Wrapped {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+    
+    /// The innermost error, looking through `Wrapped` layers around polifunction errors
+    ///
+    /// A `Wrapped` error whose source is not a `PolifunctionError` is its own root.
+    pub fn root(&self) -> &PolifunctionError {
+        let mut error = self;
+        while let PolifunctionError::Wrapped { source, .. } = error {
+            match source.downcast_ref::<PolifunctionError>() {
+                Some(inner) => error = inner,
+                None => break,
+            }
+        }
+        error
+    }
+    
+    /// Variant of the root error, with `DomainErrorAt` counted as `DomainError`
+    fn kind(&self) -> std::mem::Discriminant<PolifunctionError> {
+        match self.root() {
+            PolifunctionError::DomainErrorAt(_) => std::mem::discriminant(&PolifunctionError::DomainError),
+            root => std::mem::discriminant(root),
+        }
+    }
+    
+    /// Check whether this is a domain error, looking through any `Wrapped` layers
+    pub fn is_domain_error(&self) -> bool {
+        matches!(self.root(), PolifunctionError::DomainError | PolifunctionError::DomainErrorAt(_))
+    }
 }
 
 impl Display for PolifunctionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             PolifunctionError::DomainError => write!(f, "Input is outside the function's domain"),
+            PolifunctionError::DomainErrorAt(input) => write!(f, "Input {} is outside the function's domain", input),
             PolifunctionError::ComputationError => write!(f, "Error during computation"),
             PolifunctionError::ConvergenceError => write!(f, "Failed to converge to a result"),
             PolifunctionError::InvalidOperation => write!(f, "Invalid operation for this polifunction type"),
+            PolifunctionError::Timeout => write!(f, "Evaluation exceeded its time budget"),
+            PolifunctionError::EmptyResult => write!(f, "The result is empty"),
             PolifunctionError::Other(msg) => write!(f, "{}", msg),
+            PolifunctionError::Wrapped { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl Error for PolifunctionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PolifunctionError::Wrapped { source, .. } => Some(source.as_ref()),
+            _ => None,
         }
     }
 }
 
-impl Error for PolifunctionError {}
+impl PartialEq for PolifunctionError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind()
+    }
+}
 
 /// Represents possible output values of a polifunction
+///
+/// With the `serde` feature, values serialize externally tagged by variant
+/// (e.g. `{"Single": 3}`), and the `Set` variant serializes as a sequence.
+/// Deserialized intervals, distributions and fuzzy sets are validated like
+/// their constructors.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: serde::Deserialize<'de> + Eq + std::hash::Hash + PartialOrd + Clone")))]
 pub enum PolifunctionValue<T> {
     /// A single value
     Single(T),
@@ -49,6 +136,319 @@ pub enum PolifunctionValue<T> {
     Distribution(ProbabilityDistribution<T>),
     /// A fuzzy set with membership degrees
     FuzzySet(FuzzySet<T>),
+    /// A union of disjoint intervals
+    MultiInterval(IntervalSet<T>),
+}
+
+impl<T: Display> PolifunctionValue<T> {
+    /// Format the value, listing the elements of a `Set` in the order given by `compare`
+    ///
+    /// This is what `Display` uses with the natural ordering; call it directly
+    /// for element types that are not `PartialOrd`. Other values are printed
+    /// as by `Display`.
+    pub fn fmt_with<F>(&self, f: &mut std::fmt::Formatter<'_>, compare: F) -> std::fmt::Result
+    where
+        F: Fn(&T, &T) -> std::cmp::Ordering,
+    {
+        match self {
+            PolifunctionValue::Single(value) => write!(f, "{}", value),
+            PolifunctionValue::Set(set) => {
+                let mut values: Vec<&T> = set.iter().collect();
+                values.sort_by(|a, b| compare(a, b));
+                
+                write!(f, "{{")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "}}")
+            },
+            PolifunctionValue::Interval(interval) => write!(f, "{}", interval),
+            PolifunctionValue::Distribution(distribution) => {
+                write!(f, "{{")?;
+                for (i, (value, probability)) in distribution.pairs().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}:{}", value, probability)?;
+                }
+                write!(f, "}}")
+            },
+            PolifunctionValue::FuzzySet(fuzzy_set) => {
+                write!(f, "{{")?;
+                for (i, (value, degree)) in fuzzy_set.pairs().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}/{}", value, degree)?;
+                }
+                write!(f, "}}")
+            },
+            PolifunctionValue::MultiInterval(intervals) => write!(f, "{}", intervals),
+        }
+    }
+}
+
+impl<T: PartialEq> PolifunctionValue<T> {
+    /// Convert the value to its simplest representation
+    ///
+    /// A one-element `Set` and a closed `Interval` with equal endpoints become
+    /// `Single`. Everything else is returned unchanged.
+    pub fn normalize(self) -> Self {
+        match self {
+            PolifunctionValue::Set(set) if set.len() == 1 => {
+                let value = set.into_iter().next().unwrap();
+                PolifunctionValue::Single(value)
+            },
+            PolifunctionValue::Interval(interval)
+                if interval.lower_inclusive && interval.upper_inclusive && interval.lower == interval.upper => {
+                PolifunctionValue::Single(interval.lower)
+            },
+            other => other,
+        }
+    }
+    
+    /// Check whether the value contains no possible outputs, i.e. is an empty `Set`
+    pub fn is_empty(&self) -> bool {
+        matches!(self, PolifunctionValue::Set(set) if set.is_empty())
+    }
+}
+
+impl<T> PolifunctionValue<T> {
+    /// Get the value if this is a `Single`
+    pub fn as_single(&self) -> Option<&T> {
+        match self {
+            PolifunctionValue::Single(value) => Some(value),
+            _ => None,
+        }
+    }
+    
+    /// Get the set if this is a `Set`
+    pub fn as_set(&self) -> Option<&HashSet<T>> {
+        match self {
+            PolifunctionValue::Set(set) => Some(set),
+            _ => None,
+        }
+    }
+    
+    /// Get the interval if this is an `Interval`
+    pub fn as_interval(&self) -> Option<&Interval<T>> {
+        match self {
+            PolifunctionValue::Interval(interval) => Some(interval),
+            _ => None,
+        }
+    }
+    
+    /// Check whether this is a `Single`
+    pub fn is_single(&self) -> bool {
+        matches!(self, PolifunctionValue::Single(_))
+    }
+    
+    /// Check whether this is a `Set`
+    pub fn is_set(&self) -> bool {
+        matches!(self, PolifunctionValue::Set(_))
+    }
+    
+    /// Check whether this is an `Interval`
+    pub fn is_interval(&self) -> bool {
+        matches!(self, PolifunctionValue::Interval(_))
+    }
+    
+    /// Take the value out of a `Single`
+    ///
+    /// Returns an InvalidOperation error for every other variant.
+    pub fn into_single(self) -> Result<T, PolifunctionError> {
+        match self {
+            PolifunctionValue::Single(value) => Ok(value),
+            _ => Err(PolifunctionError::InvalidOperation),
+        }
+    }
+    
+    /// Apply a function to every value of a non-`Set` value, keeping the variant
+    ///
+    /// Interval endpoints are mapped as they are, so `f` should be
+    /// non-decreasing: a decreasing `f` turns an interval into an empty one.
+    /// Probabilities and membership degrees are kept, and mapped interval
+    /// sets are normalized again. A `Set` fails with an InvalidOperation error,
+    /// since collecting the mapped set needs `Hash + Eq` values; use
+    /// [`map_with_sets`](Self::map_with_sets) for those.
+    pub fn map<U, F>(self, f: F) -> Result<PolifunctionValue<U>, PolifunctionError>
+    where
+        F: Fn(T) -> U,
+        U: PartialOrd + Clone,
+    {
+        self.map_parts(f, |_, _| Err(PolifunctionError::InvalidOperation))
+    }
+    
+    /// Apply a function to every value, keeping the variant
+    ///
+    /// Like [`map`](Self::map), but also maps `Set` values element by element.
+    pub fn map_with_sets<U, F>(self, f: F) -> PolifunctionValue<U>
+    where
+        F: Fn(T) -> U,
+        U: Eq + std::hash::Hash + PartialOrd + Clone,
+    {
+        let mapped = self.map_parts(f, |set, f| {
+            Ok::<_, std::convert::Infallible>(PolifunctionValue::Set(set.into_iter().map(f).collect()))
+        });
+        match mapped {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+    
+    /// Map every variant with `f`, handing `Set` values to `map_set`
+    fn map_parts<U, F, E>(self, f: F, map_set: impl FnOnce(HashSet<T>, &F) -> Result<PolifunctionValue<U>, E>)
+        -> Result<PolifunctionValue<U>, E>
+    where
+        F: Fn(T) -> U,
+        U: PartialOrd + Clone,
+    {
+        let map_interval = |interval: Interval<T>| Interval {
+            lower: f(interval.lower),
+            upper: f(interval.upper),
+            lower_inclusive: interval.lower_inclusive,
+            upper_inclusive: interval.upper_inclusive,
+        };
+        
+        Ok(match self {
+            PolifunctionValue::Single(value) => PolifunctionValue::Single(f(value)),
+            PolifunctionValue::Set(set) => return map_set(set, &f),
+            PolifunctionValue::Interval(interval) => PolifunctionValue::Interval(map_interval(interval)),
+            PolifunctionValue::Distribution(distribution) => PolifunctionValue::Distribution(ProbabilityDistribution {
+                pairs: distribution.pairs.into_iter().map(|(v, p)| (f(v), p)).collect(),
+            }),
+            PolifunctionValue::FuzzySet(fuzzy_set) => PolifunctionValue::FuzzySet(FuzzySet {
+                pairs: fuzzy_set.pairs.into_iter().map(|(v, d)| (f(v), d)).collect(),
+            }),
+            PolifunctionValue::MultiInterval(intervals) => {
+                PolifunctionValue::MultiInterval(IntervalSet::new(intervals.intervals.into_iter().map(map_interval)))
+            },
+        })
+    }
+    
+    /// Iterate over the values of a `Single` or `Set`
+    ///
+    /// Returns `None` for the other variants, whose values cannot be listed.
+    pub fn try_iter(&self) -> Option<Box<dyn Iterator<Item = &T> + '_>> {
+        match self {
+            PolifunctionValue::Single(value) => Some(Box::new(std::iter::once(value))),
+            PolifunctionValue::Set(set) => Some(Box::new(set.iter())),
+            _ => None,
+        }
+    }
+    
+    /// Get the number of values of a `Single` (1) or `Set`
+    ///
+    /// Returns `None` for the other variants.
+    pub fn cardinality(&self) -> Option<usize> {
+        match self {
+            PolifunctionValue::Single(_) => Some(1),
+            PolifunctionValue::Set(set) => Some(set.len()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Into<f64> + Clone> PolifunctionValue<T> {
+    /// Check whether two values are equal up to an absolute tolerance
+    ///
+    /// Single values, interval endpoints and the values and weights of
+    /// distributions and fuzzy sets may differ by at most `tol`; inclusivity
+    /// flags and the order of pairs must match exactly. Sets of equal size are
+    /// matched after sorting, pairing the k-th smallest elements. Values of
+    /// different variants are never approximately equal.
+    pub fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        let close = |a: f64, b: f64| a == b || (a - b).abs() <= tol;
+        let close_values = |a: &T, b: &T| close(a.clone().into(), b.clone().into());
+        let close_intervals = |a: &Interval<T>, b: &Interval<T>| {
+            close_values(&a.lower, &b.lower)
+                && close_values(&a.upper, &b.upper)
+                && a.lower_inclusive == b.lower_inclusive
+                && a.upper_inclusive == b.upper_inclusive
+        };
+        let close_pairs = |a: &[(T, f64)], b: &[(T, f64)]| {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|((v, p), (w, q))| close_values(v, w) && close(*p, *q))
+        };
+        
+        match (self, other) {
+            (PolifunctionValue::Single(a), PolifunctionValue::Single(b)) => close_values(a, b),
+            (PolifunctionValue::Set(a), PolifunctionValue::Set(b)) => {
+                if a.len() != b.len() {
+                    return false;
+                }
+                let sorted = |set: &HashSet<T>| {
+                    let mut values: Vec<f64> = set.iter().map(|v| v.clone().into()).collect();
+                    values.sort_by(|x, y| x.total_cmp(y));
+                    values
+                };
+                sorted(a).into_iter().zip(sorted(b)).all(|(x, y)| close(x, y))
+            },
+            (PolifunctionValue::Interval(a), PolifunctionValue::Interval(b)) => close_intervals(a, b),
+            (PolifunctionValue::Distribution(a), PolifunctionValue::Distribution(b)) => close_pairs(&a.pairs, &b.pairs),
+            (PolifunctionValue::FuzzySet(a), PolifunctionValue::FuzzySet(b)) => close_pairs(&a.pairs, &b.pairs),
+            (PolifunctionValue::MultiInterval(a), PolifunctionValue::MultiInterval(b)) => {
+                a.intervals.len() == b.intervals.len()
+                    && a.intervals.iter().zip(&b.intervals).all(|(x, y)| close_intervals(x, y))
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<T: Eq + std::hash::Hash + PartialOrd> PolifunctionValue<T> {
+    /// Check whether `value` is a possible output
+    ///
+    /// Values of a distribution or fuzzy set count only if their probability
+    /// or membership degree is positive.
+    pub fn contains(&self, value: &T) -> bool {
+        match self {
+            PolifunctionValue::Single(v) => v == value,
+            PolifunctionValue::Set(set) => set.contains(value),
+            PolifunctionValue::Interval(interval) => interval.contains(value),
+            PolifunctionValue::Distribution(distribution) => distribution.probability(value) > 0.0,
+            PolifunctionValue::FuzzySet(fuzzy_set) => fuzzy_set.membership(value) > 0.0,
+            PolifunctionValue::MultiInterval(intervals) => intervals.contains(value),
+        }
+    }
+}
+
+/// Values are equal if they are the same variant with equal contents
+///
+/// Values of different variants are never equal, even if they describe the
+/// same outputs (e.g. `Single(1)` and a `Set` containing only 1); use
+/// [`PolifunctionValue::normalize`] first to compare those. Sets are equal if
+/// they have the same size and each contains every element of the other; this
+/// only needs `PartialEq`, so values over f64 compare too (use `approx_eq`
+/// to allow rounding differences).
+impl<T: PartialEq> PartialEq for PolifunctionValue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PolifunctionValue::Single(a), PolifunctionValue::Single(b)) => a == b,
+            (PolifunctionValue::Set(a), PolifunctionValue::Set(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|x| b.iter().any(|y| x == y))
+                    && b.iter().all(|y| a.iter().any(|x| x == y))
+            },
+            (PolifunctionValue::Interval(a), PolifunctionValue::Interval(b)) => a == b,
+            (PolifunctionValue::Distribution(a), PolifunctionValue::Distribution(b)) => a == b,
+            (PolifunctionValue::FuzzySet(a), PolifunctionValue::FuzzySet(b)) => a == b,
+            (PolifunctionValue::MultiInterval(a), PolifunctionValue::MultiInterval(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Sets are printed in ascending order so the output does not depend on
+/// hash iteration order. Distributions and fuzzy sets keep the order of
+/// their pairs.
+impl<T: Display + PartialOrd> Display for PolifunctionValue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, |a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    }
 }
 
 /// Trait for mathematical domains
@@ -60,6 +460,20 @@ pub trait Domain {
     fn contains(&self, element: &Self::Element) -> bool;
 }
 
+/// Trait for domains whose elements can be enumerated
+///
+/// This is opt-in: implement it for user domains that are finite to enable
+/// inversion and tabulation.
+pub trait FiniteDomain: Domain {
+    /// Iterate over every element of this domain
+    fn elements(&self) -> Box<dyn Iterator<Item = Self::Element> + '_>;
+    
+    /// Get the number of elements in this domain
+    fn size(&self) -> usize {
+        self.elements().count()
+    }
+}
+
 /// Trait for mathematical codomains (ranges)
 pub trait Codomain {
     /// Type of elements in this codomain
@@ -69,6 +483,20 @@ pub trait Codomain {
     fn contains(&self, element: &Self::Element) -> bool;
 }
 
+/// Whether batch evaluation checks each input against the domain
+///
+/// `Trusted` is a promise by the caller that every input lies in the domain,
+/// for instance because the inputs were enumerated from it. It makes batch
+/// evaluation call [`PolifunctionBase::evaluate_trusted`] instead of `evaluate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrustLevel {
+    /// Check every input, reporting a `DomainError` for inputs outside the domain
+    #[default]
+    Checked,
+    /// Skip the domain check; inputs outside the domain give unspecified results
+    Trusted,
+}
+
 /// Base trait for all polifunctions
 pub trait PolifunctionBase {
     /// Type representing the domain of this polifunction
@@ -86,10 +514,90 @@ pub trait PolifunctionBase {
     
     /// Check if a given input is in the domain of this polifunction
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool;
+    
+    /// Evaluate the polifunction at an input the caller has already checked
+    /// against the domain
+    ///
+    /// Implementations may leave out the `in_domain` check, so an input
+    /// outside the domain may produce a meaningless value instead of a
+    /// `DomainError`; this never causes undefined behavior. Leaving out the
+    /// check is a contract with the caller, not a measured speedup. By
+    /// default this is the same as `evaluate`.
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        self.evaluate(input)
+    }
+    
+    /// Evaluate the polifunction at each of the given inputs
+    ///
+    /// Results are returned in input order, with failures kept in their slot.
+    fn evaluate_many<'a, I>(&self, inputs: I)
+        -> Vec<Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError>>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = &'a <Self::Domain as Domain>::Element>,
+        <Self::Domain as Domain>::Element: 'a,
+    {
+        inputs.into_iter().map(|input| self.evaluate(input)).collect()
+    }
+    
+    /// Evaluate the polifunction at every input of a slice, in order
+    ///
+    /// With `TrustLevel::Trusted` each input goes through `evaluate_trusted`.
+    /// With the `parallel` feature, [`par_evaluate_batch`](super::operations::par_evaluate_batch)
+    /// spreads a batch over several threads by calling this on chunks of it.
+    fn evaluate_batch(&self, inputs: &[<Self::Domain as Domain>::Element], trust: TrustLevel)
+        -> Vec<Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError>> {
+        match trust {
+            TrustLevel::Checked => inputs.iter().map(|input| self.evaluate(input)).collect(),
+            TrustLevel::Trusted => inputs.iter().map(|input| self.evaluate_trusted(input)).collect(),
+        }
+    }
+}
+
+/// Implement `PolifunctionBase` for pointer types by forwarding to the pointee
+///
+/// This lets a polifunction be shared by several composites through `&P`,
+/// `Box<P>` or `Arc<P>` instead of being cloned into each of them.
+macro_rules! forward_polifunction_base {
+    ($($pointer:ty),*) => {
+        $(
+            impl<P: PolifunctionBase + ?Sized> PolifunctionBase for $pointer {
+                type Domain = P::Domain;
+                type Codomain = P::Codomain;
+                
+                fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+                    (**self).evaluate(input)
+                }
+                
+                fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+                    (**self).in_domain(input)
+                }
+                
+                fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+                    (**self).evaluate_trusted(input)
+                }
+                
+                fn evaluate_batch(&self, inputs: &[<Self::Domain as Domain>::Element], trust: TrustLevel)
+                    -> Vec<Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError>> {
+                    (**self).evaluate_batch(inputs, trust)
+                }
+            }
+        )*
+    };
 }
 
+forward_polifunction_base!(&P, Box<P>, std::sync::Arc<P>);
+
 /// Continuous interval [a, b]
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature, deserialization rejects empty intervals like
+/// [`Interval::new`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "IntervalRepr<T>", bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd")))]
 pub struct Interval<T> {
     pub lower: T,
     pub upper: T,
@@ -97,57 +605,1817 @@ pub struct Interval<T> {
     pub upper_inclusive: bool,
 }
 
-/// Probability distribution over possible values
-#[derive(Debug, Clone)]
+/// Unvalidated serialized form of an [`Interval`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct IntervalRepr<T> {
+    lower: T,
+    upper: T,
+    lower_inclusive: bool,
+    upper_inclusive: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<T: PartialOrd> TryFrom<IntervalRepr<T>> for Interval<T> {
+    type Error = PolifunctionError;
+    
+    fn try_from(repr: IntervalRepr<T>) -> Result<Self, Self::Error> {
+        Interval::new(repr.lower, repr.upper, repr.lower_inclusive, repr.upper_inclusive)
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lower_inclusive { '[' } else { '(' },
+            self.lower,
+            self.upper,
+            if self.upper_inclusive { ']' } else { ')' },
+        )
+    }
+}
+
+impl<T: std::str::FromStr + PartialOrd> std::str::FromStr for Interval<T> {
+    type Err = PolifunctionError;
+    
+    /// Parse an interval in the notation produced by `Display`, e.g. `[0, 1)`
+    ///
+    /// Whitespace around the brackets, the endpoints and the comma is ignored.
+    /// Empty intervals are rejected as by [`Interval::new`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| PolifunctionError::Other(format!("Invalid interval '{}': {}", s, reason));
+        let trimmed = s.trim();
+        
+        let lower_inclusive = match trimmed.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(invalid("expected '[' or '(' at the start")),
+        };
+        let upper_inclusive = match trimmed.chars().last() {
+            Some(']') if trimmed.len() > 1 => true,
+            Some(')') if trimmed.len() > 1 => false,
+            _ => return Err(invalid("expected ']' or ')' at the end")),
+        };
+        
+        let (lower, upper) = trimmed[1..trimmed.len() - 1]
+            .split_once(',')
+            .ok_or_else(|| invalid("expected two endpoints separated by ','"))?;
+        let lower = lower.trim().parse::<T>().map_err(|_| invalid("could not parse the lower endpoint"))?;
+        let upper = upper.trim().parse::<T>().map_err(|_| invalid("could not parse the upper endpoint"))?;
+        
+        Interval::new(lower, upper, lower_inclusive, upper_inclusive)
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Sub<Output = T> + PartialOrd + Clone,
+{
+    /// Check whether both endpoints are within `epsilon` of the other interval's
+    /// and the inclusivity flags match
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        let close = |a: &T, b: &T| {
+            let distance = if a >= b { a.clone() - b.clone() } else { b.clone() - a.clone() };
+            distance <= epsilon
+        };
+        
+        self.lower_inclusive == other.lower_inclusive
+            && self.upper_inclusive == other.upper_inclusive
+            && close(&self.lower, &other.lower)
+            && close(&self.upper, &other.upper)
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8> + Clone,
+{
+    /// Get the center of the interval
+    pub fn midpoint(&self) -> T {
+        (self.lower.clone() + self.upper.clone()) / T::from(2)
+    }
+    
+    /// Get half the width of the interval
+    pub fn radius(&self) -> T {
+        (self.upper.clone() - self.lower.clone()) / T::from(2)
+    }
+}
+
+impl<T: Sub<Output = T> + Clone> Interval<T> {
+    /// Get the width of the interval (`upper - lower`)
+    pub fn width(&self) -> T {
+        self.upper.clone() - self.lower.clone()
+    }
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// Get the smallest interval containing both intervals
+    ///
+    /// On a tie the endpoint is inclusive if it is inclusive in either interval.
+    /// Returns None if the endpoints are incomparable (e.g. NaN).
+    pub fn hull(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let lower = match self.lower.partial_cmp(&other.lower)? {
+            std::cmp::Ordering::Less => (self.lower.clone(), self.lower_inclusive),
+            std::cmp::Ordering::Equal => (self.lower.clone(), self.lower_inclusive || other.lower_inclusive),
+            std::cmp::Ordering::Greater => (other.lower.clone(), other.lower_inclusive),
+        };
+        
+        let upper = match self.upper.partial_cmp(&other.upper)? {
+            std::cmp::Ordering::Greater => (self.upper.clone(), self.upper_inclusive),
+            std::cmp::Ordering::Equal => (self.upper.clone(), self.upper_inclusive || other.upper_inclusive),
+            std::cmp::Ordering::Less => (other.upper.clone(), other.upper_inclusive),
+        };
+        
+        Some(Interval {
+            lower: lower.0,
+            upper: upper.0,
+            lower_inclusive: lower.1,
+            upper_inclusive: upper.1,
+        })
+    }
+    
+    /// Get the overlap of two intervals, or None if they are disjoint
+    ///
+    /// The tighter endpoint wins; on a tie the endpoint is inclusive only if it
+    /// is inclusive in both intervals. Intervals with incomparable endpoints
+    /// (e.g. NaN) are treated as disjoint.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let lower = match self.lower.partial_cmp(&other.lower)? {
+            std::cmp::Ordering::Greater => (self.lower.clone(), self.lower_inclusive),
+            std::cmp::Ordering::Equal => (self.lower.clone(), self.lower_inclusive && other.lower_inclusive),
+            std::cmp::Ordering::Less => (other.lower.clone(), other.lower_inclusive),
+        };
+        
+        let upper = match self.upper.partial_cmp(&other.upper)? {
+            std::cmp::Ordering::Less => (self.upper.clone(), self.upper_inclusive),
+            std::cmp::Ordering::Equal => (self.upper.clone(), self.upper_inclusive && other.upper_inclusive),
+            std::cmp::Ordering::Greater => (other.upper.clone(), other.upper_inclusive),
+        };
+        
+        let intersection = Interval {
+            lower: lower.0,
+            upper: upper.0,
+            lower_inclusive: lower.1,
+            upper_inclusive: upper.1,
+        };
+        
+        if intersection.is_empty() {
+            None
+        } else {
+            Some(intersection)
+        }
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// Create a new interval, checking that it contains at least one value
+    ///
+    /// Reversed bounds, equal bounds with an exclusive endpoint and
+    /// incomparable bounds (e.g. NaN) are rejected. Building the struct
+    /// directly bypasses this check; use [`Interval::is_empty`] to test such
+    /// intervals.
+    pub fn new(lower: T, upper: T, lower_inclusive: bool, upper_inclusive: bool) -> Result<Self, PolifunctionError> {
+        let interval = Self {
+            lower,
+            upper,
+            lower_inclusive,
+            upper_inclusive,
+        };
+        
+        if interval.is_empty() {
+            return Err(PolifunctionError::Other("Invalid interval bounds: the interval is empty".to_string()));
+        }
+        
+        Ok(interval)
+    }
+    
+    /// Check whether the interval contains no values
+    ///
+    /// This is the case if `lower > upper`, or if the endpoints are equal and
+    /// either of them is exclusive.
+    pub fn is_empty(&self) -> bool {
+        match self.lower.partial_cmp(&self.upper) {
+            Some(std::cmp::Ordering::Less) => false,
+            Some(std::cmp::Ordering::Equal) => !(self.lower_inclusive && self.upper_inclusive),
+            Some(std::cmp::Ordering::Greater) => true,
+            // Incomparable endpoints (e.g. NaN) bound no values
+            None => true,
+        }
+    }
+    
+    /// Check whether the interval contains exactly one value
+    pub fn is_degenerate(&self) -> bool {
+        self.lower_inclusive && self.upper_inclusive && self.lower == self.upper
+    }
+    
+    /// Check whether a value lies in the interval, respecting inclusivity
+    ///
+    /// Values incomparable with an endpoint (e.g. NaN) are never contained.
+    pub fn contains(&self, value: &T) -> bool {
+        let above_lower = match value.partial_cmp(&self.lower) {
+            Some(std::cmp::Ordering::Greater) => true,
+            Some(std::cmp::Ordering::Equal) => self.lower_inclusive,
+            _ => false,
+        };
+        
+        let below_upper = match value.partial_cmp(&self.upper) {
+            Some(std::cmp::Ordering::Less) => true,
+            Some(std::cmp::Ordering::Equal) => self.upper_inclusive,
+            _ => false,
+        };
+        
+        above_lower && below_upper
+    }
+}
+
+/// Union of disjoint intervals
+///
+/// The intervals are kept sorted and normalized: empty intervals are dropped,
+/// and intervals that overlap or touch at an included endpoint are merged.
+/// Deserialized interval sets are normalized the same way.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "IntervalSetRepr<T>", bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd + Clone")))]
+pub struct IntervalSet<T> {
+    /// Sorted, pairwise disjoint, non-empty intervals
+    intervals: Vec<Interval<T>>,
+}
+
+/// Unnormalized serialized form of an [`IntervalSet`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd"))]
+struct IntervalSetRepr<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: PartialOrd + Clone> From<IntervalSetRepr<T>> for IntervalSet<T> {
+    fn from(repr: IntervalSetRepr<T>) -> Self {
+        IntervalSet::new(repr.intervals)
+    }
+}
+
+impl<T> IntervalSet<T> {
+    /// Get the intervals of the set, sorted and disjoint
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+    
+    /// Check whether the set contains no values
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl<T: PartialOrd> IntervalSet<T> {
+    /// Check whether a value lies in any of the intervals
+    pub fn contains(&self, value: &T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+}
+
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    /// Create a new interval set from any intervals, normalizing them
+    pub fn new(intervals: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let mut sorted: Vec<Interval<T>> = intervals.into_iter().filter(|i| !i.is_empty()).collect();
+        // Non-empty intervals have comparable endpoints; an inclusive lower
+        // endpoint sorts before an exclusive one at the same value
+        sorted.sort_by(|a, b| {
+            a.lower.partial_cmp(&b.lower)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.lower_inclusive.cmp(&a.lower_inclusive))
+        });
+        
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            if let Some(last) = merged.last_mut() {
+                let joins = match interval.lower.partial_cmp(&last.upper) {
+                    Some(std::cmp::Ordering::Less) => true,
+                    Some(std::cmp::Ordering::Equal) => last.upper_inclusive || interval.lower_inclusive,
+                    _ => false,
+                };
+                if joins {
+                    if let Some(hull) = last.hull(&interval) {
+                        *last = hull;
+                        continue;
+                    }
+                }
+            }
+            merged.push(interval);
+        }
+        
+        Self { intervals: merged }
+    }
+    
+    /// Get the values contained in either set
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet::new(self.intervals.iter().chain(other.intervals.iter()).cloned())
+    }
+    
+    /// Get the values contained in both sets
+    pub fn intersect(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet::new(
+            self.intervals
+                .iter()
+                .flat_map(|a| other.intervals.iter().filter_map(move |b| a.intersect(b)))
+        )
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Default + Clone,
+{
+    /// Get the sum of the widths of the intervals
+    pub fn total_width(&self) -> T {
+        self.intervals.iter().fold(T::default(), |total, i| total + i.width())
+    }
+}
+
+impl<T: Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.intervals.is_empty() {
+            return write!(f, "{{}}");
+        }
+        
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        Ok(())
+    }
+}
+
+/// Select the smallest and largest of the candidate endpoints
+///
+/// Each candidate carries its inclusivity flag. When several candidates attain
+/// the same extremum, it is inclusive if any of them is, since the value is
+/// then reached by at least one combination of operand endpoints.
+/// Incomparable candidates (e.g. NaN) are never selected over a comparable one,
+/// wherever they appear in the list; only if all are incomparable is the first returned.
+fn interval_extrema<T>(candidates: Vec<(T, bool)>) -> ((T, bool), (T, bool))
+where
+    T: PartialOrd + Clone,
+{
+    // Seed from the first self-comparable candidate so a leading NaN cannot
+    // block every later comparison
+    let seed = candidates.iter()
+        .position(|c| c.0.partial_cmp(&c.0).is_some())
+        .unwrap_or(0);
+    let mut lower = candidates[seed].clone();
+    let mut upper = candidates[seed].clone();
+    
+    for candidate in candidates.into_iter() {
+        match candidate.0.partial_cmp(&lower.0) {
+            Some(std::cmp::Ordering::Less) => lower = candidate.clone(),
+            Some(std::cmp::Ordering::Equal) => lower.1 = lower.1 || candidate.1,
+            _ => {},
+        }
+        match candidate.0.partial_cmp(&upper.0) {
+            Some(std::cmp::Ordering::Greater) => upper = candidate,
+            Some(std::cmp::Ordering::Equal) => upper.1 = upper.1 || candidate.1,
+            _ => {},
+        }
+    }
+    
+    (lower, upper)
+}
+
+impl<T> Add for Interval<T>
+where
+    T: Add<Output = T> + Clone,
+{
+    type Output = Interval<T>;
+    
+    /// Interval addition: [a, b] + [c, d] = [a + c, b + d]
+    ///
+    /// Each endpoint of the sum is inclusive only if both contributing endpoints are.
+    /// Operands with `lower > upper` are not meaningful intervals; adding them does
+    /// not panic, but the result is unspecified.
+    fn add(self, other: Interval<T>) -> Interval<T> {
+        Interval {
+            lower: self.lower + other.lower,
+            upper: self.upper + other.upper,
+            lower_inclusive: self.lower_inclusive && other.lower_inclusive,
+            upper_inclusive: self.upper_inclusive && other.upper_inclusive,
+        }
+    }
+}
+
+impl<T> Sub for Interval<T>
+where
+    T: Sub<Output = T> + Clone,
+{
+    type Output = Interval<T>;
+    
+    /// Interval subtraction: [a, b] - [c, d] = [a - d, b - c]
+    fn sub(self, other: Interval<T>) -> Interval<T> {
+        Interval {
+            lower: self.lower - other.upper,
+            upper: self.upper - other.lower,
+            lower_inclusive: self.lower_inclusive && other.upper_inclusive,
+            upper_inclusive: self.upper_inclusive && other.lower_inclusive,
+        }
+    }
+}
+
+impl<T> Mul for Interval<T>
+where
+    T: PartialOrd + Clone + Mul<Output = T>,
+{
+    type Output = Interval<T>;
+    
+    /// Interval multiplication: the hull of the four endpoint products
+    ///
+    /// Taking the minimum and maximum of all four products handles intervals
+    /// with negative or zero-spanning endpoints, e.g. [-2, 3] * [-1, 4] = [-8, 12].
+    /// A product endpoint is inclusive only if both endpoints that produced it
+    /// are inclusive (or, on ties, if any producing pair is).
+    fn mul(self, other: Interval<T>) -> Interval<T> {
+        let candidates = vec![
+            (self.lower.clone() * other.lower.clone(), self.lower_inclusive && other.lower_inclusive),
+            (self.lower.clone() * other.upper.clone(), self.lower_inclusive && other.upper_inclusive),
+            (self.upper.clone() * other.lower.clone(), self.upper_inclusive && other.lower_inclusive),
+            (self.upper * other.upper, self.upper_inclusive && other.upper_inclusive),
+        ];
+        
+        let (lower, upper) = interval_extrema(candidates);
+        Interval {
+            lower: lower.0,
+            upper: upper.0,
+            lower_inclusive: lower.1,
+            upper_inclusive: upper.1,
+        }
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: PartialOrd + Clone + Div<Output = T>,
+{
+    /// Interval division with an explicit zero value
+    ///
+    /// Returns the hull of the four endpoint quotients, or a ComputationError
+    /// when the divisor interval contains (or touches) `zero`.
+    pub fn checked_div(self, other: Interval<T>, zero: &T) -> Result<Interval<T>, PolifunctionError> {
+        if !(other.lower > *zero || other.upper < *zero) {
+            return Err(PolifunctionError::ComputationError);
+        }
+        
+        let candidates = vec![
+            (self.lower.clone() / other.lower.clone(), self.lower_inclusive && other.lower_inclusive),
+            (self.lower.clone() / other.upper.clone(), self.lower_inclusive && other.upper_inclusive),
+            (self.upper.clone() / other.lower.clone(), self.upper_inclusive && other.lower_inclusive),
+            (self.upper / other.upper, self.upper_inclusive && other.upper_inclusive),
+        ];
+        
+        let (lower, upper) = interval_extrema(candidates);
+        Ok(Interval {
+            lower: lower.0,
+            upper: upper.0,
+            lower_inclusive: lower.1,
+            upper_inclusive: upper.1,
+        })
+    }
+}
+
+impl<T> Div for Interval<T>
+where
+    T: PartialOrd + Clone + Default + Div<Output = T>,
+{
+    type Output = Result<Interval<T>, PolifunctionError>;
+    
+    /// Interval division: the hull of the four endpoint quotients
+    ///
+    /// `T::default()` is taken as zero. Returns a ComputationError when the
+    /// divisor interval contains (or touches) zero, since the quotient is unbounded.
+    fn div(self, other: Interval<T>) -> Result<Interval<T>, PolifunctionError> {
+        self.checked_div(other, &T::default())
+    }
+}
+
+#[cfg(feature = "num")]
+impl Interval<num_rational::Ratio<i64>> {
+    /// Create a rational interval by parsing decimal literals exactly
+    ///
+    /// Accepts an optional sign, digits and an optional fractional part
+    /// (e.g. "-0.125"). Unlike going through f64, "0.1" becomes exactly 1/10.
+    /// Empty intervals are rejected like in [`Interval::new`].
+    pub fn from_decimal_strs(lower: &str, upper: &str, lower_inclusive: bool, upper_inclusive: bool)
+        -> Result<Self, PolifunctionError> {
+        Interval::new(parse_decimal(lower)?, parse_decimal(upper)?, lower_inclusive, upper_inclusive)
+    }
+    
+    /// Convert to the tightest f64 interval that encloses this one
+    ///
+    /// The lower bound is rounded down and the upper bound rounded up, so the
+    /// result always contains every rational in the original interval.
+    pub fn to_f64_outward(&self) -> Interval<f64> {
+        Interval {
+            lower: rational_to_f64_directed(&self.lower, false),
+            upper: rational_to_f64_directed(&self.upper, true),
+            lower_inclusive: self.lower_inclusive,
+            upper_inclusive: self.upper_inclusive,
+        }
+    }
+}
+
+/// Parse a decimal literal into an exact rational
+#[cfg(feature = "num")]
+fn parse_decimal(literal: &str) -> Result<num_rational::Ratio<i64>, PolifunctionError> {
+    let invalid = || PolifunctionError::Other(format!("Invalid decimal literal '{}'", literal));
+    
+    let trimmed = literal.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    
+    let (integer_part, fraction_part) = match digits.split_once('.') {
+        Some((integer_part, fraction_part)) => (integer_part, fraction_part),
+        None => (digits, ""),
+    };
+    if integer_part.is_empty() && fraction_part.is_empty() {
+        return Err(invalid());
+    }
+    
+    let mut numerator: i64 = 0;
+    let mut denominator: i64 = 1;
+    for c in integer_part.chars().chain(fraction_part.chars()) {
+        let digit = c.to_digit(10).ok_or_else(invalid)? as i64;
+        numerator = numerator.checked_mul(10).and_then(|n| n.checked_add(digit)).ok_or_else(invalid)?;
+    }
+    for _ in 0..fraction_part.len() {
+        denominator = denominator.checked_mul(10).ok_or_else(invalid)?;
+    }
+    
+    if negative {
+        numerator = -numerator;
+    }
+    Ok(num_rational::Ratio::new(numerator, denominator))
+}
+
+/// Round a rational to an adjacent f64, downwards or upwards
+#[cfg(feature = "num")]
+fn rational_to_f64_directed(value: &num_rational::Ratio<i64>, round_up: bool) -> f64 {
+    use num_traits::FromPrimitive;
+    
+    let exact = num_rational::BigRational::from_i64(*value.numer()).unwrap()
+        / num_rational::BigRational::from_i64(*value.denom()).unwrap();
+    let as_rational = |x: f64| num_rational::BigRational::from_float(x).unwrap();
+    
+    // The naive quotient is within a couple of ulps; step until it is on the right side
+    let mut result = *value.numer() as f64 / *value.denom() as f64;
+    if round_up {
+        while as_rational(result) < exact {
+            result = next_after(result, f64::INFINITY);
+        }
+    } else {
+        while as_rational(result) > exact {
+            result = next_after(result, f64::NEG_INFINITY);
+        }
+    }
+    result
+}
+
+/// The next representable f64 after `x` in the direction of `toward`
+#[cfg(feature = "num")]
+fn next_after(x: f64, toward: f64) -> f64 {
+    if x.is_nan() || toward.is_nan() {
+        return f64::NAN;
+    }
+    if x == toward {
+        return toward;
+    }
+    if x == 0.0 {
+        let smallest = f64::from_bits(1);
+        return if toward > 0.0 { smallest } else { -smallest };
+    }
+    
+    let bits = x.to_bits();
+    let away_from_zero = (toward > x) == (x > 0.0);
+    f64::from_bits(if away_from_zero { bits + 1 } else { bits - 1 })
+}
+
+/// Tolerance used when checking that probabilities sum to one
+pub(crate) const PROBABILITY_EPSILON: f64 = 1e-9;
+
+/// Unvalidated serialized form of a [`ProbabilityDistribution`] or [`FuzzySet`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PairsRepr<T> {
+    pairs: Vec<(T, f64)>,
+}
+
+/// Discrete probability distribution over possible values
+///
+/// Distributions compare equal if they have the same pairs in the same order.
+///
+/// With the `serde` feature, deserialization checks the probabilities like
+/// [`ProbabilityDistribution::new`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PairsRepr<T>"))]
 pub struct ProbabilityDistribution<T> {
-    // Implementation details would depend on specific needs
-    // This is a placeholder
+    /// Pairs of (value, probability)
+    pairs: Vec<(T, f64)>,
+}
+
+impl<T> ProbabilityDistribution<T> {
+    /// Create a new distribution from (value, probability) pairs
+    ///
+    /// Returns a ComputationError if any probability is negative or not finite,
+    /// or if the probabilities do not sum to 1.0 within a small epsilon.
+    pub fn new(pairs: Vec<(T, f64)>) -> Result<Self, PolifunctionError> {
+        if pairs.iter().any(|(_, p)| !p.is_finite() || *p < 0.0) {
+            return Err(PolifunctionError::ComputationError);
+        }
+        
+        let total: f64 = pairs.iter().map(|(_, p)| p).sum();
+        if (total - 1.0).abs() > PROBABILITY_EPSILON {
+            return Err(PolifunctionError::ComputationError);
+        }
+        
+        Ok(Self { pairs })
+    }
+    
+    /// Get the (value, probability) pairs of the distribution
+    pub fn pairs(&self) -> &[(T, f64)] {
+        &self.pairs
+    }
+    
+    /// Iterate over the values with a stored probability
+    pub fn support(&self) -> impl Iterator<Item = &T> {
+        self.pairs.iter().map(|(value, _)| value)
+    }
+    
+    /// Rescale the probabilities so that they sum to 1.0
+    ///
+    /// A distribution with zero total mass is left unchanged.
+    pub fn normalize(&mut self) {
+        let total: f64 = self.pairs.iter().map(|(_, p)| p).sum();
+        if total == 0.0 {
+            return;
+        }
+        
+        for (_, p) in self.pairs.iter_mut() {
+            *p /= total;
+        }
+    }
+}
+
+impl<T: PartialEq> ProbabilityDistribution<T> {
+    /// Get the probability of the given value (0.0 if it is not in the support)
+    pub fn probability(&self, value: &T) -> f64 {
+        self.pairs
+            .iter()
+            .filter(|(v, _)| v == value)
+            .map(|(_, p)| p)
+            .sum()
+    }
+}
+
+impl<T: Add<Output = T> + Clone + Eq + std::hash::Hash> ProbabilityDistribution<T> {
+    /// Get the distribution of the sum of two independent values drawn from the distributions
+    ///
+    /// Every pair of values is added and the product of their probabilities is
+    /// accumulated into the sum. Values appear in the order their sum first
+    /// occurs.
+    pub fn convolve(&self, other: &Self) -> Self {
+        let mut index: HashMap<T, usize> = HashMap::new();
+        let mut pairs: Vec<(T, f64)> = Vec::new();
+        for (a, p) in &self.pairs {
+            for (b, q) in &other.pairs {
+                let sum = a.clone() + b.clone();
+                match index.get(&sum) {
+                    Some(&i) => pairs[i].1 += p * q,
+                    None => {
+                        index.insert(sum.clone(), pairs.len());
+                        pairs.push((sum, p * q));
+                    }
+                }
+            }
+        }
+        
+        Self { pairs }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T> ProbabilityDistribution<T> {
+    /// Draw a value according to the probabilities by inverse-CDF selection
+    ///
+    /// Returns `EmptyResult` if the distribution has no value with a positive
+    /// probability.
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<&T, PolifunctionError> {
+        let u: f64 = rng.gen();
+        let mut cumulative = 0.0;
+        for (value, p) in &self.pairs {
+            cumulative += p;
+            if u < cumulative {
+                return Ok(value);
+            }
+        }
+        
+        // Rounding can leave the cumulative sum just below u
+        self.pairs
+            .iter()
+            .rev()
+            .find(|(_, p)| *p > 0.0)
+            .map(|(value, _)| value)
+            .ok_or(PolifunctionError::EmptyResult)
+    }
+    
+    /// Draw `n` independent values according to the probabilities
+    pub fn sample_n<R: rand::Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Result<Vec<&T>, PolifunctionError> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+impl<T: Into<f64> + Clone> ProbabilityDistribution<T> {
+    /// Get the probability-weighted mean of the values
+    ///
+    /// Returns a ComputationError if the distribution is empty.
+    pub fn expectation(&self) -> Result<f64, PolifunctionError> {
+        if self.pairs.is_empty() {
+            return Err(PolifunctionError::ComputationError);
+        }
+        
+        Ok(self.pairs.iter().map(|(v, p)| v.clone().into() * p).sum())
+    }
+    
+    /// Get the variance (second central moment) of the values
+    ///
+    /// Returns a ComputationError if the distribution is empty.
+    pub fn variance(&self) -> Result<f64, PolifunctionError> {
+        let mean = self.expectation()?;
+        
+        Ok(self.pairs.iter().map(|(v, p)| (v.clone().into() - mean).powi(2) * p).sum())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<PairsRepr<T>> for ProbabilityDistribution<T> {
+    type Error = PolifunctionError;
+    
+    fn try_from(repr: PairsRepr<T>) -> Result<Self, Self::Error> {
+        ProbabilityDistribution::new(repr.pairs).map_err(|_| {
+            PolifunctionError::Other("Invalid distribution: probabilities must be non-negative and sum to 1".to_string())
+        })
+    }
 }
 
 /// Fuzzy set with membership degrees
-#[derive(Debug, Clone)]
+///
+/// Fuzzy sets compare equal if they have the same pairs in the same order.
+///
+/// With the `serde` feature, deserialization checks the degrees like
+/// [`FuzzySet::new`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PairsRepr<T>"))]
 pub struct FuzzySet<T> {
-    // Implementation details would depend on specific needs
-    // This is a placeholder
+    /// Pairs of (element, membership degree in [0.0, 1.0])
+    pairs: Vec<(T, f64)>,
+}
+
+impl<T> FuzzySet<T> {
+    /// Create a new fuzzy set from (element, membership degree) pairs
+    ///
+    /// Returns an error if any degree lies outside [0.0, 1.0].
+    pub fn new(pairs: Vec<(T, f64)>) -> Result<Self, PolifunctionError> {
+        if let Some((_, degree)) = pairs.iter().find(|(_, d)| !(0.0..=1.0).contains(d)) {
+            return Err(PolifunctionError::Other(format!("Membership degree {} is outside [0, 1]", degree)));
+        }
+        
+        Ok(Self { pairs })
+    }
+    
+    /// Get the (element, membership degree) pairs of the set
+    pub fn pairs(&self) -> &[(T, f64)] {
+        &self.pairs
+    }
+    
+    /// Get the largest membership degree in the set (0.0 for an empty set)
+    pub fn height(&self) -> f64 {
+        self.pairs.iter().map(|(_, d)| *d).fold(0.0, f64::max)
+    }
+}
+
+impl<T: PartialEq> FuzzySet<T> {
+    /// Get the membership degree of the given element (0.0 if it is absent)
+    pub fn membership(&self, value: &T) -> f64 {
+        self.pairs
+            .iter()
+            .filter(|(v, _)| v == value)
+            .map(|(_, d)| *d)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<PairsRepr<T>> for FuzzySet<T> {
+    type Error = PolifunctionError;
+    
+    fn try_from(repr: PairsRepr<T>) -> Result<Self, Self::Error> {
+        FuzzySet::new(repr.pairs)
+    }
+}
+
+impl<T: Clone + Eq + std::hash::Hash> FuzzySet<T> {
+    /// Get the crisp set of elements whose membership degree is at least `alpha`
+    pub fn alpha_cut(&self, alpha: f64) -> HashSet<T> {
+        self.pairs
+            .iter()
+            .filter(|(_, d)| *d >= alpha)
+            .map(|(v, _)| v.clone())
+            .collect()
+    }
+    
+    /// Intersect two fuzzy sets using the minimum T-norm
+    ///
+    /// Elements in only one of the sets have degree 0 in the other, so only
+    /// elements of both sets are kept.
+    pub fn intersect(&self, other: &FuzzySet<T>) -> FuzzySet<T> {
+        let theirs = other.degrees();
+        let pairs = self.degrees_in_order()
+            .into_iter()
+            .filter_map(|(v, d)| theirs.get(&v).map(|e| (v, d.min(*e))))
+            .collect();
+        FuzzySet { pairs }
+    }
+    
+    /// Unite two fuzzy sets using the maximum S-norm
+    ///
+    /// Elements in only one of the sets keep their degree.
+    pub fn union(&self, other: &FuzzySet<T>) -> FuzzySet<T> {
+        let mut degrees = self.degrees();
+        let mut pairs = self.degrees_in_order();
+        for (v, d) in other.degrees_in_order() {
+            match degrees.get_mut(&v) {
+                Some(existing) => *existing = existing.max(d),
+                None => {
+                    degrees.insert(v.clone(), d);
+                    pairs.push((v, d));
+                },
+            }
+        }
+        
+        for (v, d) in pairs.iter_mut() {
+            *d = degrees[v];
+        }
+        FuzzySet { pairs }
+    }
+    
+    /// Membership degree of every element, taking the largest for repeated elements
+    fn degrees(&self) -> HashMap<T, f64> {
+        let mut degrees = HashMap::new();
+        for (v, d) in &self.pairs {
+            let entry = degrees.entry(v.clone()).or_insert(*d);
+            *entry = entry.max(*d);
+        }
+        degrees
+    }
+    
+    /// Elements with their membership degree, without repeats, in first-seen order
+    fn degrees_in_order(&self) -> Vec<(T, f64)> {
+        let degrees = self.degrees();
+        let mut seen = HashSet::new();
+        self.pairs
+            .iter()
+            .filter(|(v, _)| seen.insert(v.clone()))
+            .map(|(v, _)| (v.clone(), degrees[v]))
+            .collect()
+    }
+}
+
+impl<T: Into<f64> + Clone> FuzzySet<T> {
+    /// Defuzzify the set to the membership-weighted mean of its elements
+    ///
+    /// Returns a ComputationError if the total membership is zero.
+    pub fn centroid(&self) -> Result<f64, PolifunctionError> {
+        let total: f64 = self.pairs.iter().map(|(_, d)| d).sum();
+        if total == 0.0 {
+            return Err(PolifunctionError::ComputationError);
+        }
+        
+        let weighted: f64 = self.pairs.iter().map(|(v, d)| v.clone().into() * d).sum();
+        Ok(weighted / total)
+    }
 }
 
 /// Trait for composable polifunctions
-pub trait Composable: PolifunctionBase {
+///
+/// ```
+/// use polifunctions_sdk::core::interfaces::domains::Unbounded;
+/// use polifunctions_sdk::core::interfaces::operations::LiftedPolifunction;
+/// use polifunctions_sdk::core::interfaces::polifunction::{Composable, PolifunctionBase, PolifunctionError};
+///
+/// let reals = Unbounded::<f64>::new;
+/// let double = LiftedPolifunction::new(|x: &f64| Ok(2.0 * x), reals(), reals());
+/// let shift = LiftedPolifunction::new(|x: &f64| Ok(x + 0.5), reals(), reals());
+///
+/// // shift(double(x)) and double(shift(x)), borrowing both polifunctions
+/// let double_then_shift = (&shift).compose(&double)?;
+/// let shift_then_double = (&double).compose(&shift)?;
+/// assert_eq!(double_then_shift.evaluate(&3.0)?.as_single(), Some(&6.5));
+/// assert_eq!(shift_then_double.evaluate(&3.0)?.as_single(), Some(&7.0));
+///
+/// // or taking ownership of them
+/// let composed = shift.compose(double)?;
+/// assert_eq!(composed.evaluate(&1.0)?.as_single(), Some(&2.5));
+/// # Ok::<(), PolifunctionError>(())
+/// ```
+pub trait Composable: PolifunctionBase + Sized {
     /// Compose this polifunction with another
-    fn compose<P>(&self, other: &P) -> Result<ComposedPolifunction<Self, P>, PolifunctionError>
+    ///
+    /// The result applies `other` first and then `self`. Pass references to
+    /// keep using the polifunctions afterwards. A non-empty Set intermediate
+    /// value is an `InvalidOperation`; see [`Composable::compose_sets`].
+    fn compose<P>(self, other: P) -> Result<ComposedPolifunction<Self, P>, PolifunctionError>
+    where
+        P: PolifunctionBase,
+        <P::Codomain as Codomain>::Element: Into<<Self::Domain as Domain>::Element>;
+    
+    /// Compose this polifunction with another, mapping Set intermediate values element by element
+    ///
+    /// The results are united into a Set (see [`ComposedPolifunction::with_sets`]),
+    /// which needs `Hash + Eq` output elements.
+    fn compose_sets<P>(self, other: P) -> Result<ComposedPolifunction<Self, P, HashSets>, PolifunctionError>
+    where
+        P: PolifunctionBase,
+        <P::Codomain as Codomain>::Element: Into<<Self::Domain as Domain>::Element>,
+        <Self::Codomain as Codomain>::Element: std::hash::Hash + Eq;
+}
+
+impl<T: PolifunctionBase> Composable for T {
+    fn compose<P>(self, other: P) -> Result<ComposedPolifunction<Self, P>, PolifunctionError>
     where
         P: PolifunctionBase,
         <P::Codomain as Codomain>::Element: Into<<Self::Domain as Domain>::Element>,
-        Self: Sized;
+    {
+        Ok(ComposedPolifunction::new(self, other))
+    }
+    
+    fn compose_sets<P>(self, other: P) -> Result<ComposedPolifunction<Self, P, HashSets>, PolifunctionError>
+    where
+        P: PolifunctionBase,
+        <P::Codomain as Codomain>::Element: Into<<Self::Domain as Domain>::Element>,
+        <Self::Codomain as Codomain>::Element: std::hash::Hash + Eq,
+    {
+        Ok(ComposedPolifunction::new(self, other).with_sets())
+    }
+}
+
+/// How a combinator collects computed elements into a `Set` value
+///
+/// Combinators take this as a type parameter: [`HashSets`] for `Hash + Eq`
+/// elements and [`NoSets`] for element types that cannot be collected into a
+/// set, such as f64. The choice is made by the constructor and shows in the type.
+pub trait SetSupport<T> {
+    /// Collect elements into a set
+    fn collect_set(values: Vec<T>) -> Result<HashSet<T>, PolifunctionError>;
+}
+
+/// Set support for `Hash + Eq` elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashSets;
+
+impl<T: std::hash::Hash + Eq> SetSupport<T> for HashSets {
+    fn collect_set(values: Vec<T>) -> Result<HashSet<T>, PolifunctionError> {
+        Ok(values.into_iter().collect())
+    }
+}
+
+/// No set support, for elements without `Hash + Eq`
+///
+/// Only the empty set can be built; any other `Set` result is an
+/// `InvalidOperation`. Values of such element types never form non-empty sets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoSets;
+
+impl<T> SetSupport<T> for NoSets {
+    fn collect_set(values: Vec<T>) -> Result<HashSet<T>, PolifunctionError> {
+        if values.is_empty() {
+            Ok(HashSet::new())
+        } else {
+            Err(PolifunctionError::InvalidOperation)
+        }
+    }
 }
 
 /// Result of composing two polifunctions
-pub struct ComposedPolifunction<P1, P2>
+///
+/// Set intermediate values are mapped through `p1` and merged according to
+/// the [`SetSupport`] `S`. `new` builds a composition with [`NoSets`], so that
+/// it also works for element types without `Hash + Eq` (such as f64);
+/// [`ComposedPolifunction::with_sets`] switches to [`HashSets`].
+pub struct ComposedPolifunction<P1, P2, S = NoSets>
 where
     P1: PolifunctionBase,
     P2: PolifunctionBase,
 {
     p1: P1,
     p2: P2,
+    /// Whether Set elements outside the domain of `p1` are dropped instead of failing
+    skip_outside_domain: bool,
+    sets: PhantomData<S>,
 }
 
-impl<P1, P2> PolifunctionBase for ComposedPolifunction<P1, P2>
+impl<P1, P2> ComposedPolifunction<P1, P2>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase,
+{
+    /// Create the composition of two polifunctions, applying `p2` first and then `p1`
+    pub fn new(p1: P1, p2: P2) -> Self {
+        Self {
+            p1,
+            p2,
+            skip_outside_domain: false,
+            sets: PhantomData,
+        }
+    }
+    
+    /// Map Set intermediate values through `p1` element by element and unite the results
+    pub fn with_sets(self) -> ComposedPolifunction<P1, P2, HashSets>
+    where
+        <P1::Codomain as Codomain>::Element: std::hash::Hash + Eq,
+    {
+        ComposedPolifunction {
+            p1: self.p1,
+            p2: self.p2,
+            skip_outside_domain: self.skip_outside_domain,
+            sets: PhantomData,
+        }
+    }
+}
+
+impl<P1, P2, S> ComposedPolifunction<P1, P2, S>
+where
+    P1: PolifunctionBase,
+    P2: PolifunctionBase,
+{
+    
+    /// Drop elements of a Set intermediate value that fall outside the domain of `p1`
+    ///
+    /// By default such an element makes the whole evaluation fail with a DomainError.
+    pub fn skip_outside_domain(mut self) -> Self {
+        self.skip_outside_domain = true;
+        self
+    }
+}
+
+impl<P1, P2, S> PolifunctionBase for ComposedPolifunction<P1, P2, S>
 where
     P1: PolifunctionBase,
     P2: PolifunctionBase,
     <P2::Codomain as Codomain>::Element: Into<<P1::Domain as Domain>::Element>,
+    <P1::Domain as Domain>::Element: std::fmt::Debug,
+    <P2::Domain as Domain>::Element: std::fmt::Debug,
+    S: SetSupport<<P1::Codomain as Codomain>::Element>,
 {
     type Domain = P2::Domain;
     type Codomain = P1::Codomain;
     
+    /// Evaluate `p1(p2(input))`
+    ///
+    /// A Single intermediate value is fed into `p1` and must lie in its domain.
+    /// A Set intermediate value is mapped through `p1` element by element and the
+    /// results are united into a Set, which needs `with_sets` unless it is empty. Other
+    /// intermediate values are not supported. Errors from either function are
+    /// wrapped with the function they came from.
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element) 
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
-        // This would contain the actual implementation for function composition
-        // For now, we just return an error as a placeholder
-        Err(PolifunctionError::Other("ComposedPolifunction evaluation not implemented".to_string()))
+        if !self.p2.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
+        }
+        
+        match self.p2.evaluate(input).map_err(|e| e.context("inner function of composition"))? {
+            PolifunctionValue::Single(v) => {
+                let p1_input = v.into();
+                if !self.p1.in_domain(&p1_input) {
+                    // The intermediate value falls outside the outer function's domain
+                    return Err(PolifunctionError::domain_error_at(&p1_input).context("outer function of composition"));
+                }
+                self.p1.evaluate(&p1_input).map_err(|e| e.context("outer function of composition"))
+            },
+            PolifunctionValue::Set(set) => {
+                let mut results = Vec::new();
+                for v in set {
+                    let p1_input = v.into();
+                    if !self.p1.in_domain(&p1_input) {
+                        if self.skip_outside_domain {
+                            continue;
+                        }
+                        return Err(PolifunctionError::domain_error_at(&p1_input).context("outer function of composition"));
+                    }
+                    match self.p1.evaluate(&p1_input).map_err(|e| e.context("outer function of composition"))? {
+                        PolifunctionValue::Single(r) => results.push(r),
+                        PolifunctionValue::Set(rs) => results.extend(rs),
+                        _ => return Err(PolifunctionError::InvalidOperation),
+                    }
+                }
+                Ok(PolifunctionValue::Set(S::collect_set(results)?))
+            },
+            // Composing through intervals or distributions needs more structure on p1
+            _ => Err(PolifunctionError::InvalidOperation),
+        }
     }
     
+    /// Check that `p2` accepts the input and that the intermediate value lands
+    /// in the domain of `p1`
+    ///
+    /// For a Set intermediate value every element must land in the domain of
+    /// `p1`, unless elements outside it are skipped. This evaluates
+    /// `p2`, so it is as expensive as the inner polifunction. Errors other than
+    /// domain errors leave the input in the domain, so that `evaluate` reports
+    /// them instead of a bare DomainError.
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
-        self.p2.in_domain(input)
+        if !self.p2.in_domain(input) {
+            return false;
+        }
+        
+        match self.p2.evaluate(input) {
+            Ok(PolifunctionValue::Single(v)) => self.p1.in_domain(&v.into()),
+            Ok(PolifunctionValue::Set(set)) => {
+                self.skip_outside_domain || set.into_iter().all(|v| self.p1.in_domain(&v.into()))
+            },
+            Ok(_) => true,
+            Err(e) => !e.is_domain_error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::{IntegerRange, RealInterval, Unbounded};
+    use crate::core::interfaces::operations::LiftedPolifunction;
+    use crate::core::interfaces::set_valued::BasicSetValuedPolifunction;
+    
+    fn closed(lower: f64, upper: f64) -> Interval<f64> {
+        Interval::new(lower, upper, true, true).unwrap()
+    }
+    
+    fn closed_i(lower: i64, upper: i64) -> Interval<i64> {
+        Interval::new(lower, upper, true, true).unwrap()
+    }
+    
+    #[test]
+    fn normalize_collapses_singleton_sets_and_degenerate_closed_intervals() {
+        let single = PolifunctionValue::Set([4].into_iter().collect()).normalize();
+        assert_eq!(single, PolifunctionValue::Single(4));
+        assert_eq!(PolifunctionValue::Interval(closed_i(2, 2)).normalize(), PolifunctionValue::Single(2));
+        
+        // Wider intervals and larger sets are left alone
+        let half_open = Interval::new(2, 3, true, false).unwrap();
+        assert_eq!(PolifunctionValue::Interval(half_open.clone()).normalize(), PolifunctionValue::Interval(half_open));
+        assert_eq!(PolifunctionValue::Interval(closed_i(1, 2)).normalize(), PolifunctionValue::Interval(closed_i(1, 2)));
+        let pair: HashSet<i64> = [1, 2].into_iter().collect();
+        assert_eq!(PolifunctionValue::Set(pair.clone()).normalize(), PolifunctionValue::Set(pair));
+        assert_eq!(PolifunctionValue::<i64>::Set(HashSet::new()).normalize(), PolifunctionValue::Set(HashSet::new()));
+    }
+    
+    #[test]
+    fn only_empty_sets_are_empty_values() {
+        assert!(PolifunctionValue::<i64>::Set(HashSet::new()).is_empty());
+        assert!(!PolifunctionValue::Set([0].into_iter().collect::<HashSet<i64>>()).is_empty());
+        assert!(!PolifunctionValue::Single(0).is_empty());
+        assert!(!PolifunctionValue::Interval(closed_i(0, 1)).is_empty());
+    }
+    
+    #[test]
+    fn interval_contains_respects_each_endpoint() {
+        for (lower_inclusive, upper_inclusive) in [(true, true), (true, false), (false, true), (false, false)] {
+            let interval = Interval::new(1.0, 3.0, lower_inclusive, upper_inclusive).unwrap();
+            assert_eq!(interval.contains(&1.0), lower_inclusive);
+            assert_eq!(interval.contains(&3.0), upper_inclusive);
+            assert!(interval.contains(&2.0) && interval.contains(&2.999));
+            assert!(!interval.contains(&0.5) && !interval.contains(&3.5) && !interval.contains(&f64::NAN));
+        }
+    }
+    
+    #[test]
+    fn interval_hull_keeps_the_looser_endpoint() {
+        let open = Interval::new(0.0, 2.0, false, false).unwrap();
+        let closed_right = Interval::new(1.0, 2.0, true, true).unwrap();
+        assert_eq!(open.hull(&closed_right), Interval::new(0.0, 2.0, false, true).ok());
+        assert_eq!(closed_right.hull(&open), open.hull(&closed_right));
+        
+        // Disjoint intervals are spanned, keeping the outer endpoints' inclusivity
+        let left = Interval::new(-3.0, -2.0, true, false).unwrap();
+        assert_eq!(left.hull(&open), Interval::new(-3.0, 2.0, true, false).ok());
+        
+        let nan = Interval { lower: f64::NAN, upper: 1.0, lower_inclusive: true, upper_inclusive: true };
+        assert_eq!(open.hull(&nan), None);
+    }
+    
+    #[test]
+    fn interval_width_midpoint_and_degeneracy() {
+        for (lower_inclusive, upper_inclusive) in [(true, true), (true, false), (false, true), (false, false)] {
+            let interval = Interval::new(-1.0, 3.0, lower_inclusive, upper_inclusive).unwrap();
+            assert_eq!((interval.width(), interval.midpoint(), interval.radius()), (4.0, 1.0, 2.0));
+            assert!(!interval.is_degenerate());
+        }
+        
+        let point = closed(2.0, 2.0);
+        assert!(point.is_degenerate());
+        assert_eq!((point.width(), point.midpoint()), (0.0, 2.0));
+        
+        // Equal endpoints with an open end hold no value at all
+        let hollow = Interval { lower: 2.0, upper: 2.0, lower_inclusive: true, upper_inclusive: false };
+        assert!(!hollow.is_degenerate() && hollow.is_empty());
+        assert!(!hollow.contains(&2.0));
+    }
+    
+    #[test]
+    fn interval_mul_handles_signs() {
+        let product = closed(-2.0, 3.0) * closed(-1.0, 4.0);
+        assert_eq!((product.lower, product.upper), (-8.0, 12.0));
+        
+        let product = closed(-3.0, -1.0) * closed(-4.0, -2.0);
+        assert_eq!((product.lower, product.upper), (2.0, 12.0));
+        
+        let product = closed(-3.0, -1.0) * closed(2.0, 4.0);
+        assert_eq!((product.lower, product.upper), (-12.0, -2.0));
+    }
+    
+    #[test]
+    fn interval_mul_inclusivity_follows_endpoints() {
+        let half_open = Interval::new(1.0, 2.0, true, false).unwrap();
+        let product = half_open * closed(3.0, 4.0);
+        assert!(product.lower_inclusive);
+        assert!(!product.upper_inclusive);
+    }
+    
+    #[test]
+    fn interval_div_by_interval_containing_zero_fails() {
+        assert_eq!(closed(1.0, 2.0) / closed(-1.0, 1.0), Err(PolifunctionError::ComputationError));
+        assert_eq!(closed(1.0, 2.0) / closed(0.0, 1.0), Err(PolifunctionError::ComputationError));
+        assert_eq!(closed_i(1, 2).checked_div(closed_i(-3, -1), &0).map(|i| (i.lower, i.upper)), Ok((-2, 0)));
+    }
+    
+    #[test]
+    fn interval_div_handles_signs() {
+        let quotient = (closed(-4.0, 2.0) / closed(-2.0, -1.0)).unwrap();
+        assert_eq!((quotient.lower, quotient.upper), (-2.0, 4.0));
+    }
+    
+    #[test]
+    fn interval_extrema_skips_leading_nan() {
+        let (lower, upper) = interval_extrema(vec![
+            (f64::NAN, true),
+            (3.0, false),
+            (-1.0, true),
+            (f64::NAN, true),
+        ]);
+        assert_eq!(lower, (-1.0, true));
+        assert_eq!(upper, (3.0, false));
+        
+        // 0 * inf is NaN and comes first among the candidates
+        let product = closed(0.0, 1.0) * closed(f64::INFINITY, f64::INFINITY);
+        assert!(!product.lower.is_nan() && !product.upper.is_nan());
+    }
+    
+    #[test]
+    fn distribution_rejects_invalid_probabilities() {
+        assert_eq!(ProbabilityDistribution::new(vec![(1, 0.5), (2, 0.4)]).unwrap_err(), PolifunctionError::ComputationError);
+        assert_eq!(ProbabilityDistribution::new(vec![(1, 0.5), (2, 0.6)]).unwrap_err(), PolifunctionError::ComputationError);
+        assert_eq!(ProbabilityDistribution::new(vec![(1, 1.5), (2, -0.5)]).unwrap_err(), PolifunctionError::ComputationError);
+        assert_eq!(ProbabilityDistribution::new(vec![(1, f64::NAN), (2, 1.0)]).unwrap_err(), PolifunctionError::ComputationError);
+        assert_eq!(ProbabilityDistribution::<i32>::new(Vec::new()).unwrap_err(), PolifunctionError::ComputationError);
+        
+        // Rounding within the epsilon is accepted
+        let distribution = ProbabilityDistribution::new(vec![(1, 0.1), (2, 0.2), (3, 0.7)]).unwrap();
+        assert_eq!(distribution.support().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+    
+    #[test]
+    fn distribution_normalize_rescales_mass() {
+        let mut distribution = ProbabilityDistribution { pairs: vec![(1, 1.0), (2, 3.0)] };
+        distribution.normalize();
+        assert_eq!(distribution.pairs(), &[(1, 0.25), (2, 0.75)]);
+        
+        let mut massless = ProbabilityDistribution { pairs: vec![(1, 0.0), (2, 0.0)] };
+        massless.normalize();
+        assert_eq!(massless.pairs(), &[(1, 0.0), (2, 0.0)]);
+    }
+    
+    #[test]
+    fn distribution_probability_sums_repeated_values() {
+        let distribution = ProbabilityDistribution::new(vec![(1, 0.25), (2, 0.5), (1, 0.25)]).unwrap();
+        assert_eq!(distribution.probability(&1), 0.5);
+        assert_eq!(distribution.probability(&2), 0.5);
+        assert_eq!(distribution.probability(&3), 0.0);
+    }
+    
+    #[test]
+    fn distribution_moments() {
+        let coin = ProbabilityDistribution::new(vec![(0, 0.5), (10, 0.5)]).unwrap();
+        assert_eq!(coin.expectation(), Ok(5.0));
+        assert_eq!(coin.variance(), Ok(25.0));
+        
+        let certain = ProbabilityDistribution::new(vec![(3, 1.0)]).unwrap();
+        assert_eq!(certain.expectation(), Ok(3.0));
+        assert_eq!(certain.variance(), Ok(0.0));
+        
+        let empty = ProbabilityDistribution::<i32> { pairs: Vec::new() };
+        assert_eq!(empty.support().count(), 0);
+        assert_eq!(empty.probability(&0), 0.0);
+        assert_eq!(empty.expectation(), Err(PolifunctionError::ComputationError));
+        assert_eq!(empty.variance(), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn distribution_convolution_of_small_distributions() {
+        let coin = ProbabilityDistribution::new(vec![(0, 0.5), (1, 0.5)]).unwrap();
+        let skewed = ProbabilityDistribution::new(vec![(0, 0.25), (1, 0.75)]).unwrap();
+        
+        let sum = coin.convolve(&skewed);
+        assert_eq!(sum.pairs(), &[(0, 0.125), (1, 0.5), (2, 0.375)]);
+        assert_eq!(sum.expectation(), Ok(coin.expectation().unwrap() + skewed.expectation().unwrap()));
+        
+        let two_coins = coin.convolve(&coin);
+        assert_eq!(two_coins.pairs(), &[(0, 0.25), (1, 0.5), (2, 0.25)]);
+        assert_eq!(two_coins.variance(), Ok(0.5));
+    }
+    
+    #[test]
+    fn fuzzy_set_rejects_degrees_outside_unit_interval() {
+        assert!(FuzzySet::new(vec![(1, 1.5)]).is_err());
+        assert!(FuzzySet::new(vec![(1, 0.5), (2, -0.1)]).is_err());
+        assert!(FuzzySet::new(vec![(1, f64::NAN)]).is_err());
+        assert!(FuzzySet::new(vec![(1, 0.0), (2, 1.0)]).is_ok());
+    }
+    
+    #[test]
+    fn fuzzy_set_membership_and_height() {
+        let set = FuzzySet::new(vec![(1, 0.2), (2, 0.9), (1, 0.6)]).unwrap();
+        assert_eq!(set.membership(&1), 0.6);
+        assert_eq!(set.membership(&2), 0.9);
+        assert_eq!(set.membership(&3), 0.0);
+        assert_eq!(set.height(), 0.9);
+        assert_eq!(FuzzySet::<i32>::new(Vec::new()).unwrap().height(), 0.0);
+    }
+    
+    #[test]
+    fn fuzzy_set_alpha_cut_includes_threshold() {
+        let set = FuzzySet::new(vec![(1, 0.2), (2, 0.5), (3, 1.0)]).unwrap();
+        assert_eq!(set.alpha_cut(0.5), [2, 3].into_iter().collect());
+        assert_eq!(set.alpha_cut(0.50001), [3].into_iter().collect());
+        assert_eq!(set.alpha_cut(0.0), [1, 2, 3].into_iter().collect());
+        assert!(set.alpha_cut(1.5).is_empty());
+    }
+    
+    #[test]
+    fn fuzzy_set_intersection_and_union() {
+        let a = FuzzySet::new(vec![(1, 0.3), (2, 0.8)]).unwrap();
+        let b = FuzzySet::new(vec![(2, 0.5), (3, 0.4), (1, 0.9)]).unwrap();
+        
+        assert_eq!(a.intersect(&b).pairs(), &[(1, 0.3), (2, 0.5)]);
+        assert_eq!(a.union(&b).pairs(), &[(1, 0.9), (2, 0.8), (3, 0.4)]);
+        
+        // Repeated elements take their largest degree
+        let repeated = FuzzySet::new(vec![(1, 0.1), (1, 0.7)]).unwrap();
+        assert_eq!(repeated.union(&a).pairs(), &[(1, 0.7), (2, 0.8)]);
+        assert_eq!(repeated.intersect(&b).pairs(), &[(1, 0.7)]);
+    }
+    
+    #[test]
+    fn fuzzy_set_centroid() {
+        let set = FuzzySet::new(vec![(0, 0.5), (10, 1.0)]).unwrap();
+        let centroid = set.centroid().unwrap();
+        assert!((centroid - 20.0 / 3.0).abs() < 1e-12);
+        
+        let all_zero = FuzzySet::new(vec![(1, 0.0), (2, 0.0)]).unwrap();
+        assert_eq!(all_zero.centroid(), Err(PolifunctionError::ComputationError));
+        assert_eq!(FuzzySet::<i32>::new(Vec::new()).unwrap().centroid(), Err(PolifunctionError::ComputationError));
+    }
+    
+    #[test]
+    fn composition_of_real_singles() {
+        let double = LiftedPolifunction::new(|x: &f64| Ok(2.0 * x), Unbounded::<f64>::new(), Unbounded::<f64>::new());
+        let root = LiftedPolifunction::new(|x: &f64| Ok(x.sqrt()), RealInterval::closed(0.0, 100.0), Unbounded::<f64>::new());
+        let composed = ComposedPolifunction::new(root, double);
+        
+        assert_eq!(composed.evaluate(&8.0).unwrap().as_single(), Some(&4.0));
+        
+        // -1 doubles to -2, outside the domain of the square root
+        let error = composed.evaluate(&-1.0).unwrap_err();
+        assert!(error.is_domain_error());
+        assert_eq!(error.to_string(), "outer function of composition: Input -2.0 is outside the function's domain");
+        assert!(!composed.in_domain(&-1.0));
+    }
+    
+    fn plus_minus() -> BasicSetValuedPolifunction<IntegerRange, Unbounded<i64>> {
+        BasicSetValuedPolifunction::new(
+            |x: &i64| Ok([*x, -*x].into_iter().collect()),
+            IntegerRange::new(-10, 10).unwrap(),
+            Unbounded::new(),
+        )
+    }
+    
+    fn square_naturals() -> LiftedPolifunction<impl Fn(&i64) -> Result<i64, PolifunctionError>, IntegerRange, Unbounded<i64>> {
+        LiftedPolifunction::new(|x: &i64| Ok(x * x), IntegerRange::new(0, 10).unwrap(), Unbounded::new())
+    }
+    
+    #[test]
+    fn composition_through_sets_needs_with_sets() {
+        let composed = ComposedPolifunction::new(square_naturals(), plus_minus());
+        assert_eq!(composed.evaluate(&0).unwrap_err(), PolifunctionError::InvalidOperation);
+        
+        let composed = composed.with_sets();
+        assert_eq!(composed.evaluate(&0).unwrap().as_set(), Some(&[0].into_iter().collect()));
+        
+        // -2 is outside the domain of the outer function
+        let error = composed.evaluate(&2).unwrap_err();
+        assert!(error.is_domain_error());
+        assert!(error.to_string().starts_with("outer function of composition"));
+    }
+    
+    #[test]
+    fn composition_domain_agrees_with_evaluation() {
+        let composed = ComposedPolifunction::new(square_naturals(), plus_minus()).with_sets();
+        let skipping = ComposedPolifunction::new(square_naturals(), plus_minus()).with_sets().skip_outside_domain();
+        for x in -12..12 {
+            assert_eq!(composed.in_domain(&x), !composed.evaluate(&x).is_err_and(|e| e.is_domain_error()), "input {}", x);
+            assert_eq!(skipping.in_domain(&x), !skipping.evaluate(&x).is_err_and(|e| e.is_domain_error()), "input {}", x);
+        }
+        
+        // Only 0 maps to a set inside the naturals
+        assert!(composed.in_domain(&0) && !composed.in_domain(&2));
+        assert!(skipping.in_domain(&2) && !skipping.in_domain(&10));
+    }
+    
+    #[test]
+    fn composable_compose_sets_merges_sets() {
+        let composed = square_naturals().compose_sets(plus_minus()).unwrap();
+        assert_eq!(composed.evaluate(&0).unwrap().as_set(), Some(&[0].into_iter().collect()));
+        assert!(composed.evaluate(&3).unwrap_err().is_domain_error());
+        assert!(!composed.in_domain(&3));
+        
+        // Plain compose leaves sets alone
+        let composed = square_naturals().compose(plus_minus()).unwrap();
+        assert_eq!(composed.evaluate(&0), Err(PolifunctionError::InvalidOperation));
+    }
+    
+    #[test]
+    fn composition_can_skip_set_elements_outside_domain() {
+        let composed = ComposedPolifunction::new(square_naturals(), plus_minus())
+            .with_sets()
+            .skip_outside_domain();
+        assert_eq!(composed.evaluate(&2).unwrap().as_set(), Some(&[4].into_iter().collect()));
+        assert_eq!(composed.evaluate(&-3).unwrap().as_set(), Some(&[9].into_iter().collect()));
+    }
+    
+    #[test]
+    fn values_compare_with_partial_eq() {
+        let third = PolifunctionValue::Single(0.1 + 0.2);
+        assert_ne!(third, PolifunctionValue::Single(0.3));
+        assert!(third.approx_eq(&PolifunctionValue::Single(0.3), 1e-12));
+        
+        let interval = PolifunctionValue::Interval(Interval::new(0.1 + 0.2, 1.0, true, false).unwrap());
+        let rounded = PolifunctionValue::Interval(Interval::new(0.3, 1.0, true, false).unwrap());
+        assert_ne!(interval, rounded);
+        assert!(interval.approx_eq(&rounded, 1e-12));
+        assert_eq!(interval.clone(), interval);
+        
+        assert_ne!(PolifunctionValue::Single(1.0), PolifunctionValue::Interval(Interval::new(1.0, 1.0, true, true).unwrap()));
+    }
+    
+    #[test]
+    fn sets_compare_by_mutual_containment() {
+        let set = |values: &[i32]| PolifunctionValue::Set(values.iter().copied().collect::<HashSet<_>>());
+        assert_eq!(set(&[1, 2, 3]), set(&[3, 1, 2]));
+        assert_ne!(set(&[1, 2, 3]), set(&[1, 2]));
+        assert_ne!(set(&[1, 2]), set(&[1, 2, 3]));
+        assert_ne!(set(&[1, 2]), set(&[1, 4]));
+        assert_eq!(set(&[]), set(&[]));
+        assert!(!set(&[1, 2, 3]).approx_eq(&set(&[1, 2]), 0.5));
+    }
+    
+    #[test]
+    fn errors_compare_by_root_kind() {
+        let wrapped = PolifunctionError::ComputationError.context("left operand").context("outer function of composition");
+        assert_eq!(wrapped, PolifunctionError::ComputationError);
+        assert_eq!(PolifunctionError::ComputationError, wrapped);
+        assert_eq!(wrapped, PolifunctionError::ComputationError.context("right operand"));
+        assert_ne!(wrapped, PolifunctionError::DomainError);
+        assert_ne!(wrapped, PolifunctionError::DomainError.context("left operand"));
+        assert_eq!(PolifunctionError::Other("a".to_string()), PolifunctionError::Other("b".to_string()));
+        assert_ne!(PolifunctionError::Other("a".to_string()), PolifunctionError::InvalidOperation);
+        
+        // A recorded input does not change the kind
+        let at_three = PolifunctionError::domain_error_at(&3).context("x");
+        assert_eq!(at_three, PolifunctionError::DomainError);
+        assert_eq!(at_three, PolifunctionError::DomainErrorAt("4".to_string()));
+        assert_eq!(at_three.to_string(), "x: Input 3 is outside the function's domain");
+        
+        // A foreign source ends the chain
+        let foreign = PolifunctionError::Wrapped {
+            context: "reading table".to_string(),
+            source: Box::new(std::fmt::Error),
+        };
+        assert!(matches!(foreign.root(), PolifunctionError::Wrapped { .. }));
+        assert_ne!(foreign, PolifunctionError::ComputationError);
+    }
+    
+    #[test]
+    fn context_surfaces_through_two_levels_of_composition() {
+        let failing = LiftedPolifunction::new(
+            |x: &i64| if *x < 0 { Err(PolifunctionError::ComputationError) } else { Ok(*x) },
+            Unbounded::<i64>::new(),
+            Unbounded::<i64>::new(),
+        );
+        let shift = || LiftedPolifunction::new(|x: &i64| Ok(x - 5), Unbounded::<i64>::new(), Unbounded::<i64>::new());
+        
+        // failing(shift(shift(x))): the error comes from the outermost function
+        let composed = ComposedPolifunction::new(failing.clone(), ComposedPolifunction::new(shift(), shift()));
+        let error = composed.evaluate(&3).unwrap_err();
+        assert_eq!(error, PolifunctionError::ComputationError);
+        assert_eq!(error.to_string(), format!("outer function of composition: {}", PolifunctionError::ComputationError));
+        
+        // shift(shift(failing(x))): the error is two compositions deep
+        let composed = ComposedPolifunction::new(shift(), ComposedPolifunction::new(shift(), failing));
+        let error = composed.evaluate(&-1).unwrap_err();
+        assert_eq!(error, PolifunctionError::ComputationError);
+        assert_eq!(
+            error.to_string(),
+            format!("inner function of composition: inner function of composition: {}", PolifunctionError::ComputationError),
+        );
+        assert!(!error.is_domain_error());
+        assert!(error.source().is_some());
+    }
+    
+    fn every_variant() -> Vec<PolifunctionValue<i64>> {
+        vec![
+            PolifunctionValue::Single(2),
+            PolifunctionValue::Set([-1, 1, 3].into_iter().collect()),
+            PolifunctionValue::Interval(Interval::new(1, 4, true, false).unwrap()),
+            PolifunctionValue::Distribution(ProbabilityDistribution::new(vec![(1, 0.25), (2, 0.75)]).unwrap()),
+            PolifunctionValue::FuzzySet(FuzzySet::new(vec![(1, 0.5), (2, 1.0)]).unwrap()),
+            PolifunctionValue::MultiInterval(IntervalSet::new(vec![closed_i(0, 1), closed_i(3, 4)])),
+        ]
+    }
+    
+    #[test]
+    fn map_keeps_variants_without_hashable_values() {
+        let half = |x: i64| x as f64 / 2.0;
+        let mapped: Vec<_> = every_variant().into_iter().map(|value| value.map(half)).collect();
+        assert_eq!(mapped, vec![
+            Ok(PolifunctionValue::Single(1.0)),
+            Err(PolifunctionError::InvalidOperation),
+            Ok(PolifunctionValue::Interval(Interval::new(0.5, 2.0, true, false).unwrap())),
+            Ok(PolifunctionValue::Distribution(ProbabilityDistribution::new(vec![(0.5, 0.25), (1.0, 0.75)]).unwrap())),
+            Ok(PolifunctionValue::FuzzySet(FuzzySet::new(vec![(0.5, 0.5), (1.0, 1.0)]).unwrap())),
+            Ok(PolifunctionValue::MultiInterval(IntervalSet::new(vec![closed(0.0, 0.5), closed(1.5, 2.0)]))),
+        ]);
+    }
+    
+    #[test]
+    fn map_with_sets_maps_every_variant() {
+        let mapped: Vec<_> = every_variant().into_iter().map(|value| value.map_with_sets(|x| x * 10)).collect();
+        assert_eq!(mapped[0], PolifunctionValue::Single(20));
+        assert_eq!(mapped[1], PolifunctionValue::Set([-10, 10, 30].into_iter().collect()));
+        assert_eq!(mapped[2], PolifunctionValue::Interval(Interval::new(10, 40, true, false).unwrap()));
+        assert_eq!(mapped[5], PolifunctionValue::MultiInterval(IntervalSet::new(vec![closed_i(0, 10), closed_i(30, 40)])));
+        
+        // Values that collide are merged in sets
+        let squared = PolifunctionValue::Set([-1, 1, 3].into_iter().collect()).map_with_sets(|x| x * x);
+        assert_eq!(squared, PolifunctionValue::Set([1, 9].into_iter().collect()));
+        
+        // Mapped interval sets are normalized again
+        let merged = every_variant().pop().unwrap().map_with_sets(|x| x.min(1));
+        assert!(matches!(merged, PolifunctionValue::MultiInterval(set) if set.intervals() == [closed_i(0, 1)]));
+    }
+    
+    #[test]
+    fn map_with_decreasing_function_empties_intervals() {
+        let negated = PolifunctionValue::Interval(closed_i(1, 4)).map(|x| -x).unwrap();
+        assert!(negated.as_interval().unwrap().is_empty());
+        
+        let negated = PolifunctionValue::MultiInterval(IntervalSet::new(vec![closed_i(1, 4)])).map(|x| -x).unwrap();
+        assert!(matches!(negated, PolifunctionValue::MultiInterval(set) if set.is_empty()));
+    }
+    
+    #[test]
+    fn try_iter_and_cardinality_list_singles_and_sets() {
+        let counts: Vec<_> = every_variant().iter().map(PolifunctionValue::cardinality).collect();
+        assert_eq!(counts, vec![Some(1), Some(3), None, None, None, None]);
+        
+        let values = every_variant();
+        assert_eq!(values[0].try_iter().unwrap().collect::<Vec<_>>(), vec![&2]);
+        let mut set: Vec<_> = values[1].try_iter().unwrap().copied().collect();
+        set.sort();
+        assert_eq!(set, vec![-1, 1, 3]);
+        assert!(values[2..].iter().all(|value| value.try_iter().is_none()));
+        
+        let empty = PolifunctionValue::<i64>::Set(HashSet::new());
+        assert_eq!(empty.cardinality(), Some(0));
+        assert_eq!(empty.try_iter().unwrap().count(), 0);
+    }
+    
+    #[test]
+    fn subtraction_needs_no_ordering() {
+        /// Element type with subtraction but no ordering
+        #[derive(Debug, Clone, PartialEq)]
+        struct Offset(i64);
+        
+        impl Sub for Offset {
+            type Output = Offset;
+            
+            fn sub(self, other: Offset) -> Offset {
+                Offset(self.0 - other.0)
+            }
+        }
+        
+        let interval = |lower, upper, lower_inclusive, upper_inclusive| Interval { lower: Offset(lower), upper: Offset(upper), lower_inclusive, upper_inclusive };
+        assert_eq!(interval(5, 9, true, false) - interval(1, 2, false, true), interval(3, 8, true, false));
+        assert_eq!(closed_i(5, 9) - closed_i(1, 2), closed_i(3, 8));
+    }
+    
+    #[test]
+    fn intervals_parse_what_they_display() {
+        let intervals = [
+            Interval::new(0.0, 1.0, true, true).unwrap(),
+            Interval::new(-0.1, 2.5, false, true).unwrap(),
+            Interval::new(1e-3, 1e6, true, false).unwrap(),
+            Interval::new(-3.0, -2.0, false, false).unwrap(),
+            Interval::new(4.0, 4.0, true, true).unwrap(),
+        ];
+        for interval in intervals {
+            assert_eq!(interval.to_string().parse::<Interval<f64>>(), Ok(interval.clone()), "{}", interval);
+        }
+        
+        assert_eq!(closed_i(-2, 7).to_string(), "[-2, 7]");
+        assert_eq!(Interval::new(0, 1, false, true).unwrap().to_string(), "(0, 1]");
+        assert_eq!(Interval::new(0, 1, true, false).unwrap().to_string(), "[0, 1)");
+        assert_eq!(" (  0 ,1 ) ".parse::<Interval<i64>>(), Interval::new(0, 1, false, false));
+    }
+    
+    #[test]
+    fn malformed_intervals_are_rejected_with_a_reason() {
+        let reason = |s: &str| match s.parse::<Interval<i64>>() {
+            Err(PolifunctionError::Other(message)) => message,
+            other => panic!("'{}' parsed as {:?}", s, other),
+        };
+        assert_eq!(reason("0, 1]"), "Invalid interval '0, 1]': expected '[' or '(' at the start");
+        assert_eq!(reason(""), "Invalid interval '': expected '[' or '(' at the start");
+        assert_eq!(reason("[0, 1"), "Invalid interval '[0, 1': expected ']' or ')' at the end");
+        assert_eq!(reason("["), "Invalid interval '[': expected ']' or ')' at the end");
+        assert_eq!(reason("[0 1]"), "Invalid interval '[0 1]': expected two endpoints separated by ','");
+        assert_eq!(reason("[a, 1]"), "Invalid interval '[a, 1]': could not parse the lower endpoint");
+        assert_eq!(reason("[0, 1, 2]"), "Invalid interval '[0, 1, 2]': could not parse the upper endpoint");
+        
+        // Well-formed but empty intervals are rejected like Interval::new
+        assert_eq!(reason("[3, 1]"), "Invalid interval bounds: the interval is empty");
+        assert!("(1, 1]".parse::<Interval<i64>>().is_err());
+    }
+    
+    #[test]
+    fn values_display_each_variant() {
+        let displayed: Vec<_> = every_variant().iter().map(|value| value.to_string()).collect();
+        assert_eq!(displayed, vec!["2", "{-1, 1, 3}", "[1, 4)", "{1:0.25, 2:0.75}", "{1/0.5, 2/1}", "[0, 1] ∪ [3, 4]"]);
+        
+        assert_eq!(PolifunctionValue::<i64>::Set(HashSet::new()).to_string(), "{}");
+        assert_eq!(PolifunctionValue::<i64>::MultiInterval(IntervalSet::new(vec![])).to_string(), "{}");
+    }
+    
+    #[cfg(feature = "num")]
+    fn decimal(lower: &str, upper: &str) -> Interval<num_rational::Ratio<i64>> {
+        Interval::from_decimal_strs(lower, upper, true, true).unwrap()
+    }
+    
+    #[cfg(feature = "num")]
+    #[test]
+    fn outward_conversion_strictly_contains_naive_construction() {
+        let outward = decimal("0.1", "0.3").to_f64_outward();
+        let naive = closed(0.1, 0.3);
+        
+        assert!(outward.lower < naive.lower && naive.upper < outward.upper, "{} does not strictly contain {}", outward, naive);
+        // and is only one ulp wider on each side
+        assert_eq!(outward.lower, next_after(0.1, f64::NEG_INFINITY));
+        assert_eq!(outward.upper, next_after(0.3, f64::INFINITY));
+        
+        // Exactly representable endpoints are kept
+        let exact = decimal("-0.5", "2").to_f64_outward();
+        assert_eq!((exact.lower, exact.upper), (-0.5, 2.0));
+    }
+    
+    #[cfg(feature = "num")]
+    #[test]
+    fn ten_times_a_tenth_contains_exactly_one() {
+        let one = num_rational::Ratio::from_integer(1);
+        let product = decimal("10", "10") * decimal("0.1", "0.1");
+        assert_eq!((product.lower, product.upper), (one, one));
+        assert!(product.contains(&one));
+        assert!(product.to_f64_outward().contains(&1.0));
+    }
+    
+    #[cfg(feature = "num")]
+    #[test]
+    fn decimal_strs_reject_empty_and_malformed_input() {
+        let empty = "Invalid interval bounds: the interval is empty";
+        assert_eq!(Interval::from_decimal_strs("0.3", "0.1", true, true).unwrap_err().to_string(), empty);
+        assert_eq!(Interval::from_decimal_strs("0.1", "0.10", false, true).unwrap_err().to_string(), empty);
+        assert!(Interval::from_decimal_strs("0.1", "0.1", true, true).unwrap().is_degenerate());
+        
+        for literal in ["", ".", "1e3", "0x10", "1.2.3", "--1", "99999999999999999999"] {
+            assert_eq!(
+                Interval::from_decimal_strs(literal, "1", true, true),
+                Err(PolifunctionError::Other(format!("Invalid decimal literal '{}'", literal))),
+            );
+        }
+        assert_eq!(decimal("-.5", "+1.").lower, num_rational::Ratio::new(-1, 2));
+    }
+    
+    #[cfg(feature = "num")]
+    #[test]
+    fn next_after_steps_one_ulp() {
+        assert_eq!(next_after(1.0, 2.0), 1.0 + f64::EPSILON);
+        assert_eq!(next_after(1.0, 0.0), 1.0 - f64::EPSILON / 2.0);
+        assert_eq!(next_after(-1.0, f64::NEG_INFINITY), -1.0 - f64::EPSILON);
+        assert_eq!(next_after(0.0, 1.0), f64::from_bits(1));
+        assert_eq!(next_after(0.0, -1.0), -f64::from_bits(1));
+        assert_eq!(next_after(2.0, 2.0), 2.0);
+        assert!(next_after(f64::NAN, 1.0).is_nan());
+    }
+    
+    #[cfg(feature = "serde")]
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn values_survive_a_json_round_trip() {
+        let values = vec![
+            PolifunctionValue::Single(3),
+            PolifunctionValue::Set([1, -1, 4].into_iter().collect()),
+            PolifunctionValue::Set(HashSet::new()),
+            PolifunctionValue::Interval(Interval::new(-2, 5, false, true).unwrap()),
+            PolifunctionValue::Distribution(ProbabilityDistribution::new(vec![(1, 0.25), (2, 0.75)]).unwrap()),
+            PolifunctionValue::FuzzySet(FuzzySet::new(vec![(1, 0.5), (2, 1.0)]).unwrap()),
+            PolifunctionValue::MultiInterval(IntervalSet::new(vec![closed_i(0, 1), closed_i(3, 4)])),
+        ];
+        for value in &values {
+            assert_eq!(&round_trip(value), value);
+        }
+        
+        let interval = Interval::new(0.5, 1.5, true, false).unwrap();
+        assert_eq!(round_trip(&interval), interval);
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn values_serialize_tagged_by_variant() {
+        assert_eq!(serde_json::to_string(&PolifunctionValue::Single(3)).unwrap(), r#"{"Single":3}"#);
+        assert_eq!(
+            serde_json::to_string(&PolifunctionValue::Interval(closed_i(1, 2))).unwrap(),
+            r#"{"Interval":{"lower":1,"upper":2,"lower_inclusive":true,"upper_inclusive":true}}"#,
+        );
+        let set: PolifunctionValue<i64> = serde_json::from_str(r#"{"Set":[2,1,2]}"#).unwrap();
+        assert_eq!(set, PolifunctionValue::Set([1, 2].into_iter().collect()));
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialization_rejects_empty_intervals() {
+        let reversed = r#"{"lower":5,"upper":1,"lower_inclusive":true,"upper_inclusive":true}"#;
+        let error = serde_json::from_str::<Interval<i64>>(reversed).unwrap_err();
+        assert!(error.to_string().contains("the interval is empty"), "{}", error);
+        
+        let half_open_point = r#"{"lower":1,"upper":1,"lower_inclusive":true,"upper_inclusive":false}"#;
+        assert!(serde_json::from_str::<Interval<i64>>(half_open_point).is_err());
+        
+        // Also inside a value
+        let value = format!(r#"{{"Interval":{}}}"#, reversed);
+        assert!(serde_json::from_str::<PolifunctionValue<i64>>(&value).is_err());
+        
+        let point = r#"{"lower":1,"upper":1,"lower_inclusive":true,"upper_inclusive":true}"#;
+        assert_eq!(serde_json::from_str::<Interval<i64>>(point).unwrap(), closed_i(1, 1));
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialization_checks_distributions_and_fuzzy_sets() {
+        assert!(serde_json::from_str::<ProbabilityDistribution<i64>>(r#"{"pairs":[[1,0.5],[2,0.25]]}"#).is_err());
+        assert!(serde_json::from_str::<ProbabilityDistribution<i64>>(r#"{"pairs":[[1,1.5],[2,-0.5]]}"#).is_err());
+        assert!(serde_json::from_str::<FuzzySet<i64>>(r#"{"pairs":[[1,1.5]]}"#).is_err());
+        
+        // Interval sets are normalized instead of rejected
+        let merged: IntervalSet<i64> = serde_json::from_str(
+            r#"{"intervals":[{"lower":3,"upper":4,"lower_inclusive":true,"upper_inclusive":true},{"lower":0,"upper":3,"lower_inclusive":true,"upper_inclusive":true}]}"#,
+        ).unwrap();
+        assert_eq!(merged.intervals(), &[closed_i(0, 4)]);
     }
 }
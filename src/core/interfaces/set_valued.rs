@@ -3,10 +3,12 @@
 //! This module provides traits and implementations for polifunctions
 //! that map inputs to sets of output values.
 
-use std::collections::HashSet;
+use std::collections::{hash_set, HashMap, HashSet};
 use std::hash::Hash;
+use std::sync::Arc;
 
 use super::polifunction::{PolifunctionBase, PolifunctionValue, PolifunctionError, Domain, Codomain};
+use super::domains::Unbounded;
 
 /// Trait for set-valued polifunctions
 pub trait SetValuedPolifunction: PolifunctionBase {
@@ -22,10 +24,92 @@ pub trait SetValuedPolifunction: PolifunctionBase {
     /// Get the cardinality of the output set for a given input
     fn cardinality(&self, input: &<Self::Domain as Domain>::Element) 
         -> Result<usize, PolifunctionError>;
+    
+    /// Check if the output set for a given input has at most `n` values
+    ///
+    /// Implementations that generate their values lazily can override this to
+    /// stop as soon as the threshold is exceeded.
+    fn cardinality_at_most(&self, input: &<Self::Domain as Domain>::Element, n: usize)
+        -> Result<bool, PolifunctionError> {
+        Ok(self.cardinality(input)? <= n)
+    }
+    
+    /// Iterate over the values in the output set for a given input
+    fn iter_values(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<hash_set::IntoIter<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        Ok(self.value_set(input)?.into_iter())
+    }
+    
+    /// Get the smallest value in the output set for a given input
+    ///
+    /// Returns `EmptyResult` if the set is empty.
+    fn min_value(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Ord,
+    {
+        self.iter_values(input)?.min().ok_or(PolifunctionError::EmptyResult)
+    }
+    
+    /// Get the largest value in the output set for a given input
+    ///
+    /// Returns `EmptyResult` if the set is empty.
+    fn max_value(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<<Self::Codomain as Codomain>::Element, PolifunctionError>
+    where
+        <Self::Codomain as Codomain>::Element: Ord,
+    {
+        self.iter_values(input)?.max().ok_or(PolifunctionError::EmptyResult)
+    }
+}
+
+/// Implement `SetValuedPolifunction` for pointer types by forwarding to the pointee
+macro_rules! forward_set_valued {
+    ($($pointer:ty),*) => {
+        $(
+            impl<P: SetValuedPolifunction + ?Sized> SetValuedPolifunction for $pointer {
+                fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+                    (**self).value_set(input)
+                }
+                
+                fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                                 value: &<Self::Codomain as Codomain>::Element)
+                    -> Result<bool, PolifunctionError> {
+                    (**self).contains_value(input, value)
+                }
+                
+                fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<usize, PolifunctionError> {
+                    (**self).cardinality(input)
+                }
+                
+                fn cardinality_at_most(&self, input: &<Self::Domain as Domain>::Element, n: usize)
+                    -> Result<bool, PolifunctionError> {
+                    (**self).cardinality_at_most(input, n)
+                }
+                
+                fn iter_values(&self, input: &<Self::Domain as Domain>::Element)
+                    -> Result<hash_set::IntoIter<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+                    (**self).iter_values(input)
+                }
+            }
+        )*
+    };
 }
 
+forward_set_valued!(&P, Box<P>, Arc<P>);
+
+/// Mapping function of a [`BasicSetValuedPolifunction`], stored behind an `Arc`
+pub type SetMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<HashSet<<C as Codomain>::Element>, PolifunctionError>;
+
+/// Mapping function of a [`BasicSetValuedPolifunction`] that can be shared between threads
+pub type SyncSetMapping<D, C> = dyn Fn(&<D as Domain>::Element) -> Result<HashSet<<C as Codomain>::Element>, PolifunctionError> + Send + Sync;
+
 /// Basic implementation of a set-valued polifunction
-pub struct BasicSetValuedPolifunction<D, C>
+///
+/// The mapping function is kept behind an `Arc`, so clones share it.
+pub struct BasicSetValuedPolifunction<D, C, F: ?Sized = SetMapping<D, C>>
 where
     D: Domain,
     C: Codomain,
@@ -33,13 +117,46 @@ where
     C::Element: Clone + Hash + Eq,
 {
     /// Function that maps inputs to sets of outputs
-    mapping_function: Box<dyn Fn(&D::Element) -> Result<HashSet<C::Element>, PolifunctionError>>,
+    mapping_function: Arc<F>,
     /// Domain of the function
     domain: D,
     /// Codomain of the function
     codomain: C,
 }
 
+impl<D, C, F: ?Sized> Clone for BasicSetValuedPolifunction<D, C, F>
+where
+    D: Domain + Clone,
+    C: Codomain + Clone,
+    D::Element: Clone + Hash + Eq,
+    C::Element: Clone + Hash + Eq,
+{
+    fn clone(&self) -> Self {
+        Self {
+            mapping_function: Arc::clone(&self.mapping_function),
+            domain: self.domain.clone(),
+            codomain: self.codomain.clone(),
+        }
+    }
+}
+
+/// The mapping function is shown as a placeholder
+impl<D, C, F: ?Sized> std::fmt::Debug for BasicSetValuedPolifunction<D, C, F>
+where
+    D: Domain + std::fmt::Debug,
+    C: Codomain + std::fmt::Debug,
+    D::Element: Clone + Hash + Eq,
+    C::Element: Clone + Hash + Eq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicSetValuedPolifunction")
+            .field("mapping_function", &format_args!("<function>"))
+            .field("domain", &self.domain)
+            .field("codomain", &self.codomain)
+            .finish()
+    }
+}
+
 impl<D, C> BasicSetValuedPolifunction<D, C>
 where
     D: Domain,
@@ -54,19 +171,63 @@ where
         codomain: C,
     ) -> Self {
         Self {
-            mapping_function: Box::new(mapping_function),
+            mapping_function: Arc::new(mapping_function),
             domain,
             codomain,
         }
     }
 }
 
-impl<D, C> PolifunctionBase for BasicSetValuedPolifunction<D, C>
+impl<D, C, F> BasicSetValuedPolifunction<D, C, F>
 where
     D: Domain,
     C: Codomain,
     D::Element: Clone + Hash + Eq,
     C::Element: Clone + Hash + Eq,
+    F: Fn(&D::Element) -> Result<HashSet<C::Element>, PolifunctionError>,
+{
+    /// Create a new set-valued polifunction that keeps the concrete type of the mapping function
+    ///
+    /// The result is `Send + Sync` whenever the function, domain and codomain are.
+    pub fn from_fn(mapping_function: F, domain: D, codomain: C) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C> BasicSetValuedPolifunction<D, C, SyncSetMapping<D, C>>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: Clone + Hash + Eq,
+    C::Element: Clone + Hash + Eq,
+{
+    /// Create a new set-valued polifunction whose mapping function can be shared between threads
+    ///
+    /// The result is `Send + Sync` whenever the domain and codomain are.
+    pub fn new_sync(
+        mapping_function: impl Fn(&D::Element) -> Result<HashSet<C::Element>, PolifunctionError> + Send + Sync + 'static,
+        domain: D,
+        codomain: C,
+    ) -> Self {
+        Self {
+            mapping_function: Arc::new(mapping_function),
+            domain,
+            codomain,
+        }
+    }
+}
+
+impl<D, C, F> PolifunctionBase for BasicSetValuedPolifunction<D, C, F>
+where
+    D: Domain,
+    C: Codomain,
+    D::Element: Clone + Hash + Eq + std::fmt::Debug,
+    C::Element: Clone + Hash + Eq,
+    F: Fn(&D::Element) -> Result<HashSet<C::Element>, PolifunctionError> + ?Sized,
 {
     type Domain = D;
     type Codomain = C;
@@ -74,7 +235,7 @@ where
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
         let result_set = (self.mapping_function)(input)?;
@@ -84,19 +245,26 @@ where
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
         self.domain.contains(input)
     }
+    
+    fn evaluate_trusted(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let result_set = (self.mapping_function)(input)?;
+        Ok(PolifunctionValue::Set(result_set))
+    }
 }
 
-impl<D, C> SetValuedPolifunction for BasicSetValuedPolifunction<D, C>
+impl<D, C, F> SetValuedPolifunction for BasicSetValuedPolifunction<D, C, F>
 where
     D: Domain,
     C: Codomain,
-    D::Element: Clone + Hash + Eq,
+    D::Element: Clone + Hash + Eq + std::fmt::Debug,
     C::Element: Clone + Hash + Eq,
+    F: Fn(&D::Element) -> Result<HashSet<C::Element>, PolifunctionError> + ?Sized,
 {
     fn value_set(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
         (self.mapping_function)(input)
@@ -116,7 +284,301 @@ where
     }
 }
 
+/// Set-valued polifunction backed by an explicit lookup table
+///
+/// The domain is the set of inputs present in the table. An input may map to
+/// an empty set, which is different from being absent. A reverse index from
+/// each output to the inputs producing it is kept alongside the table.
+#[derive(Debug, Clone)]
+pub struct TabularSetValuedPolifunction<K, V>
+where
+    K: Hash + Eq,
+    V: Hash + Eq,
+{
+    /// Output set stored for each input
+    table: HashMap<K, HashSet<V>>,
+    /// Inputs whose output set contains each output
+    index: HashMap<V, HashSet<K>>,
+}
+
+impl<K, V> TabularSetValuedPolifunction<K, V>
+where
+    K: Hash + Eq,
+    V: Hash + Eq,
+{
+    /// Create a new empty table
+    pub fn new() -> Self {
+        Self {
+            table: HashMap::new(),
+            index: HashMap::new(),
+        }
+    }
+    
+    /// Get the stored output set of an input without cloning it
+    pub fn get(&self, k: &K) -> Option<&HashSet<V>> {
+        self.table.get(k)
+    }
+    
+    /// Iterate over every stored (input, output) pair
+    pub fn pairs(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.table.iter().flat_map(|(k, set)| set.iter().map(move |v| (k, v)))
+    }
+    
+    /// Iterate over the inputs in the table
+    pub fn inputs(&self) -> impl Iterator<Item = &K> {
+        self.table.keys()
+    }
+    
+    /// Iterate over the inputs whose output set contains `v`
+    ///
+    /// This is a lookup in the reverse index rather than a scan of the table.
+    pub fn inputs_producing<'a>(&'a self, v: &V) -> impl Iterator<Item = &'a K> + 'a {
+        self.index.get(v).into_iter().flatten()
+    }
+}
+
+impl<K, V> TabularSetValuedPolifunction<K, V>
+where
+    K: Clone + Hash + Eq,
+    V: Clone + Hash + Eq,
+{
+    /// Create a table from (input, output) pairs
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut result = Self::new();
+        for (k, v) in pairs {
+            result.insert(k, v);
+        }
+        result
+    }
+    
+    /// Add an output to the set of an input, adding the input if needed
+    ///
+    /// Returns true if the output was not already present.
+    pub fn insert(&mut self, k: K, v: V) -> bool {
+        if !self.table.entry(k.clone()).or_default().insert(v.clone()) {
+            return false;
+        }
+        
+        self.index.entry(v).or_default().insert(k);
+        true
+    }
+    
+    /// Add an input with an empty output set, keeping any existing outputs
+    pub fn insert_input(&mut self, k: K) {
+        self.table.entry(k).or_default();
+    }
+    
+    /// Remove an output from the set of an input
+    ///
+    /// The input stays in the domain even if its set becomes empty.
+    /// Returns true if the output was present.
+    pub fn remove(&mut self, k: &K, v: &V) -> bool {
+        if !self.table.get_mut(k).is_some_and(|set| set.remove(v)) {
+            return false;
+        }
+        
+        self.unindex(k, v);
+        true
+    }
+    
+    /// Remove an input and its whole output set from the table
+    pub fn remove_input(&mut self, k: &K) -> Option<HashSet<V>> {
+        let set = self.table.remove(k)?;
+        for v in &set {
+            self.unindex(k, v);
+        }
+        Some(set)
+    }
+    
+    /// Remove an output from the set of every input producing it
+    ///
+    /// The inputs stay in the domain. Returns the inputs whose set contained `v`.
+    pub fn remove_value_everywhere(&mut self, v: &V) -> HashSet<K> {
+        let inputs = self.index.remove(v).unwrap_or_default();
+        for k in &inputs {
+            if let Some(set) = self.table.get_mut(k) {
+                set.remove(v);
+            }
+        }
+        inputs
+    }
+    
+    /// Replace the output `old` by `new` in every output set
+    ///
+    /// Where an input already produces `new` the two outputs merge into one.
+    /// Returns the number of inputs whose set contained `old`.
+    pub fn rename_value(&mut self, old: &V, new: V) -> usize {
+        if *old == new {
+            return self.index.get(old).map_or(0, HashSet::len);
+        }
+        
+        let inputs = self.remove_value_everywhere(old);
+        let renamed = inputs.len();
+        for k in inputs {
+            self.insert(k, new.clone());
+        }
+        renamed
+    }
+    
+    /// Drop `k` from the reverse index entry of `v`, removing the entry once it is empty
+    fn unindex(&mut self, k: &K, v: &V) {
+        if let Some(inputs) = self.index.get_mut(v) {
+            inputs.remove(k);
+            if inputs.is_empty() {
+                self.index.remove(v);
+            }
+        }
+    }
+}
+
+impl<K, V> Default for TabularSetValuedPolifunction<K, V>
+where
+    K: Hash + Eq,
+    V: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TabularSetValuedPolifunction<K, V>
+where
+    K: Clone + Hash + Eq,
+    V: Clone + Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from_pairs(iter)
+    }
+}
+
+/// One input of a [`TabularSetValuedPolifunction`] with its outputs, as stored in JSON
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TabularRecord<K, V> {
+    input: K,
+    values: Vec<V>,
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> TabularSetValuedPolifunction<K, V>
+where
+    K: Clone + Hash + Eq + serde::Serialize + serde::de::DeserializeOwned,
+    V: Clone + Hash + Eq + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Encode the table as a JSON list of `{"input": ..., "values": [...]}` records
+    ///
+    /// Records are sorted by their serialized input, and the values of a
+    /// record by their serialized form, so the output is stable and diffable.
+    ///
+    /// Returns an error if an input or value fails to serialize, which only
+    /// happens for types whose `Serialize` implementation can fail.
+    pub fn to_json(&self) -> Result<String, PolifunctionError> {
+        fn sort_key<T: serde::Serialize>(x: &T) -> Result<String, PolifunctionError> {
+            serde_json::to_string(x).map_err(serialize_error)
+        }
+        
+        fn serialize_error(e: serde_json::Error) -> PolifunctionError {
+            PolifunctionError::Other(format!("Cannot serialize table: {}", e))
+        }
+        
+        let mut records: Vec<(String, TabularRecord<&K, &V>)> = Vec::with_capacity(self.table.len());
+        for (k, set) in &self.table {
+            let mut values = set.iter()
+                .map(|v| Ok((sort_key(v)?, v)))
+                .collect::<Result<Vec<(String, &V)>, PolifunctionError>>()?;
+            values.sort_by(|a, b| a.0.cmp(&b.0));
+            let record = TabularRecord {
+                input: k,
+                values: values.into_iter().map(|(_, v)| v).collect(),
+            };
+            records.push((sort_key(k)?, record));
+        }
+        records.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        let records: Vec<TabularRecord<&K, &V>> = records.into_iter().map(|(_, record)| record).collect();
+        serde_json::to_string_pretty(&records).map_err(serialize_error)
+    }
+    
+    /// Decode a table written by [`to_json`](Self::to_json)
+    ///
+    /// Records with the same input are merged. Returns an error if the JSON
+    /// is malformed.
+    pub fn from_json(json: &str) -> Result<Self, PolifunctionError> {
+        Self::from_json_records(json, |_| true)
+    }
+    
+    /// Decode a table written by [`to_json`](Self::to_json), checking every value against a codomain
+    ///
+    /// Like [`from_json`](Self::from_json), but also returns an error if any
+    /// value is not contained in `codomain`.
+    pub fn from_json_checked<C>(json: &str, codomain: &C) -> Result<Self, PolifunctionError>
+    where
+        C: Codomain<Element = V>,
+    {
+        Self::from_json_records(json, |v| codomain.contains(v))
+    }
+    
+    /// Decode JSON records, merging duplicate inputs and rejecting values that fail `check`
+    fn from_json_records(json: &str, check: impl Fn(&V) -> bool) -> Result<Self, PolifunctionError> {
+        let records: Vec<TabularRecord<K, V>> = serde_json::from_str(json)
+            .map_err(|e| PolifunctionError::Other(format!("Invalid table JSON: {}", e)))?;
+        
+        let mut result = Self::new();
+        for (i, record) in records.into_iter().enumerate() {
+            if !record.values.iter().all(&check) {
+                return Err(PolifunctionError::Other(format!("Invalid table JSON: record {} has a value outside the codomain", i)));
+            }
+            
+            result.insert_input(record.input.clone());
+            for v in record.values {
+                result.insert(record.input.clone(), v);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<K, V> PolifunctionBase for TabularSetValuedPolifunction<K, V>
+where
+    K: Hash + Eq,
+    V: Clone + Hash + Eq,
+{
+    type Domain = Unbounded<K>;
+    type Codomain = Unbounded<V>;
+    
+    fn evaluate(&self, input: &K) -> Result<PolifunctionValue<V>, PolifunctionError> {
+        Ok(PolifunctionValue::Set(self.value_set(input)?))
+    }
+    
+    fn in_domain(&self, input: &K) -> bool {
+        self.table.contains_key(input)
+    }
+}
+
+impl<K, V> SetValuedPolifunction for TabularSetValuedPolifunction<K, V>
+where
+    K: Hash + Eq,
+    V: Clone + Hash + Eq,
+{
+    fn value_set(&self, input: &K) -> Result<HashSet<V>, PolifunctionError> {
+        self.table.get(input).cloned().ok_or(PolifunctionError::DomainError)
+    }
+    
+    fn contains_value(&self, input: &K, value: &V) -> Result<bool, PolifunctionError> {
+        let set = self.table.get(input).ok_or(PolifunctionError::DomainError)?;
+        Ok(set.contains(value))
+    }
+    
+    fn cardinality(&self, input: &K) -> Result<usize, PolifunctionError> {
+        let set = self.table.get(input).ok_or(PolifunctionError::DomainError)?;
+        Ok(set.len())
+    }
+}
+
 /// Union of two set-valued polifunctions
+///
+/// `evaluate` reports a union with exactly one value as `Single`, and wraps
+/// errors other than domain errors with the operand they came from.
 pub struct UnionPolifunction<P1, P2>
 where
     P1: SetValuedPolifunction,
@@ -141,7 +603,7 @@ impl<P1, P2> PolifunctionBase for UnionPolifunction<P1, P2>
 where
     P1: SetValuedPolifunction,
     P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
-    <P1::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P1::Domain as Domain>::Element: Clone + Hash + Eq + std::fmt::Debug,
     <P1::Codomain as Codomain>::Element: Clone + Hash + Eq,
 {
     type Domain = P1::Domain;
@@ -149,53 +611,128 @@ where
     
     fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
         -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        // A single common value is reported as Single
+        Ok(PolifunctionValue::Set(self.value_set(input)?).normalize())
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.p1.in_domain(input) || self.p2.in_domain(input)
+    }
+}
+
+impl<P1, P2> SetValuedPolifunction for UnionPolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Domain as Domain>::Element: Clone + Hash + Eq + std::fmt::Debug,
+    <P1::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    /// Unite the sets of the operands that are defined at `input`
+    ///
+    /// An operand outside its domain contributes nothing; the union is only a
+    /// domain error when neither operand is defined. Other errors are wrapped
+    /// with the operand they came from.
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
         if !self.in_domain(input) {
-            return Err(PolifunctionError::DomainError);
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
-        let mut result_set = HashSet::new();
+        let set1 = defined_operand(self.p1.value_set(input), "first operand of union")?;
+        let set2 = defined_operand(self.p2.value_set(input), "second operand of union")?;
         
-        // Try to get values from the first polifunction
-        match self.p1.value_set(input) {
-            Ok(set1) => {
-                result_set.extend(set1);
+        match (set1, set2) {
+            (Some(mut set1), Some(set2)) => {
+                set1.extend(set2);
+                Ok(set1)
             },
-            Err(e) => {
-                if matches!(e, PolifunctionError::DomainError) {
-                    // If it's a domain error, that's fine, we'll just use the second function
-                } else {
-                    return Err(e);
-                }
-            }
+            (Some(set), None) | (None, Some(set)) => Ok(set),
+            (None, None) => Err(PolifunctionError::domain_error_at(input)),
+        }
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::domain_error_at(input));
         }
         
-        // Try to get values from the second polifunction
-        match self.p2.value_set(input) {
-            Ok(set2) => {
-                result_set.extend(set2);
-            },
-            Err(e) => {
-                if matches!(e, PolifunctionError::DomainError) {
-                    // If it's a domain error, that's fine, we already have results from the first function
-                    if result_set.is_empty() {
-                        // But if we don't have any results from the first function either, it's an error
-                        return Err(PolifunctionError::DomainError);
-                    }
-                } else {
-                    return Err(e);
-                }
-            }
+        let in_first = defined_operand(self.p1.contains_value(input, value), "first operand of union")?;
+        if in_first == Some(true) {
+            return Ok(true);
         }
         
+        match defined_operand(self.p2.contains_value(input, value), "second operand of union")? {
+            Some(in_second) => Ok(in_second),
+            None if in_first.is_some() => Ok(false),
+            None => Err(PolifunctionError::domain_error_at(input)),
+        }
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.len())
+    }
+}
+
+/// Turn an operand's domain error into `None` and wrap any other error with `operand`
+fn defined_operand<T>(result: Result<T, PolifunctionError>, operand: &str)
+    -> Result<Option<T>, PolifunctionError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_domain_error() => Ok(None),
+        Err(e) => Err(e.context(operand)),
+    }
+}
+
+/// Intersection of two set-valued polifunctions
+///
+/// The intersection is only defined where both operands are defined. An empty
+/// intersection is a valid (empty) value, not an error.
+pub struct IntersectionPolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    p1: P1,
+    p2: P2,
+}
+
+impl<P1, P2> IntersectionPolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    /// Create a new intersection of two set-valued polifunctions
+    pub fn new(p1: P1, p2: P2) -> Self {
+        Self { p1, p2 }
+    }
+}
+
+impl<P1, P2> PolifunctionBase for IntersectionPolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P1::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let result_set = self.value_set(input)?;
         Ok(PolifunctionValue::Set(result_set))
     }
     
     fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
-        self.p1.in_domain(input) || self.p2.in_domain(input)
+        self.p1.in_domain(input) && self.p2.in_domain(input)
     }
 }
 
-impl<P1, P2> SetValuedPolifunction for UnionPolifunction<P1, P2>
+impl<P1, P2> SetValuedPolifunction for IntersectionPolifunction<P1, P2>
 where
     P1: SetValuedPolifunction,
     P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
@@ -208,23 +745,99 @@ where
             return Err(PolifunctionError::DomainError);
         }
         
-        let mut result_set = HashSet::new();
+        let set1 = self.p1.value_set(input)?;
+        let set2 = self.p2.value_set(input)?;
         
-        // Try to get values from the first polifunction
-        if let Ok(set1) = self.p1.value_set(input) {
-            result_set.extend(set1);
+        // An empty intersection is a meaningful result, not an error
+        Ok(set1.into_iter().filter(|v| set2.contains(v)).collect())
+    }
+    
+    fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
+                     value: &<Self::Codomain as Codomain>::Element)
+        -> Result<bool, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        // Try to get values from the second polifunction
-        if let Ok(set2) = self.p2.value_set(input) {
-            result_set.extend(set2);
+        Ok(self.p1.contains_value(input, value)? && self.p2.contains_value(input, value)?)
+    }
+    
+    fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<usize, PolifunctionError> {
+        let set = self.value_set(input)?;
+        Ok(set.len())
+    }
+}
+
+/// Difference of two set-valued polifunctions
+///
+/// The difference contains the values of `p1` that are not values of `p2`. It is
+/// defined wherever `p1` is; outside `p2`'s domain nothing is subtracted. An
+/// empty difference (e.g. when `p2` covers every value of `p1`) is a valid
+/// (empty) value, not an error.
+pub struct DifferencePolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    p1: P1,
+    p2: P2,
+}
+
+impl<P1, P2> DifferencePolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+{
+    /// Create a new difference of two set-valued polifunctions
+    pub fn new(p1: P1, p2: P2) -> Self {
+        Self { p1, p2 }
+    }
+}
+
+impl<P1, P2> PolifunctionBase for DifferencePolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P1::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    type Domain = P1::Domain;
+    type Codomain = P1::Codomain;
+    
+    fn evaluate(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<PolifunctionValue<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        let result_set = self.value_set(input)?;
+        Ok(PolifunctionValue::Set(result_set))
+    }
+    
+    fn in_domain(&self, input: &<Self::Domain as Domain>::Element) -> bool {
+        self.p1.in_domain(input)
+    }
+}
+
+impl<P1, P2> SetValuedPolifunction for DifferencePolifunction<P1, P2>
+where
+    P1: SetValuedPolifunction,
+    P2: SetValuedPolifunction<Domain = P1::Domain, Codomain = P1::Codomain>,
+    <P1::Domain as Domain>::Element: Clone + Hash + Eq,
+    <P1::Codomain as Codomain>::Element: Clone + Hash + Eq,
+{
+    fn value_set(&self, input: &<Self::Domain as Domain>::Element)
+        -> Result<HashSet<<Self::Codomain as Codomain>::Element>, PolifunctionError> {
+        if !self.in_domain(input) {
+            return Err(PolifunctionError::DomainError);
         }
         
-        if result_set.is_empty() {
-            return Err(PolifunctionError::DomainError);
+        let set1 = self.p1.value_set(input)?;
+        
+        // Outside p2's domain the subtrahend is the empty set
+        if !self.p2.in_domain(input) {
+            return Ok(set1);
         }
         
-        Ok(result_set)
+        let set2 = self.p2.value_set(input)?;
+        Ok(set1.into_iter().filter(|v| !set2.contains(v)).collect())
     }
     
     fn contains_value(&self, input: &<Self::Domain as Domain>::Element,
@@ -234,28 +847,15 @@ where
             return Err(PolifunctionError::DomainError);
         }
         
-        // Check if either polifunction contains the value
-        match self.p1.contains_value(input, value) {
-            Ok(true) => return Ok(true),
-            Ok(false) => {},
-            Err(e) => {
-                if !matches!(e, PolifunctionError::DomainError) {
-                    return Err(e);
-                }
-            }
+        if !self.p1.contains_value(input, value)? {
+            return Ok(false);
         }
         
-        match self.p2.contains_value(input, value) {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                if matches!(e, PolifunctionError::DomainError) {
-                    // If both functions have domain errors, then it's a domain error
-                    return Err(PolifunctionError::DomainError);
-                } else {
-                    return Err(e);
-                }
-            }
+        if !self.p2.in_domain(input) {
+            return Ok(true);
         }
+        
+        Ok(!self.p2.contains_value(input, value)?)
     }
     
     fn cardinality(&self, input: &<Self::Domain as Domain>::Element)
@@ -264,3 +864,373 @@ where
         Ok(set.len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::interfaces::domains::IntegerRange;
+    use crate::core::interfaces::interval_valued::IntervalValuedPolifunction;
+    use crate::core::interfaces::operations::to_interval;
+    
+    fn set_of(values: &[i64]) -> HashSet<i64> {
+        values.iter().copied().collect()
+    }
+    
+    fn table() -> TabularSetValuedPolifunction<&'static str, i64> {
+        [("a", 1), ("a", 2), ("b", 3)].into_iter().collect()
+    }
+    
+    #[test]
+    fn tabular_mutation_keeps_inputs_until_removed() {
+        let mut table = table();
+        assert!(table.insert("b", 4));
+        assert!(!table.insert("b", 4));
+        assert_eq!(table.value_set(&"b"), Ok(set_of(&[3, 4])));
+        
+        table.insert_input("c");
+        table.insert_input("a");
+        assert_eq!(table.value_set(&"c"), Ok(HashSet::new()));
+        assert_eq!(table.get(&"a"), Some(&set_of(&[1, 2])));
+        
+        // Emptying a set keeps the input in the domain
+        assert!(table.remove(&"b", &3));
+        assert!(table.remove(&"b", &4));
+        assert!(!table.remove(&"b", &4));
+        assert!(!table.remove(&"z", &4));
+        assert!(table.in_domain(&"b"));
+        assert_eq!(table.cardinality(&"b"), Ok(0));
+        
+        assert_eq!(table.remove_input(&"a"), Some(set_of(&[1, 2])));
+        assert_eq!(table.remove_input(&"a"), None);
+        assert!(!table.in_domain(&"a"));
+        assert_eq!(table.evaluate(&"a"), Err(PolifunctionError::DomainError));
+        
+        let mut inputs: Vec<_> = table.inputs().copied().collect();
+        inputs.sort();
+        assert_eq!(inputs, vec!["b", "c"]);
+        assert_eq!(table.pairs().count(), 0);
+    }
+    
+    fn producing(table: &TabularSetValuedPolifunction<&'static str, i64>, v: i64) -> Vec<&'static str> {
+        let mut inputs: Vec<_> = table.inputs_producing(&v).copied().collect();
+        inputs.sort();
+        inputs
+    }
+    
+    #[test]
+    fn tabular_reverse_index_follows_mutation() {
+        let mut table = table();
+        table.insert("b", 1);
+        assert_eq!(producing(&table, 1), vec!["a", "b"]);
+        assert_eq!(producing(&table, 3), vec!["b"]);
+        assert!(producing(&table, 7).is_empty());
+        
+        // Inserting an existing pair or an empty input changes nothing
+        table.insert("b", 1);
+        table.insert_input("c");
+        assert_eq!(producing(&table, 1), vec!["a", "b"]);
+        
+        assert!(table.remove(&"a", &1));
+        assert!(!table.remove(&"a", &1));
+        assert_eq!(producing(&table, 1), vec!["b"]);
+        
+        table.remove_input(&"b");
+        assert!(producing(&table, 1).is_empty());
+        assert!(producing(&table, 3).is_empty());
+        assert_eq!(producing(&table, 2), vec!["a"]);
+        
+        // The index agrees with a scan of the table
+        for (k, v) in table.pairs() {
+            assert!(table.inputs_producing(v).any(|input| input == k));
+        }
+        assert_eq!(table.index.len(), 1);
+    }
+    
+    /// Check that the reverse index equals one rebuilt from the table
+    fn assert_index_consistent(table: &TabularSetValuedPolifunction<&'static str, i64>) {
+        let mut rebuilt: HashMap<i64, HashSet<&'static str>> = HashMap::new();
+        for (k, v) in table.pairs() {
+            rebuilt.entry(*v).or_default().insert(*k);
+        }
+        assert_eq!(table.index, rebuilt);
+    }
+    
+    #[test]
+    fn tabular_remove_and_rename_values_everywhere() {
+        let mut table = table();
+        table.insert("b", 1);
+        
+        assert_eq!(table.rename_value(&1, 2), 2);
+        // "a" already produced 2, so the outputs merged
+        assert_eq!(table.value_set(&"a"), Ok(set_of(&[2])));
+        assert_eq!(table.value_set(&"b"), Ok(set_of(&[2, 3])));
+        assert_eq!(producing(&table, 2), vec!["a", "b"]);
+        assert!(producing(&table, 1).is_empty());
+        assert_eq!(table.rename_value(&7, 8), 0);
+        assert_eq!(table.rename_value(&3, 3), 1);
+        assert_index_consistent(&table);
+        
+        let removed = table.remove_value_everywhere(&2);
+        assert_eq!(removed, ["a", "b"].into_iter().collect());
+        assert_eq!(table.value_set(&"a"), Ok(HashSet::new()));
+        assert!(table.in_domain(&"a"));
+        assert!(table.remove_value_everywhere(&2).is_empty());
+        assert_index_consistent(&table);
+    }
+    
+    #[test]
+    fn tabular_index_matches_a_rebuild_after_random_mutations() {
+        const INPUTS: [&str; 4] = ["a", "b", "c", "d"];
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+        
+        let mut table = TabularSetValuedPolifunction::new();
+        for _ in 0..2000 {
+            let (k, v, w) = (INPUTS[next(4) as usize], next(6) as i64, next(6) as i64);
+            match next(6) {
+                0 | 1 => { table.insert(k, v); },
+                2 => { table.remove(&k, &v); },
+                3 => { table.remove_input(&k); },
+                4 => { table.remove_value_everywhere(&v); },
+                _ => { table.rename_value(&v, w); },
+            }
+            assert_index_consistent(&table);
+        }
+    }
+    
+    #[test]
+    fn tabular_queries() {
+        let table = table();
+        assert_eq!(table.contains_value(&"a", &2), Ok(true));
+        assert_eq!(table.contains_value(&"a", &3), Ok(false));
+        assert_eq!(table.contains_value(&"z", &3), Err(PolifunctionError::DomainError));
+        
+        let mut values: Vec<_> = table.iter_values(&"a").unwrap().collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2]);
+        assert!(table.iter_values(&"z").is_err());
+        
+        assert_eq!(table.cardinality_at_most(&"a", 2), Ok(true));
+        assert_eq!(table.cardinality_at_most(&"a", 1), Ok(false));
+        assert_eq!(table.cardinality_at_most(&"z", 1), Err(PolifunctionError::DomainError));
+        assert_eq!((table.min_value(&"a"), table.max_value(&"a")), (Ok(1), Ok(2)));
+        
+        let mut pairs: Vec<_> = table.pairs().map(|(k, v)| (*k, *v)).collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![("a", 1), ("a", 2), ("b", 3)]);
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tabular_json_round_trip() {
+        let mut table: TabularSetValuedPolifunction<String, i64> = table()
+            .pairs()
+            .map(|(k, v)| (k.to_string(), *v))
+            .collect();
+        table.insert_input("c".to_string());
+        
+        let json = table.to_json().unwrap();
+        let compact: String = json.split_whitespace().collect();
+        assert_eq!(compact, r#"[{"input":"a","values":[1,2]},{"input":"b","values":[3]},{"input":"c","values":[]}]"#);
+        
+        let decoded = TabularSetValuedPolifunction::<String, i64>::from_json(&json).unwrap();
+        assert_eq!(decoded.value_set(&"a".to_string()), Ok(set_of(&[1, 2])));
+        assert_eq!(decoded.value_set(&"c".to_string()), Ok(HashSet::new()));
+        assert_eq!(decoded.to_json().unwrap(), json);
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tabular_json_merges_duplicates_and_checks_codomain() {
+        let json = r#"[{"input": 1, "values": [10]}, {"input": 1, "values": [11]}]"#;
+        let table = TabularSetValuedPolifunction::<i64, i64>::from_json(json).unwrap();
+        assert_eq!(table.value_set(&1), Ok(set_of(&[10, 11])));
+        assert_eq!(table.inputs_producing(&11).collect::<Vec<_>>(), vec![&1]);
+        
+        let codomain = IntegerRange::new(0, 11).unwrap();
+        assert!(TabularSetValuedPolifunction::<i64, i64>::from_json_checked(json, &codomain).is_err());
+        assert!(TabularSetValuedPolifunction::<i64, i64>::from_json("{").is_err());
+    }
+    
+    fn multiples() -> BasicSetValuedPolifunction<IntegerRange, IntegerRange> {
+        BasicSetValuedPolifunction::new(
+            |x: &i64| Ok((1..=3).map(|k| k * x).collect()),
+            IntegerRange::new(0, 5).unwrap(),
+            IntegerRange::new(0, 100).unwrap(),
+        )
+    }
+    
+    fn neighbours() -> BasicSetValuedPolifunction<IntegerRange, IntegerRange> {
+        BasicSetValuedPolifunction::new(
+            |x: &i64| Ok([x - 1, *x, x + 1].into_iter().collect()),
+            IntegerRange::new(1, 10).unwrap(),
+            IntegerRange::new(0, 100).unwrap(),
+        )
+    }
+    
+    #[test]
+    fn min_and_max_values_match_the_to_interval_endpoints() {
+        let spans = to_interval(multiples());
+        for x in 0..5 {
+            let interval = spans.value_interval(&x).unwrap();
+            assert_eq!(multiples().min_value(&x), Ok(interval.lower));
+            assert_eq!(multiples().max_value(&x), Ok(interval.upper));
+        }
+        
+        let mut visited: Vec<_> = multiples().iter_values(&4).unwrap().collect();
+        visited.sort();
+        assert_eq!(visited, vec![4, 8, 12]);
+        
+        // An empty set has no extrema, and to_interval has no interval for it
+        let nothing = DifferencePolifunction::new(neighbours(), neighbours());
+        assert_eq!(nothing.min_value(&5), Err(PolifunctionError::EmptyResult));
+        assert_eq!(nothing.max_value(&5), Err(PolifunctionError::EmptyResult));
+        assert!(to_interval(nothing).value_interval(&5).is_err());
+    }
+    
+    fn assert_send_sync<T: Send + Sync>() {}
+    
+    type SetFn = fn(&i64) -> Result<HashSet<i64>, PolifunctionError>;
+    
+    #[test]
+    fn thread_safe_variants_cross_threads() {
+        assert_send_sync::<BasicSetValuedPolifunction<IntegerRange, IntegerRange, SetFn>>();
+        assert_send_sync::<BasicSetValuedPolifunction<IntegerRange, IntegerRange, SyncSetMapping<IntegerRange, IntegerRange>>>();
+        
+        let offset = 10;
+        let shifted = Arc::new(BasicSetValuedPolifunction::from_fn(
+            move |x: &i64| Ok([x + offset, x - offset].into_iter().collect()),
+            IntegerRange::new(0, 5).unwrap(),
+            IntegerRange::new(-10, 15).unwrap(),
+        ));
+        let divisors = Arc::new(BasicSetValuedPolifunction::new_sync(
+            |x: &i64| Ok((1..=*x).filter(|d| x % d == 0).collect()),
+            IntegerRange::new(1, 100).unwrap(),
+            IntegerRange::new(1, 100).unwrap(),
+        ));
+        
+        let handle = {
+            let (shifted, divisors) = (Arc::clone(&shifted), Arc::clone(&divisors));
+            std::thread::spawn(move || (shifted.value_set(&2), divisors.value_set(&12)))
+        };
+        let (from_other_thread, divisors_of_twelve) = handle.join().unwrap();
+        assert_eq!(from_other_thread, Ok(set_of(&[12, -8])));
+        assert_eq!(divisors_of_twelve, Ok(set_of(&[1, 2, 3, 4, 6, 12])));
+        assert!(shifted.value_set(&5).unwrap_err().is_domain_error());
+    }
+    
+    #[test]
+    fn one_instance_cloned_into_two_combinators() {
+        let p = multiples();
+        let union = UnionPolifunction::new(p.clone(), neighbours());
+        let intersection = IntersectionPolifunction::new(p.clone(), neighbours());
+        
+        assert_eq!(union.value_set(&2), Ok(set_of(&[1, 2, 3, 4, 6])));
+        assert_eq!(intersection.value_set(&2), Ok(set_of(&[2])));
+        assert_eq!(p.value_set(&2), Ok(set_of(&[2, 4, 6])));
+        
+        assert_eq!(
+            format!("{:?}", p),
+            "BasicSetValuedPolifunction { mapping_function: <function>, \
+             domain: IntegerRange { start: 0, end: 5 }, codomain: IntegerRange { start: 0, end: 100 } }",
+        );
+    }
+    
+    #[test]
+    fn union_of_borrowed_and_shared_tables() {
+        let first = table();
+        let second: TabularSetValuedPolifunction<&'static str, i64> = [("a", 5), ("c", 6)].into_iter().collect();
+        
+        let borrowed = UnionPolifunction::new(&first, &second);
+        assert_eq!(borrowed.value_set(&"a"), Ok(set_of(&[1, 2, 5])));
+        assert_eq!(borrowed.value_set(&"c"), Ok(set_of(&[6])));
+        
+        let (first, second) = (Arc::new(first), Arc::new(second));
+        let shared = UnionPolifunction::new(Arc::clone(&first), Arc::clone(&second));
+        let mixed = UnionPolifunction::new(first.as_ref(), Box::new(Arc::clone(&second)));
+        assert_eq!(shared.value_set(&"b"), Ok(set_of(&[3])));
+        assert_eq!(mixed.value_set(&"a"), shared.value_set(&"a"));
+        assert_eq!(Arc::strong_count(&second), 3);
+    }
+    
+    #[test]
+    fn intersection_is_defined_where_both_operands_are() {
+        let both = IntersectionPolifunction::new(multiples(), neighbours());
+        assert_eq!(both.value_set(&2), Ok(set_of(&[2])));
+        assert_eq!(both.evaluate(&2), Ok(PolifunctionValue::Set(set_of(&[2]))));
+        assert_eq!(both.contains_value(&3, &3), Ok(true));
+        assert_eq!(both.contains_value(&3, &6), Ok(false));
+        
+        assert_eq!(both.cardinality(&4), Ok(1));
+        assert!(!both.in_domain(&0) && !both.in_domain(&7));
+        assert!(both.value_set(&0).unwrap_err().is_domain_error());
+        
+        // An empty intersection is a value, not an error
+        let disjoint = IntersectionPolifunction::new(neighbours(), DifferencePolifunction::new(neighbours(), neighbours()));
+        assert_eq!(disjoint.value_set(&5), Ok(HashSet::new()));
+    }
+    
+    #[test]
+    fn union_skips_operands_outside_their_domain() {
+        let union = UnionPolifunction::new(multiples(), neighbours());
+        assert_eq!(union.value_set(&2), Ok(set_of(&[1, 2, 3, 4, 6])));
+        assert_eq!(union.value_set(&0), Ok(set_of(&[0])));
+        assert_eq!(union.value_set(&8), Ok(set_of(&[7, 8, 9])));
+        assert_eq!(union.evaluate(&0), Ok(PolifunctionValue::Single(0)));
+        
+        assert_eq!(union.contains_value(&0, &0), Ok(true));
+        assert_eq!(union.contains_value(&0, &1), Ok(false));
+        assert_eq!(union.contains_value(&8, &9), Ok(true));
+        
+        let error = union.value_set(&11).unwrap_err();
+        assert!(error.is_domain_error());
+        assert_eq!(error.to_string(), "Input 11 is outside the function's domain");
+        assert!(union.contains_value(&11, &0).unwrap_err().is_domain_error());
+    }
+    
+    #[test]
+    fn union_reports_operand_failures_from_every_method() {
+        let failing = BasicSetValuedPolifunction::new(
+            |_: &i64| Err(PolifunctionError::ComputationError),
+            IntegerRange::new(1, 10).unwrap(),
+            IntegerRange::new(0, 100).unwrap(),
+        );
+        let union = UnionPolifunction::new(multiples(), failing);
+        
+        let expected = "second operand of union: Error during computation";
+        assert_eq!(union.value_set(&2).unwrap_err().to_string(), expected);
+        assert_eq!(union.evaluate(&2).unwrap_err().to_string(), expected);
+        assert_eq!(union.contains_value(&2, &5).unwrap_err().to_string(), expected);
+        assert_eq!(union.contains_value(&2, &4), Ok(true));
+        
+        // Only the first operand is defined at 0
+        assert_eq!(union.value_set(&0), Ok(set_of(&[0])));
+    }
+    
+    #[test]
+    fn domain_errors_name_the_input() {
+        let error = multiples().value_set(&9).unwrap_err();
+        assert_eq!(error, PolifunctionError::DomainError);
+        assert_eq!(error.to_string(), "Input 9 is outside the function's domain");
+        assert_eq!(multiples().evaluate(&-1).unwrap_err().to_string(), "Input -1 is outside the function's domain");
+    }
+    
+    #[test]
+    fn difference_subtracts_only_inside_the_second_domain() {
+        let difference = DifferencePolifunction::new(multiples(), neighbours());
+        assert_eq!(difference.value_set(&2), Ok(set_of(&[4, 6])));
+        assert_eq!(difference.value_set(&1), Ok(set_of(&[3])));
+        
+        // 0 is outside the domain of `neighbours`, so nothing is removed
+        assert_eq!(difference.value_set(&0), Ok(set_of(&[0])));
+        assert!(difference.value_set(&7).unwrap_err().is_domain_error());
+        
+        let nothing_left = DifferencePolifunction::new(neighbours(), neighbours());
+        assert_eq!(nothing_left.cardinality(&5), Ok(0));
+    }
+}